@@ -0,0 +1,43 @@
+//! What the benchmarks share.
+
+use aoc_2024::Day;
+
+/// Returns the inputs to benchmark `day` on, each with the parts to solve for
+/// it and its number if it's an example: the day's real input if it can be
+/// found, and otherwise the first example that documents the answer to each
+/// part.
+///
+/// Parts are only solved for examples that document their answers, since the
+/// solutions can assume things of the real inputs that examples for the other
+/// part don't satisfy.
+pub fn inputs(day: &Day) -> Vec<(String, Vec<usize>, Option<usize>)> {
+    if let Some(input) = aoc_2024::input::read_year(day.year, day.number) {
+        return vec![(input, vec![1, 2], None)];
+    }
+
+    let examples = day.examples.map_or_else(Vec::new, |examples| examples());
+    let mut inputs: Vec<(String, Vec<usize>, Option<usize>)> = Vec::new();
+
+    for part in 1..=2 {
+        let Some(index) = examples
+            .iter()
+            .position(|example| example.answers[part - 1].is_some())
+        else {
+            continue;
+        };
+
+        match inputs
+            .iter_mut()
+            .find(|(.., number)| *number == Some(index + 1))
+        {
+            Some((_, parts, _)) => parts.push(part),
+            None => inputs.push((
+                examples[index].input.to_string(),
+                vec![part],
+                Some(index + 1),
+            )),
+        }
+    }
+
+    inputs
+}
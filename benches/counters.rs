@@ -0,0 +1,309 @@
+//! Counts the instructions retired and the cache misses in parsing and solving
+//! each day, with the CPU's performance counters.
+//!
+//! Unlike wall-clock times, these barely change from one run to the next, so a
+//! regression shows up even on a noisy machine. Each run is compared against
+//! the previous one, whose counts are kept in Cargo's target directory. Days
+//! are solved sequentially, since the counters only follow the calling thread,
+//! and are picked out like criterion's benchmarks, e.g. with
+//! `cargo bench --bench counters -- day02`.
+//!
+//! The counters are read with Linux's `perf_event_open`, which needs
+//! `perf_event_paranoid` to be at most 2, and a CPU (or virtual machine) that
+//! exposes them.
+
+use std::{collections::HashMap, hint::black_box, path::PathBuf};
+
+use aoc_2024::DAYS;
+
+mod common;
+
+/// The counts for one benchmark.
+#[derive(Debug, Clone, Copy, Default)]
+struct Counts {
+    instructions: u64,
+    cache_references: u64,
+    cache_misses: u64,
+}
+
+#[cfg(target_os = "linux")]
+mod perf {
+    use std::{
+        fs::File,
+        io::{self, Read},
+        os::fd::{AsRawFd, FromRawFd},
+    };
+
+    use super::Counts;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_REFERENCES: u64 = 2;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+
+    const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: libc::c_ulong = 0x2401;
+    const PERF_EVENT_IOC_RESET: libc::c_ulong = 0x2403;
+
+    /// The flags that start a counter disabled, and have it count only this
+    /// process in user space.
+    const DISABLED: u64 = 1 << 0;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    /// The fifth version of `struct perf_event_attr`, which later kernels
+    /// still accept.
+    #[repr(C)]
+    #[derive(Default)]
+    struct Attr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+        config2: u64,
+        branch_sample_type: u64,
+        sample_regs_user: u64,
+        sample_stack_user: u32,
+        clockid: i32,
+        sample_regs_intr: u64,
+        aux_watermark: u32,
+        sample_max_stack: u16,
+        reserved: u16,
+    }
+
+    /// One of the CPU's counters, counting events on the calling thread.
+    struct Counter(File);
+
+    impl Counter {
+        fn open(config: u64) -> io::Result<Self> {
+            let attr = Attr {
+                kind: PERF_TYPE_HARDWARE,
+                size: size_of::<Attr>() as u32,
+                config,
+                flags: DISABLED | EXCLUDE_KERNEL | EXCLUDE_HV,
+                ..Attr::default()
+            };
+
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const Attr,
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+
+            match fd {
+                -1 => Err(io::Error::last_os_error()),
+                fd => Ok(Self(unsafe { File::from_raw_fd(fd as i32) })),
+            }
+        }
+
+        fn ioctl(&self, request: libc::c_ulong) -> io::Result<()> {
+            match unsafe { libc::ioctl(self.0.as_raw_fd(), request, 0) } {
+                -1 => Err(io::Error::last_os_error()),
+                _ => Ok(()),
+            }
+        }
+
+        fn read(&mut self) -> io::Result<u64> {
+            let mut value = [0; 8];
+            self.0.read_exact(&mut value)?;
+            Ok(u64::from_ne_bytes(value))
+        }
+    }
+
+    /// The counters behind [`Counts`].
+    pub struct Counters([Counter; 3]);
+
+    impl Counters {
+        pub fn open() -> io::Result<Self> {
+            Ok(Self([
+                Counter::open(PERF_COUNT_HW_INSTRUCTIONS)?,
+                Counter::open(PERF_COUNT_HW_CACHE_REFERENCES)?,
+                Counter::open(PERF_COUNT_HW_CACHE_MISSES)?,
+            ]))
+        }
+
+        /// Counts the events while running `f`.
+        pub fn count<T>(&mut self, f: impl FnOnce() -> T) -> io::Result<(T, Counts)> {
+            for counter in &self.0 {
+                counter.ioctl(PERF_EVENT_IOC_RESET)?;
+                counter.ioctl(PERF_EVENT_IOC_ENABLE)?;
+            }
+
+            let result = f();
+
+            for counter in &self.0 {
+                counter.ioctl(PERF_EVENT_IOC_DISABLE)?;
+            }
+
+            let [instructions, cache_references, cache_misses] = &mut self.0;
+            let counts = Counts {
+                instructions: instructions.read()?,
+                cache_references: cache_references.read()?,
+                cache_misses: cache_misses.read()?,
+            };
+
+            Ok((result, counts))
+        }
+    }
+}
+
+/// Where the latest counts of each benchmark are kept, as a line of
+/// tab-separated values for each.
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("counters.tsv")
+}
+
+fn read_baseline() -> HashMap<String, Counts> {
+    let contents = std::fs::read_to_string(baseline_path()).unwrap_or_default();
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.next()?.to_string();
+            let mut count = || fields.next()?.parse().ok();
+
+            let counts = Counts {
+                instructions: count()?,
+                cache_references: count()?,
+                cache_misses: count()?,
+            };
+
+            Some((name, counts))
+        })
+        .collect()
+}
+
+/// Formats `count`, and how it changed from `previous` if there is one.
+fn format_count(count: u64, previous: Option<u64>) -> String {
+    match previous {
+        Some(0) | None => format!("{count:>14}          "),
+        Some(previous) => {
+            let change = (count as f64 - previous as f64) / previous as f64 * 100.0;
+            format!("{count:>14} ({change:>+6.2}%)")
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn main() {
+    // the arguments that aren't flags pick out benchmarks, like criterion's
+    let filters = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect::<Vec<_>>();
+
+    let mut counters = match perf::Counters::open() {
+        Ok(counters) => counters,
+        Err(err) => {
+            eprintln!("skipping the counters, which can't be read here: {err}");
+            return;
+        }
+    };
+
+    aoc_2024::par::set_sequential(true);
+
+    let baseline = read_baseline();
+    let mut results = Vec::new();
+
+    println!(
+        "{:<30}  {:>24}  {:>24}  {:>24}",
+        "benchmark", "instructions", "cache references", "cache misses"
+    );
+
+    let mut report = |name: String, counts: Counts| {
+        let previous = baseline.get(&name);
+        println!(
+            "{name:<30}  {}  {}  {}",
+            format_count(counts.instructions, previous.map(|p| p.instructions)),
+            format_count(
+                counts.cache_references,
+                previous.map(|p| p.cache_references)
+            ),
+            format_count(counts.cache_misses, previous.map(|p| p.cache_misses)),
+        );
+
+        results.push((name, counts));
+    };
+
+    for day in DAYS {
+        let group = match day.year {
+            2024 => format!("day{:02}", day.number),
+            year => format!("y{year}_day{:02}", day.number),
+        };
+
+        if !filters.is_empty() && !filters.iter().any(|filter| group.contains(filter.as_str())) {
+            continue;
+        }
+
+        for (input, parts, example) in common::inputs(day) {
+            let suffix = example.map_or_else(String::new, |number| format!(" (example {number})"));
+
+            let (parsed, counts) = counters
+                .count(|| (day.parse)(&input))
+                .expect("failed to read the counters");
+
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    eprintln!("skipping {err}");
+                    continue;
+                }
+            };
+
+            report(format!("{group}/parse{suffix}"), counts);
+
+            for (number, part) in (1..).zip([Some(day.part_1), day.part_2]) {
+                let Some(part) = part.filter(|_| parts.contains(&number)) else {
+                    continue;
+                };
+
+                // a first run warms up anything lazily initialized, so that
+                // the counted run does the same work every time
+                black_box(part(&*parsed));
+
+                let (_, counts) = counters
+                    .count(|| black_box(part(&*parsed)))
+                    .expect("failed to read the counters");
+
+                report(format!("{group}/part {number}{suffix}"), counts);
+            }
+        }
+    }
+
+    // benchmarks that were filtered out keep their previous counts
+    let mut baseline = baseline;
+    baseline.extend(results);
+
+    let mut lines = baseline
+        .iter()
+        .map(|(name, counts)| {
+            format!(
+                "{name}\t{}\t{}\t{}\n",
+                counts.instructions, counts.cache_references, counts.cache_misses
+            )
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    let contents = lines.concat();
+
+    let path = baseline_path();
+    std::fs::write(&path, contents)
+        .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() {
+    eprintln!("skipping the counters, which are only read on Linux");
+}
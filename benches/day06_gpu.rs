@@ -0,0 +1,23 @@
+use aoc_2024::day06::{gpu::Gpu, parse, part2};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Compares the CPU and GPU versions of part 2, skipping the GPU if there is
+/// no adapter available.
+pub fn part_2_benchmark(c: &mut Criterion) {
+    let Some(input) = aoc_2024::input::read(6) else {
+        return;
+    };
+
+    let area = parse(&input).unwrap();
+    let mut group = c.benchmark_group("part 2");
+    group.bench_function("cpu", |b| b.iter(|| part2(&area)));
+
+    if let Some(gpu) = Gpu::new() {
+        group.bench_function("gpu", |b| b.iter(|| gpu.count_possible_loops(&area)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(day06_gpu, part_2_benchmark);
+criterion_main!(day06_gpu);
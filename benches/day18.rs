@@ -0,0 +1,45 @@
+use aoc_2024::day18::{MemorySpace, SIZE};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Reads the input through the configured input directory.
+fn input() -> String {
+    aoc_2024::input::read(18).expect("missing input for day 18")
+}
+
+pub fn binary_search_benchmark(c: &mut Criterion) {
+    let space = MemorySpace::parse(&input(), SIZE).unwrap();
+
+    c.bench_function("part 2 (binary search)", |b| {
+        b.iter(|| {
+            space.first_blocking_byte();
+        })
+    });
+}
+
+pub fn replanning_benchmark(c: &mut Criterion) {
+    let space = MemorySpace::parse(&input(), SIZE).unwrap();
+
+    c.bench_function("part 2 (replanning)", |b| {
+        b.iter(|| {
+            space.first_blocking_byte_replanning();
+        })
+    });
+}
+
+pub fn union_find_benchmark(c: &mut Criterion) {
+    let space = MemorySpace::parse(&input(), SIZE).unwrap();
+
+    c.bench_function("part 2 (union-find)", |b| {
+        b.iter(|| {
+            space.first_blocking_byte_union_find();
+        })
+    });
+}
+
+criterion_group!(
+    day18,
+    binary_search_benchmark,
+    replanning_benchmark,
+    union_find_benchmark
+);
+criterion_main!(day18);
@@ -0,0 +1,40 @@
+use aoc_2024::day19::Onsen;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Reads the input through the configured input directory.
+fn input() -> String {
+    aoc_2024::input::read(19).expect("missing input for day 19")
+}
+
+pub fn scaling_benchmark(c: &mut Criterion) {
+    let onsen = input().parse::<Onsen>().unwrap();
+    let mut group = c.benchmark_group("arrangement counts");
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            onsen.arrangement_counts_sequential();
+        })
+    });
+
+    let max_threads = std::thread::available_parallelism().map_or(1, usize::from);
+    let thread_counts =
+        std::iter::successors(Some(1), |n| Some(n * 2)).take_while(|&n| n <= max_threads);
+
+    for threads in thread_counts {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+
+        group.bench_with_input(BenchmarkId::new("parallel", threads), &pool, |b, pool| {
+            b.iter(|| {
+                pool.install(|| onsen.arrangement_counts());
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(day19, scaling_benchmark);
+criterion_main!(day19);
@@ -0,0 +1,31 @@
+use aoc_2024::day22::{parse, sum_of_nth_secrets_scalar, SECRETS_PER_DAY};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Reads the input through the configured input directory.
+fn input() -> String {
+    aoc_2024::input::read(22).expect("missing input for day 22")
+}
+
+pub fn part_1_benchmark(c: &mut Criterion) {
+    let secrets = parse(&input()).unwrap();
+    let mut group = c.benchmark_group("part 1");
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| sum_of_nth_secrets_scalar(&secrets, SECRETS_PER_DAY))
+    });
+
+    #[cfg(feature = "simd")]
+    group.bench_function("batched", |b| {
+        b.iter(|| aoc_2024::day22::sum_of_nth_secrets_batched(&secrets, SECRETS_PER_DAY))
+    });
+
+    group.finish();
+}
+
+pub fn part_2_benchmark(c: &mut Criterion) {
+    let secrets = parse(&input()).unwrap();
+    c.bench_function("part 2", |b| b.iter(|| aoc_2024::day22::part2(&secrets)));
+}
+
+criterion_group!(day22, part_1_benchmark, part_2_benchmark);
+criterion_main!(day22);
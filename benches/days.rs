@@ -0,0 +1,91 @@
+use std::{hint::black_box, path::PathBuf, time::Instant};
+
+use aoc_2024::{timings::Timing, DAYS};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod common;
+
+/// How many samples criterion takes of each benchmark.
+const SAMPLES: usize = 100;
+
+/// Benchmarks the parsing and both parts of every registered day, in a group
+/// per day so that `cargo bench -- day02` picks out one of them. Days whose
+/// input can't be found are benchmarked on their examples instead, with
+/// `(example N)` after the name of each benchmark.
+///
+/// If `AOC_TIMINGS_CSV` is set, the timings of each part on a real input are
+/// also appended to the CSV file it names.
+pub fn days_benchmark(c: &mut Criterion) {
+    let csv = std::env::var_os("AOC_TIMINGS_CSV").map(PathBuf::from);
+    let mut timings = Vec::new();
+
+    for day in DAYS {
+        let inputs = common::inputs(day);
+        if inputs.is_empty() {
+            continue;
+        }
+
+        let mut group = c.benchmark_group(match day.year {
+            2024 => format!("day{:02}", day.number),
+            year => format!("y{year}_day{:02}", day.number),
+        });
+        group.sample_size(SAMPLES);
+
+        for (input, parts, example) in inputs {
+            let parsed = match (day.parse)(&input) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    eprintln!("skipping {err}");
+                    continue;
+                }
+            };
+
+            let suffix = example.map_or_else(String::new, |number| format!(" (example {number})"));
+            group.bench_function(format!("parse{suffix}"), |b| b.iter(|| (day.parse)(&input)));
+
+            for (number, part) in (1..).zip([Some(day.part_1), day.part_2]) {
+                let Some(part) = part.filter(|_| parts.contains(&number)) else {
+                    continue;
+                };
+
+                let mut samples = Vec::new();
+                group.bench_function(format!("part {number}{suffix}"), |b| {
+                    b.iter_custom(|iters| {
+                        let start = Instant::now();
+                        for _ in 0..iters {
+                            black_box(part(&*parsed));
+                        }
+
+                        let elapsed = start.elapsed();
+                        samples.push(elapsed / iters as u32);
+                        elapsed
+                    })
+                });
+
+                if example.is_some() {
+                    continue;
+                }
+
+                // the warm-up comes first, so the last samples are the measured ones
+                let measured = &samples[samples.len().saturating_sub(SAMPLES)..];
+                timings.extend(Timing::from_samples(
+                    day.year,
+                    day.number,
+                    number as u8,
+                    measured,
+                    None,
+                ));
+            }
+        }
+
+        group.finish();
+    }
+
+    if let Some(path) = csv {
+        aoc_2024::timings::append_csv(&path, &timings)
+            .unwrap_or_else(|err| panic!("failed to append to {}: {err}", path.display()));
+    }
+}
+
+criterion_group!(days, days_benchmark);
+criterion_main!(days);
@@ -0,0 +1,345 @@
+//! Generates the day registry by scanning `src/` for `dayNN.rs` files, which
+//! are the days of 2024, and `src/yYYYY/` for the days of earlier years.
+//!
+//! Each day must have a `pub fn parse` that returns a `Result` with an
+//! `AocError`, and its part functions (which take a reference to whatever
+//! `parse` parses) are found by their `/// Computes the solution to part N.`
+//! doc comments, so adding a new day only requires creating its file.
+//! Likewise, a function documented with `/// Describes the structure of the
+//! input.` is registered as the day's statistics.
+//!
+//! The `dayNN` features only select days of 2024; earlier years are compiled
+//! under `all-days`.
+//!
+//! With the `ffi` feature, this also generates the C header for `src/ffi.rs`,
+//! from its `#[repr(C)]` enums and `extern "C"` functions.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+/// The year whose days live directly in `src/`.
+const YEAR: u16 = 2024;
+
+/// A day module found in `src/`.
+struct DayFile {
+    year: u16,
+    number: u8,
+    path: String,
+    part_1: String,
+    part_2: Option<String>,
+    stats: Option<String>,
+    /// Whether the day is declared with `aoc_day!`, which generates its
+    /// `examples` function.
+    examples: bool,
+}
+
+/// Finds the name of the free function documented as the solution to `part`.
+fn find_part(source: &str, part: u8) -> Option<String> {
+    find_documented(
+        source,
+        &format!("/// Computes the solution to part {part}."),
+    )
+}
+
+/// Finds the name of the free function whose doc comment starts with `marker`.
+fn find_documented(source: &str, marker: &str) -> Option<String> {
+    let mut lines = source.lines();
+
+    lines.find(|line| *line == marker)?;
+    let signature = lines.find(|line| !line.starts_with("///"))?;
+    let name = signature.strip_prefix("pub fn ")?.split('(').next()?;
+
+    Some(name.to_string())
+}
+
+/// Formats an optional expression as Rust source.
+fn optional(expression: Option<String>) -> String {
+    match expression {
+        Some(expression) => format!("Some({expression})"),
+        None => "None".to_string(),
+    }
+}
+
+/// Converts a Rust type name to `SCREAMING_SNAKE_CASE`.
+fn screaming(name: &str) -> String {
+    let mut screaming = String::new();
+
+    for (index, c) in name.char_indices() {
+        if index > 0 && c.is_ascii_uppercase() {
+            screaming.push('_');
+        }
+        screaming.push(c.to_ascii_uppercase());
+    }
+
+    screaming
+}
+
+/// Formats doc comment lines as a C comment, indented by `indent`.
+fn c_comment(docs: &[String], indent: &str) -> String {
+    let docs = docs
+        .iter()
+        .map(|line| line.replace("[`", "`").replace("`]", "`"))
+        .collect::<Vec<_>>();
+
+    match docs.as_slice() {
+        [] => String::new(),
+        [line] => format!("{indent}/** {line} */\n"),
+        lines => {
+            let mut comment = format!("{indent}/**\n");
+            for line in lines {
+                match line.is_empty() {
+                    true => writeln!(comment, "{indent} *").unwrap(),
+                    false => writeln!(comment, "{indent} * {line}").unwrap(),
+                }
+            }
+            comment + &format!("{indent} */\n")
+        }
+    }
+}
+
+/// Translates one of the Rust types that the C API uses to C.
+fn c_type(ty: &str, enums: &[String]) -> String {
+    match ty {
+        "u8" => "uint8_t".to_string(),
+        "usize" => "size_t".to_string(),
+        "*const u8" => "const uint8_t *".to_string(),
+        "*mut c_char" => "char *".to_string(),
+        ty if enums.iter().any(|name| name == ty) => ty.to_string(),
+        ty => panic!("src/ffi.rs uses `{ty}`, which has no C translation"),
+    }
+}
+
+/// Generates the C header for the API in `source` (which is `src/ffi.rs`).
+fn c_header(source: &str) -> String {
+    let mut enums = Vec::new();
+    let mut items = String::new();
+    let mut docs = Vec::new();
+    let mut lines = source.lines();
+
+    while let Some(line) = lines.next() {
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(doc.trim().to_string());
+            continue;
+        }
+
+        if line.starts_with("#[") {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix("pub enum ")
+            .and_then(|rest| rest.strip_suffix(" {"))
+        {
+            items += &c_comment(&docs, "");
+            writeln!(items, "typedef enum {name} {{").unwrap();
+            docs.clear();
+
+            for line in lines.by_ref().take_while(|line| *line != "}") {
+                if let Some(doc) = line.trim().strip_prefix("///") {
+                    docs.push(doc.trim().to_string());
+                } else if let Some((variant, value)) = line.trim().split_once(" = ") {
+                    items += &c_comment(&docs, "  ");
+                    let value = value.trim_end_matches(',');
+                    writeln!(
+                        items,
+                        "  {}_{} = {value},",
+                        screaming(name),
+                        screaming(variant)
+                    )
+                    .unwrap();
+                    docs.clear();
+                }
+            }
+
+            writeln!(items, "}} {name};\n").unwrap();
+            enums.push(name.to_string());
+        } else if let Some(rest) = line.strip_prefix("pub unsafe extern \"C\" fn ") {
+            // the signature may be split over several lines
+            let mut signature = rest.to_string();
+            while !signature.ends_with('{') {
+                signature += lines.next().unwrap().trim();
+            }
+
+            let (name, rest) = signature.split_once('(').unwrap();
+            let (params, rest) = rest.split_once(')').unwrap();
+            let ret = rest.strip_prefix(" -> ").map_or("void".to_string(), |ret| {
+                c_type(ret.trim_end_matches('{').trim(), &enums)
+            });
+
+            let params = params
+                .split(',')
+                .map(str::trim)
+                .filter(|param| !param.is_empty())
+                .map(|param| {
+                    let (name, ty) = param.split_once(": ").unwrap();
+                    let ty = c_type(ty, &enums);
+                    match ty.ends_with('*') {
+                        true => format!("{ty}{name}"),
+                        false => format!("{ty} {name}"),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let prefix = format!("{ret} {name}(");
+            let separator = format!(",\n{}", " ".repeat(prefix.len()));
+            items += &c_comment(&docs, "");
+            writeln!(items, "{prefix}{});\n", params.join(&separator)).unwrap();
+        }
+
+        docs.clear();
+    }
+
+    format!(
+        "/* Generated by build.rs from src/ffi.rs, so don't edit it by hand. */\n\n\
+         #ifndef AOC2024_H\n\
+         #define AOC2024_H\n\n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {{\n\
+         #endif\n\n\
+         {items}\
+         #ifdef __cplusplus\n\
+         }}\n\
+         #endif\n\n\
+         #endif /* AOC2024_H */\n"
+    )
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src = Path::new(&manifest_dir).join("src");
+    println!("cargo:rerun-if-changed=src");
+
+    let all_days = env::var_os("CARGO_FEATURE_ALL_DAYS").is_some();
+    let mut days = Vec::new();
+
+    // the days of 2024, followed by the directory of each earlier year
+    let mut dirs = vec![(YEAR, src.clone())];
+
+    if all_days {
+        for entry in fs::read_dir(&src).unwrap() {
+            let path = entry.unwrap().path();
+            let year = path
+                .file_name()
+                .and_then(|name| name.to_str()?.strip_prefix('y')?.parse::<u16>().ok());
+
+            if let Some(year) = year.filter(|_| path.is_dir()) {
+                dirs.push((year, path));
+            }
+        }
+    }
+
+    for (year, dir) in dirs {
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|extension| extension != "rs") {
+                continue;
+            }
+
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            let Some(number) = stem
+                .strip_prefix("day")
+                .filter(|digits| digits.len() == 2)
+                .and_then(|digits| digits.parse::<u8>().ok())
+            else {
+                continue;
+            };
+
+            // days without their own feature are still compiled under `all-days`
+            if !all_days && env::var_os(format!("CARGO_FEATURE_DAY{number:02}")).is_none() {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path).unwrap();
+            assert!(
+                source.contains("\npub fn parse("),
+                "{year} {stem}.rs has no parse function"
+            );
+
+            days.push(DayFile {
+                year,
+                number,
+                path: path.to_str().unwrap().to_string(),
+                part_1: find_part(&source, 1).unwrap_or_else(|| {
+                    panic!("{year} {stem}.rs has no documented part 1 function")
+                }),
+                part_2: find_part(&source, 2),
+                stats: find_documented(&source, "/// Describes the structure of the input."),
+                examples: source.contains("\naoc_day! {"),
+            });
+        }
+    }
+
+    days.sort_by_key(|day| (day.year, day.number));
+
+    let mut output = String::new();
+
+    for day in days.iter().filter(|day| day.year == YEAR) {
+        writeln!(output, "#[path = {:?}]", day.path).unwrap();
+        writeln!(output, "pub mod day{:02};", day.number).unwrap();
+    }
+
+    // earlier years get a module of their own, like `y2023::day01`
+    let mut years = days
+        .iter()
+        .map(|day| day.year)
+        .filter(|&year| year != YEAR)
+        .collect::<Vec<_>>();
+    years.dedup();
+
+    for year in years {
+        writeln!(output, "\npub mod y{year} {{").unwrap();
+
+        for day in days.iter().filter(|day| day.year == year) {
+            writeln!(output, "    #[path = {:?}]", day.path).unwrap();
+            writeln!(output, "    pub mod day{:02};", day.number).unwrap();
+        }
+
+        output.push_str("}\n");
+    }
+
+    output.push_str("\n/// Every day compiled into this build, in order of year and then day.\n");
+    output.push_str("pub const DAYS: &[Day] = &[\n");
+
+    for day in &days {
+        let module = match day.year {
+            YEAR => format!("day{:02}", day.number),
+            year => format!("y{year}::day{:02}", day.number),
+        };
+
+        // each function recovers the parsed input's concrete type from the
+        // day's `parse` function
+        let call =
+            |name: &String| format!("|parsed| {module}::{name}(downcast(parsed, {module}::parse))");
+
+        // parse errors are tagged with the day they came from
+        let parse = format!(
+            "|input| match {module}::parse(input) {{ Ok(parsed) => Ok(Box::new(parsed)), Err(error) => Err(error.in_day({}, {})) }}",
+            day.year, day.number,
+        );
+        let part_1 = call(&day.part_1);
+        let part_2 = optional(day.part_2.as_ref().map(call));
+        let stats = optional(day.stats.as_ref().map(call));
+        let examples = optional(day.examples.then(|| format!("{module}::examples")));
+
+        writeln!(
+            output,
+            "    Day {{ year: {}, number: {}, parse: {parse}, part_1: {part_1}, part_2: {part_2}, stats: {stats}, examples: {examples} }},",
+            day.year, day.number,
+        )
+        .unwrap();
+    }
+
+    output.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), output).unwrap();
+
+    if env::var_os("CARGO_FEATURE_FFI").is_some() {
+        let source = fs::read_to_string(src.join("ffi.rs")).unwrap();
+        fs::write(Path::new(&out_dir).join("aoc2024.h"), c_header(&source)).unwrap();
+    }
+}
@@ -0,0 +1,35 @@
+//! Feeds arbitrary input to the parser of every registered day, which should
+//! reject anything malformed with an error rather than panicking.
+//!
+//! The first byte picks the day, and the rest is the input. Run with
+//! `cargo fuzz run parse` from the repository root.
+
+#![no_main]
+
+use aoc_2024::{error::AocError, DAYS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, input)) = data.split_first() else {
+        return;
+    };
+
+    let Ok(input) = std::str::from_utf8(input) else {
+        return;
+    };
+
+    let day = &DAYS[selector as usize % DAYS.len()];
+
+    // an error must point somewhere in the input (or just past its end)
+    if let Err(AocError::Day { error, .. }) = (day.parse)(input) {
+        if let AocError::Parse { line, column, .. } = *error {
+            let text = input.split('\n').nth(line - 1);
+            assert!(
+                text.is_some_and(|text| column <= text.len() + 1),
+                "{} day {} reported line {line}, column {column}",
+                day.year,
+                day.number
+            );
+        }
+    }
+});
@@ -0,0 +1,55 @@
+/* Generated by build.rs from src/ffi.rs, so don't edit it by hand. */
+
+#ifndef AOC2024_H
+#define AOC2024_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** The outcome of a call to `aoc2024_solve`. */
+typedef enum AocStatus {
+  /** The answer was written to the output buffer. */
+  AOC_STATUS_OK = 0,
+  /** The day isn't compiled into the library. */
+  AOC_STATUS_NO_SUCH_DAY = 1,
+  /** The day has no such part. */
+  AOC_STATUS_NO_SUCH_PART = 2,
+  /** The input isn't valid UTF-8. */
+  AOC_STATUS_INVALID_UTF8 = 3,
+  /** The input couldn't be parsed, and the output buffer holds the reason. */
+  AOC_STATUS_INVALID_INPUT = 4,
+  /** The output buffer is too small for the answer. */
+  AOC_STATUS_BUFFER_TOO_SMALL = 5,
+  /** The solver panicked, which it should only do on an input it can't solve. */
+  AOC_STATUS_PANICKED = 6,
+} AocStatus;
+
+/**
+ * Solves `part` of `day` (of 2024) for the `len` bytes of input at `input`,
+ * and writes the answer to the `out_len` bytes at `out_buf` as a
+ * nul-terminated string.
+ *
+ * If the input can't be parsed, the reason is written instead, truncated to
+ * fit. After any other failure the buffer holds an empty string.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes, and `out_buf` to `out_len`
+ * writable bytes. Either may be null if its length is zero.
+ */
+AocStatus aoc2024_solve(uint8_t day,
+                        uint8_t part,
+                        const uint8_t *input,
+                        size_t len,
+                        char *out_buf,
+                        size_t out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC2024_H */
@@ -0,0 +1,170 @@
+//! Known-correct answers for the puzzle inputs, so that every day can be
+//! checked at once rather than only by the golden tests of the days that have
+//! them.
+//!
+//! The answers are kept in `answers.toml` next to the inputs (see
+//! [`Answers::path`]), with a table for each day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 2066446
+//! part2 = 24931009
+//!
+//! [day17]
+//! part1 = "7,3,5,7,5,7,4,3,0"
+//! ```
+//!
+//! Answers are compared as strings, so numbers can be written either way.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::config::{parse_integer, parse_string};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswersError {
+    /// The answers file couldn't be read.
+    Io(PathBuf, String),
+    /// A line isn't a `[dayNN]` header or a `partN = answer` pair within one.
+    Malformed(usize),
+    /// A line has an answer that isn't a string or an integer.
+    InvalidValue(usize),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "failed to read {}: {err}", path.display()),
+            Self::Malformed(line) => {
+                write!(f, "line {line} of the answers is not a day or a part")
+            }
+            Self::InvalidValue(line) => write!(f, "line {line} of the answers has no valid answer"),
+        }
+    }
+}
+
+impl Answers {
+    /// Returns the path of the answers for the configured year, which are kept
+    /// with its inputs.
+    pub fn path() -> PathBuf {
+        // the inputs are in a directory for the year, except for 2024's when
+        // they predate those
+        crate::input::local_path(1).with_file_name("answers.toml")
+    }
+
+    /// Loads the answers in the file at `path`.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| AnswersError::Io(path.to_path_buf(), err.to_string()))?;
+
+        contents.parse()
+    }
+
+    /// Returns the known answer to `part` of `day`, if there is one.
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Returns the number of known answers.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Returns whether there are no known answers.
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = AnswersError::Malformed(index + 1);
+
+            if let Some(header) = line.strip_prefix('[') {
+                let (name, _) = header.split_once(']').ok_or(malformed.clone())?;
+                let number = name
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|digits| digits.parse::<u8>().ok())
+                    .filter(|day| (1..=25).contains(day))
+                    .ok_or(malformed)?;
+
+                day = Some(number);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(malformed.clone())?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(malformed),
+            };
+
+            let answer = parse_string(value.trim())
+                .or_else(|| parse_integer::<i128>(value.trim()).map(|n| n.to_string()))
+                .ok_or(AnswersError::InvalidValue(index + 1))?;
+
+            answers.insert((day.ok_or(malformed)?, part), answer);
+        }
+
+        Ok(Self { answers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_file() {
+        let answers = r#"
+            # the answers for my account
+            [day01]
+            part1 = 2_066_446
+            part2 = "24931009" # strings work too
+
+            [day17]
+            part1 = "7,3,5,7,5,7,4,3,0"
+        "#
+        .parse::<Answers>()
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.get(1, 1), Some("2066446"));
+        assert_eq!(answers.get(1, 2), Some("24931009"));
+        assert_eq!(answers.get(17, 1), Some("7,3,5,7,5,7,4,3,0"));
+        assert_eq!(answers.get(17, 2), None);
+    }
+
+    #[test]
+    fn invalid_answers_file() {
+        let parse = |contents: &str| contents.parse::<Answers>();
+
+        assert_eq!(parse("part1 = 1"), Err(AnswersError::Malformed(1)));
+        assert_eq!(parse("[day26]"), Err(AnswersError::Malformed(1)));
+        assert_eq!(parse("[day01]\npart3 = 1"), Err(AnswersError::Malformed(2)));
+        assert_eq!(
+            parse("[day01]\npart1 = x"),
+            Err(AnswersError::InvalidValue(2))
+        );
+    }
+}
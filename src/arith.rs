@@ -0,0 +1,78 @@
+//! Arithmetic for the accumulators in the solutions.
+//!
+//! By default these are just the usual operators, which wrap on overflow in
+//! release builds. With the `checked-arithmetic` feature they panic instead
+//! (in every build), so that scaled-up synthetic inputs fail loudly rather
+//! than quietly producing a wrong answer.
+
+use core::ops::{Add, Mul};
+
+/// An integer type that can be used as an accumulator.
+pub trait Accumulator: Copy + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulator for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulator!(u32, u64, usize);
+
+/// Adds `lhs` and `rhs`, panicking on overflow with `checked-arithmetic`.
+#[inline(always)]
+pub fn add<T: Accumulator>(lhs: T, rhs: T) -> T {
+    match cfg!(feature = "checked-arithmetic") {
+        true => lhs.checked_add(rhs).expect("accumulator overflowed"),
+        false => lhs + rhs,
+    }
+}
+
+/// Multiplies `lhs` and `rhs`, panicking on overflow with
+/// `checked-arithmetic`.
+#[inline(always)]
+pub fn mul<T: Accumulator>(lhs: T, rhs: T) -> T {
+    match cfg!(feature = "checked-arithmetic") {
+        true => lhs.checked_mul(rhs).expect("accumulator overflowed"),
+        false => lhs * rhs,
+    }
+}
+
+/// Sums `iter` with [`add`].
+pub fn sum<T: Accumulator>(iter: impl IntoIterator<Item = T>) -> T {
+    iter.into_iter().fold(T::ZERO, add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_range() {
+        assert_eq!(sum([1u32, 2, 3]), 6);
+        assert_eq!(mul(u64::MAX / 2, 2), u64::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(any(debug_assertions, feature = "checked-arithmetic"))]
+    fn overflow() {
+        add(u32::MAX, 1);
+    }
+}
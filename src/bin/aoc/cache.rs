@@ -0,0 +1,101 @@
+//! A cache of computed answers, keyed by the day, the part, and the SHA-256
+//! hash of the input.
+//!
+//! The cache lives in `answers.tsv` under `$XDG_STATE_HOME/aoc-2024` (or
+//! `~/.local/state/aoc-2024`). It knows nothing about the solutions themselves,
+//! so answers computed by an older build are reused until `--force` is passed.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// The key of a cached answer.
+type Key = (u8, u8, String);
+
+#[derive(Debug, Default)]
+pub struct Cache {
+    answers: HashMap<Key, String>,
+    changed: bool,
+}
+
+/// Returns the directory where the cache is stored, if it can be determined.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?)
+            .join(".local")
+            .join("state"),
+    };
+
+    Some(base.join("aoc-2024"))
+}
+
+fn path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("answers.tsv"))
+}
+
+/// Returns the hex-encoded SHA-256 hash of `input`.
+pub fn hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+impl Cache {
+    /// Loads the cache, which is empty if it doesn't exist yet.
+    pub fn load() -> Self {
+        let Some(contents) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        let answers = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\t');
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let hash = fields.next()?.to_string();
+                let answer = fields.next()?.to_string();
+                Some(((day, part, hash), answer))
+            })
+            .collect();
+
+        Self {
+            answers,
+            changed: false,
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8, hash: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, hash.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, hash: &str, answer: &str) {
+        let previous = self
+            .answers
+            .insert((day, part, hash.to_string()), answer.to_string());
+
+        self.changed |= previous.as_deref() != Some(answer);
+    }
+
+    /// Writes the cache back to disk if anything was added to it.
+    pub fn save(&self) -> Result<(), String> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        let path = path().ok_or("no state directory to store the cache in")?;
+
+        let mut entries = self.answers.iter().collect::<Vec<_>>();
+        entries.sort();
+
+        let contents = entries
+            .into_iter()
+            .map(|((day, part, hash), answer)| format!("{day}\t{part}\t{hash}\t{answer}\n"))
+            .collect::<String>();
+
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, contents))
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))
+    }
+}
@@ -0,0 +1,185 @@
+//! Solving every day at once, for `aoc all --concurrent`.
+//!
+//! The days don't depend on each other, so the calendar can be solved in far
+//! less wall-clock time than its parts take one after another. Each day's
+//! input is parsed and solved as a job of its own (see
+//! [`aoc_2024::par::map_concurrent`]), and the results are reported in order
+//! once every day has finished.
+//!
+//! The days share the cores, so a part's time here is usually longer than in
+//! a sequential run, and peak memory and allocations (which are counted for
+//! the whole process) aren't reported at all.
+
+use std::time::{Duration, Instant};
+
+use aoc_2024::{answers::Answers, Day};
+use serde_json::json;
+
+use crate::{cache, solves, style, submissions, Failures, Format};
+
+/// A day's parse or one of its parts, as solved by a job.
+struct Step {
+    /// The part, or `None` for the parse.
+    part: Option<u8>,
+    /// The answer, or why there isn't one.
+    answer: Result<String, String>,
+    elapsed: Duration,
+}
+
+/// Parses `input` and solves each of `day`'s parts, stopping early if the
+/// input can't be parsed.
+fn solve(day: &Day, input: &str) -> Vec<Step> {
+    let start = Instant::now();
+    let parsed = (day.parse)(input);
+    let mut steps = vec![Step {
+        part: None,
+        answer: parsed
+            .as_ref()
+            .map(|_| "(parse)".to_string())
+            .map_err(|err| err.to_string()),
+        elapsed: start.elapsed(),
+    }];
+
+    let Ok(parsed) = parsed else {
+        return steps;
+    };
+
+    for (number, part) in (1..).zip([Some(day.part_1), day.part_2]) {
+        let Some(part) = part else {
+            continue;
+        };
+
+        let start = Instant::now();
+        let answer = part(&*parsed).to_string();
+
+        steps.push(Step {
+            part: Some(number),
+            answer: Ok(answer),
+            elapsed: start.elapsed(),
+        });
+    }
+
+    steps
+}
+
+/// Solves every day with an input concurrently, checking the answers against
+/// `known` if given (and the submissions otherwise).
+pub fn all(known: Option<Answers>, format: Format, refresh: bool) -> Result<(), String> {
+    // inputs are read (or downloaded) up front, so that the jobs only solve
+    let mut days = Vec::new();
+    for day in aoc_2024::days(aoc_2024::config::get().year) {
+        if let Some(input) = crate::read_input(day.number, refresh)? {
+            days.push((day, input));
+        }
+    }
+
+    let start = Instant::now();
+    let results = aoc_2024::par::map_concurrent(&days, |(day, input)| solve(day, input));
+    let wall_time = start.elapsed();
+
+    if format == Format::Text {
+        println!(
+            "{}",
+            style::heading(format!(
+                "{:>3}  {:>4}  {:<20}  {:>12}  {:>5}",
+                "day", "part", "answer", "time", "check"
+            ))
+        );
+    }
+
+    // the time spent parsing, and solving each part
+    let mut totals = [Duration::ZERO; 3];
+    let mut cache = cache::Cache::load();
+    let submissions = submissions::Submissions::load();
+    let mut solves = solves::Solves::load();
+    let mut failures = Failures::default();
+
+    for ((day, input), steps) in days.iter().zip(results) {
+        let hash = cache::hash(input);
+
+        for Step {
+            part,
+            answer,
+            elapsed,
+        } in steps
+        {
+            totals[part.map_or(0, usize::from)] += elapsed;
+
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    failures.invalid += 1;
+                    continue;
+                }
+            };
+
+            let correct = part.and_then(|number| match &known {
+                Some(known) => known.get(day.number, number).map(|known| known == answer),
+                None => submissions.check(day.number, number, &answer),
+            });
+
+            if let Some(number) = part {
+                cache.insert(day.number, number, &hash, &answer);
+
+                // a mismatch with the submissions is only shown, not a failure
+                if known.is_some() {
+                    failures.wrong += usize::from(correct == Some(false));
+                }
+
+                if correct == Some(true) {
+                    solves.record(day.number, number)?;
+                }
+            }
+
+            match format {
+                Format::Text => println!(
+                    "{:>3}  {:>4}  {:<20}  {}  {}",
+                    day.number,
+                    part.map_or_else(|| "-".to_string(), |number| number.to_string()),
+                    answer,
+                    style::time(elapsed, 12),
+                    match part {
+                        Some(_) => style::check(correct, 5),
+                        None => format!("{:>5}", "-"),
+                    }
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "day": day.number,
+                        "part": part,
+                        "answer": part.map(|_| &answer),
+                        "time_ns": elapsed.as_nanos(),
+                        "correct": correct,
+                    })
+                ),
+            }
+        }
+    }
+
+    let [parsing, part_1, part_2] = totals;
+    let total = parsing + part_1 + part_2;
+
+    match format {
+        Format::Text => {
+            println!(
+                "total time: {total:.2?} (parsing {parsing:.2?}, part 1 {part_1:.2?}, part 2 {part_2:.2?})"
+            );
+            println!(
+                "wall-clock time: {wall_time:.2?} (a {:.1}x speedup over the total)",
+                total.as_secs_f64() / wall_time.as_secs_f64()
+            );
+        }
+        Format::Json => println!(
+            "{}",
+            json!({
+                "total_ns": total.as_nanos(),
+                "wall_time_ns": wall_time.as_nanos(),
+            })
+        ),
+    }
+
+    cache.save()?;
+    failures.into_result()
+}
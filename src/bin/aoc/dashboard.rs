@@ -0,0 +1,245 @@
+//! An interactive overview of the whole calendar, showing which days have been
+//! solved, which inputs are missing, and how long each part last took.
+//!
+//! A day counts as solved for the stars when the answer cache has an answer
+//! for its current input, since there's no way to check an answer offline.
+
+use std::time::Duration;
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+
+use crate::{cache, history, measure};
+
+/// The number of times each part is solved when benchmarking.
+const BENCH_RUNS: u32 = 10;
+
+const HELP: &str = "up/down select  r run  b benchmark  v view input  q quit";
+
+struct Dashboard {
+    table: TableState,
+    cache: cache::Cache,
+    history: history::History,
+    /// Each day's input and its hash, if it has one.
+    inputs: Vec<Option<(String, String)>>,
+    /// The output of the last action, shown below the calendar.
+    output: Vec<String>,
+}
+
+impl Dashboard {
+    fn load() -> Self {
+        let inputs = (1..=25)
+            .map(|day| aoc_2024::input::read(day).map(|input| (cache::hash(&input), input)))
+            .collect();
+
+        Self {
+            table: TableState::default().with_selected(0),
+            cache: cache::Cache::load(),
+            history: history::History::load(),
+            inputs,
+            output: Vec::new(),
+        }
+    }
+
+    fn selected(&self) -> u8 {
+        self.table.selected().unwrap_or(0).min(24) as u8 + 1
+    }
+
+    /// Returns the selected day and its input, or an explanation of why it
+    /// can't be solved.
+    fn selection(&self) -> Result<(&'static aoc_2024::Day, &str, &str), String> {
+        let number = self.selected();
+        let day = aoc_2024::day(number).ok_or(format!("day {number} is not compiled in"))?;
+
+        match &self.inputs[number as usize - 1] {
+            Some((hash, input)) => Ok((day, hash, input)),
+            None => Err(format!("missing input for day {number}")),
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let output = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.table.select_next();
+                    continue;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.table.select_previous();
+                    continue;
+                }
+                KeyCode::Char('r') | KeyCode::Enter => self.solve(),
+                KeyCode::Char('b') => self.benchmark(),
+                KeyCode::Char('v') => self.view(),
+                _ => continue,
+            };
+
+            self.output = output.unwrap_or_else(|message| vec![format!("error: {message}")]);
+        }
+    }
+
+    /// Solves both parts of the selected day, caching the answers and logging
+    /// the timings.
+    fn solve(&mut self) -> Result<Vec<String>, String> {
+        let (day, hash, input) = self.selection()?;
+        let (parsed, elapsed, _) = measure(|| (day.parse)(input));
+        let parsed = parsed.map_err(|err| err.to_string())?;
+
+        let mut output = vec![format!("parsed in {elapsed:.2?}")];
+        let mut runs = Vec::new();
+        let mut answers = Vec::new();
+
+        for (number, part) in (1..).zip([Some(day.part_1), day.part_2]) {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let (answer, elapsed, _) = measure(|| part(&*parsed));
+            output.push(format!("part {number}: {answer} in {elapsed:.2?}"));
+            runs.push((day.number, number, elapsed));
+            answers.push((number, answer.to_string()));
+        }
+
+        let (number, hash) = (day.number, hash.to_string());
+        for (part, answer) in answers {
+            self.cache.insert(number, part, &hash, &answer);
+        }
+
+        self.history.record(&runs)?;
+        Ok(output)
+    }
+
+    /// Solves each part of the selected day several times, reporting the mean
+    /// and fastest times.
+    fn benchmark(&self) -> Result<Vec<String>, String> {
+        let (day, _, input) = self.selection()?;
+        let parsed = (day.parse)(input).map_err(|err| err.to_string())?;
+        let mut output = vec![format!("{BENCH_RUNS} runs of each part:")];
+
+        for (number, part) in (1..).zip([Some(day.part_1), day.part_2]) {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let times = (0..BENCH_RUNS)
+                .map(|_| measure(|| part(&*parsed)).1)
+                .collect::<Vec<_>>();
+
+            let mean = times.iter().sum::<Duration>() / BENCH_RUNS;
+            let fastest = times.iter().min().unwrap();
+            output.push(format!(
+                "part {number}: mean {mean:.2?}, fastest {fastest:.2?}"
+            ));
+        }
+
+        Ok(output)
+    }
+
+    /// Describes the structure of the selected day's input.
+    fn view(&self) -> Result<Vec<String>, String> {
+        let (day, _, input) = self.selection()?;
+        let mut output = vec![format!("lines: {}", input.lines().count())];
+
+        if let Some(stats) = day.stats {
+            let parsed = (day.parse)(input).map_err(|err| err.to_string())?;
+            output.extend(
+                stats(&*parsed)
+                    .into_iter()
+                    .map(|(name, value)| format!("{name}: {value}")),
+            );
+        }
+
+        Ok(output)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [calendar, output, help] = Layout::vertical([
+            Constraint::Min(8),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let rows = (1..=25u8).map(|number| {
+            let hash = self.inputs[number as usize - 1]
+                .as_ref()
+                .map(|(hash, _)| hash.as_str());
+
+            let stars = (1..=2)
+                .map(
+                    |part| match hash.and_then(|hash| self.cache.get(number, part, hash)) {
+                        Some(_) => '*',
+                        None => '.',
+                    },
+                )
+                .collect::<String>();
+
+            let timing = |part| {
+                self.history
+                    .latest(number, part)
+                    .map_or_else(|| "-".to_string(), |elapsed| format!("{elapsed:.2?}"))
+            };
+
+            let row = Row::new([
+                number.to_string(),
+                stars,
+                hash.map_or("missing", |_| "present").to_string(),
+                timing(1),
+                timing(2),
+            ]);
+
+            match aoc_2024::day(number) {
+                Some(_) => row,
+                None => row.dark_gray(),
+            }
+        });
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Length(7),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(["day", "stars", "input", "part 1", "part 2"]).bold())
+            .block(Block::bordered().title(" Advent of Code 2024 "))
+            .row_highlight_style(Style::new().reversed())
+            .highlight_symbol("> ");
+
+        frame.render_stateful_widget(table, calendar, &mut self.table);
+
+        let title = format!(" day {} ", self.selected());
+        frame.render_widget(
+            Paragraph::new(self.output.join("\n")).block(Block::bordered().title(title)),
+            output,
+        );
+        frame.render_widget(Paragraph::new(HELP).dark_gray(), help);
+    }
+}
+
+pub fn dashboard() -> Result<(), String> {
+    let mut dashboard = Dashboard::load();
+    let result = ratatui::run(|terminal| dashboard.run(terminal))
+        .map_err(|err| format!("terminal error: {err}"));
+
+    dashboard.cache.save()?;
+    result
+}
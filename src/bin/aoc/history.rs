@@ -0,0 +1,99 @@
+//! A log of how long each part took to solve, so that the latest timings can
+//! be shown without solving everything again.
+//!
+//! The log lives in `runs.tsv` next to the answer cache, with a line for each
+//! time a part was solved: the Unix timestamp, the day, the part, and the time
+//! taken in nanoseconds.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+fn path() -> Option<PathBuf> {
+    crate::cache::state_dir().map(|dir| dir.join("runs.tsv"))
+}
+
+/// The latest timing of each part, as shown by the dashboard and the report,
+/// along with the one before it.
+#[derive(Debug, Default)]
+pub struct History {
+    latest: HashMap<(u8, u8), Duration>,
+    previous: HashMap<(u8, u8), Duration>,
+}
+
+impl History {
+    /// Loads the latest two timings of each part, which are empty if nothing
+    /// has been logged yet.
+    pub fn load() -> Self {
+        let Some(contents) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        // later lines overwrite earlier ones
+        let runs = contents.lines().filter_map(|line| {
+            let mut fields = line.split('\t').skip(1);
+            let day = fields.next()?.parse().ok()?;
+            let part = fields.next()?.parse().ok()?;
+            let nanos = fields.next()?.parse().ok()?;
+            Some((day, part, Duration::from_nanos(nanos)))
+        });
+
+        let mut history = Self::default();
+        history.insert(runs);
+        history
+    }
+
+    fn insert(&mut self, runs: impl IntoIterator<Item = (u8, u8, Duration)>) {
+        for (day, part, elapsed) in runs {
+            if let Some(previous) = self.latest.insert((day, part), elapsed) {
+                self.previous.insert((day, part), previous);
+            }
+        }
+    }
+
+    pub fn latest(&self, day: u8, part: u8) -> Option<Duration> {
+        self.latest.get(&(day, part)).copied()
+    }
+
+    /// Returns the timing logged before the latest one.
+    pub fn previous(&self, day: u8, part: u8) -> Option<Duration> {
+        self.previous.get(&(day, part)).copied()
+    }
+
+    /// Appends `runs` to the log, and makes them the latest timings.
+    #[cfg(feature = "dashboard")]
+    pub fn record(&mut self, runs: &[(u8, u8, Duration)]) -> Result<(), String> {
+        self.insert(runs.iter().copied());
+        append(runs)
+    }
+}
+
+/// Appends the timings of `runs`, given as `(day, part, elapsed)`, to the log.
+pub fn append(runs: &[(u8, u8, Duration)]) -> Result<(), String> {
+    if runs.is_empty() {
+        return Ok(());
+    }
+
+    let path = path().ok_or("no state directory to store the run history in")?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    let lines = runs
+        .iter()
+        .map(|(day, part, elapsed)| format!("{timestamp}\t{day}\t{part}\t{}\n", elapsed.as_nanos()))
+        .collect::<String>();
+
+    std::fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+        })
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|err| format!("failed to append to {}: {err}", path.display()))
+}
@@ -0,0 +1,1398 @@
+use std::{
+    io::IsTerminal,
+    path::PathBuf,
+    process::ExitCode,
+    sync::{mpsc, OnceLock},
+    time::{Duration, Instant},
+};
+
+use aoc_2024::answers::Answers;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::json;
+
+mod cache;
+mod concurrent;
+#[cfg(feature = "dashboard")]
+mod dashboard;
+mod history;
+mod memory;
+mod progress;
+mod report;
+mod scaffold;
+#[cfg(feature = "serve")]
+mod serve;
+mod solves;
+mod style;
+mod submissions;
+#[cfg(feature = "visualize")]
+mod visualize;
+mod watch;
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Solutions for Advent of Code 2024")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Reports statistics about the structure of each input before solving it
+    #[arg(short, long, global = true)]
+    verbose: bool,
+    /// How `all`, `batch`, and `run` print their results
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Downloads each input again, rather than using any copy on disk
+    #[arg(long, global = true)]
+    refresh: bool,
+    /// The directory of dayNN.txt inputs [default: from the config, or input/]
+    #[arg(long, global = true, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+    /// The year of the event, whose solutions and inputs to use [default:
+    /// from the config, or 2024]
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// The number of threads for parallel solvers [default: from the config,
+    /// or one per core]
+    #[arg(long, global = true, value_name = "N")]
+    threads: Option<usize>,
+    /// Bypasses the parallel code paths, solving everything on one thread so
+    /// that results and timings are reproducible
+    #[arg(long, global = true, conflicts_with = "threads")]
+    sequential: bool,
+    /// Gives up on any part that `all`, `batch`, or `run` is still solving
+    /// after this many seconds
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A table for people to read
+    Text,
+    /// A JSON object per line, for each parse (with a null part), each part,
+    /// and any error
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solves every day with an input, and reports the time and peak memory
+    /// used by each part (and with the `counting-alloc` feature, how many
+    /// allocations it made).
+    ///
+    /// Answers are cached by the hash of their input, so parts whose input
+    /// hasn't changed since the last run are not recomputed.
+    All {
+        /// Recomputes every answer, ignoring the cache
+        #[arg(short, long)]
+        force: bool,
+        /// Solves every day at once, recomputing every answer, and reports the
+        /// wall-clock time alongside the total. Memory isn't measured, and
+        /// parts can't be timed out
+        #[arg(short = 'j', long)]
+        concurrent: bool,
+        /// Recomputes every answer and checks it against a file of known
+        /// answers, failing if any differ [default: answers.toml with the
+        /// inputs]
+        #[arg(long, value_name = "PATH", num_args = 0..=1)]
+        verify: Option<Option<PathBuf>>,
+    },
+    /// Shows an interactive overview of every day, with keys to solve,
+    /// benchmark, or inspect the input of the selected one.
+    #[cfg(feature = "dashboard")]
+    Dashboard,
+    /// Solves one day for each of several inputs, checking the answers against
+    /// any that are expected.
+    ///
+    /// The expected answers for `NAME.txt` are read from `NAME.answers` if it
+    /// exists, in the same `part N: ANSWER` format that `scramble` reports.
+    /// Directories are expanded to every input file in them.
+    Batch {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+    },
+    /// Solves one day, for either its input or an example from its puzzle
+    /// description.
+    Run {
+        #[arg(short, long)]
+        day: u8,
+        /// Solves only the given part [default: both]
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// The input to solve [default: input/dayNN.txt, or aocd's cache]
+        #[arg(short, long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Solves the Nth example instead (or every example, without N), and
+        /// checks the documented answers. Days without examples use those in
+        /// the downloaded puzzle page
+        #[arg(short, long, value_name = "N", num_args = 0..=1)]
+        example: Option<Option<usize>>,
+        /// Solves every input in a directory instead, like `batch`
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["part", "input", "example"])]
+        inputs: Option<PathBuf>,
+    },
+    /// Runs a day's parts back to back, reporting split times like the
+    /// leaderboard does.
+    Race {
+        #[arg(short, long)]
+        day: u8,
+        /// A file to append the result to, as a line of tab-separated values
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Solves each part of every day with an input (or of just one day)
+    /// several times, and reports the mean and 95th percentile times.
+    Bench {
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// How many times to solve each part
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// A CSV file to append the timings to
+        #[arg(long, value_name = "PATH")]
+        csv: Option<PathBuf>,
+    },
+    /// Writes an overview of the calendar, with the answers, timings, and
+    /// stars of each day, as Markdown or HTML.
+    Report {
+        /// Writes a standalone HTML page instead of Markdown
+        #[arg(long)]
+        html: bool,
+        /// Where to write the report [default: stdout]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Downloads the puzzle page for a day, and lists the examples and
+    /// answers found in it.
+    Puzzle {
+        #[arg(short, long)]
+        day: u8,
+    },
+    /// Creates the module for a new day from a skeleton, and registers it as
+    /// a Cargo feature and with cargo-aoc.
+    New {
+        #[arg(short, long)]
+        day: u8,
+    },
+    /// Submits the answer to one part of a day to adventofcode.com.
+    ///
+    /// Judged submissions are logged, and an answer that has already been
+    /// judged, or one for a part that is already solved, is not submitted.
+    Submit {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// The answer to submit [default: the answer computed from the input]
+        answer: Option<String>,
+        /// Submits the answer even if it has already been judged
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Serves the solutions over HTTP, as a JSON API.
+    ///
+    /// `POST /<year>/day/<day>/part/<part>` solves a part for the input in the
+    /// request body, and `GET /<year>/days` lists the days that can be solved.
+    #[cfg(feature = "serve")]
+    Serve {
+        /// The address to listen on
+        #[arg(long, default_value = "127.0.0.1:8024")]
+        address: String,
+    },
+    /// Solves a day again whenever its source or input changes, and reports
+    /// how the answers changed.
+    Watch {
+        #[arg(short, long)]
+        day: u8,
+        /// What to solve on each change
+        #[arg(long, value_enum, default_value_t = watch::Target::Both)]
+        against: watch::Target,
+    },
+    /// Steps through the simulation in a day that has one (days 6, 14, and
+    /// 15), with keys to play, pause, and step it and counters that update as
+    /// it runs.
+    #[cfg(feature = "visualize")]
+    Visualize {
+        #[arg(short, long)]
+        day: u8,
+        /// The input to simulate [default: input/dayNN.txt, or aocd's cache]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Summarizes how long each part took to solve after its puzzle
+    /// unlocked, from when it was first answered correctly.
+    Stats,
+    /// Writes an anonymized copy of a day's input, along with its answers.
+    Scramble {
+        #[arg(short, long)]
+        day: u8,
+        /// The input to scramble [default: input/dayNN.txt, or aocd's cache]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Where to write the scrambled input [default: stdout]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[arg(short, long, default_value_t = 2024)]
+        seed: u64,
+    },
+}
+
+fn scramble(
+    day: u8,
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+    seed: u64,
+) -> Result<(), String> {
+    let path = input.unwrap_or_else(|| aoc_2024::input::find(day));
+    let input = std::fs::read_to_string(&path)
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+
+    let scrambled = aoc_2024::scramble::scramble(day, &input, seed)
+        .ok_or_else(|| format!("day {day} does not support scrambling"))?;
+
+    match output {
+        Some(path) => std::fs::write(&path, &scrambled)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?,
+        None => print!("{scrambled}"),
+    }
+
+    // the answers go to stderr so that stdout is just the scrambled input
+    if let Some(solution) = aoc_2024::find(2024, day) {
+        let parsed = (solution.parse)(&scrambled).map_err(|err| err.to_string())?;
+        eprintln!("part 1: {}", (solution.part_1)(&*parsed));
+
+        if let Some(part_2) = solution.part_2 {
+            eprintln!("part 2: {}", part_2(&*parsed));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the line count of `input`, followed by any statistics that `day`
+/// reports about the structure of its parsed form.
+fn print_stats(day: &aoc_2024::Day, input: &str, parsed: &aoc_2024::Parsed, format: Format) {
+    let lines = input.lines().count().to_string();
+    let stats = day.stats.map_or_else(Vec::new, |stats| stats(&**parsed));
+    let stats = std::iter::once(("lines", lines)).chain(stats);
+
+    match format {
+        Format::Text => {
+            println!("day {} input:", day.number);
+            for (name, value) in stats {
+                println!("  {name}: {value}");
+            }
+        }
+        Format::Json => {
+            let stats = stats
+                .map(|(name, value)| (name.to_string(), value.into()))
+                .collect::<serde_json::Map<_, _>>();
+
+            println!("{}", json!({ "day": day.number, "stats": stats }));
+        }
+    }
+}
+
+/// Reports that an input for `day` couldn't be parsed, which under
+/// `--format json` is a line of JSON like the error that ends a command.
+fn report_parse_error(day: u8, err: impl std::fmt::Display, format: Format) {
+    match format {
+        Format::Text => eprintln!("error: {err}"),
+        Format::Json => println!("{}", json!({ "day": day, "error": err.to_string() })),
+    }
+}
+
+/// Reads the input for `day`, downloading it first if there's a session token
+/// but no copy on disk (or if `refresh` is set).
+fn read_input(day: u8, refresh: bool) -> Result<Option<String>, String> {
+    aoc_2024::input::fetch(day, refresh)
+        .map_err(|err| format!("failed to download the input for day {day}: {err}"))
+}
+
+/// Runs `f`, returning its result, running time, and peak memory usage.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, Option<usize>) {
+    memory::reset_peak();
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    (result, elapsed, memory::peak_bytes())
+}
+
+/// How long a part may run before it's cancelled, if there's a limit.
+static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// How long a cancelled part has to stop, before the whole process gives up
+/// on it.
+const CANCEL_GRACE: Duration = Duration::from_secs(2);
+
+/// Runs a part like [`measure`], but cancels it once the [`TIMEOUT`] has
+/// passed, in which case its answer is discarded.
+///
+/// Parts that never check for cancellation can't be stopped, so if one is
+/// still running once the grace period is up, the process exits instead.
+fn measure_part<T>(f: impl FnOnce() -> T) -> (Option<T>, Duration, Option<usize>) {
+    let Some(&timeout) = TIMEOUT.get() else {
+        let (result, elapsed, peak) = measure(f);
+        return (Some(result), elapsed, peak);
+    };
+
+    aoc_2024::cancel::reset();
+
+    // the sender is dropped when the part returns, which wakes the watchdog
+    let (done, finished) = mpsc::channel::<()>();
+    let watchdog = std::thread::spawn(move || {
+        if finished.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+            aoc_2024::cancel::cancel();
+
+            if finished.recv_timeout(CANCEL_GRACE) == Err(mpsc::RecvTimeoutError::Timeout) {
+                eprintln!("error: a part timed out, and could not be cancelled");
+                std::process::exit(1);
+            }
+        }
+    });
+
+    let (result, elapsed, peak) = measure(f);
+    drop(done);
+    watchdog.join().unwrap();
+
+    let cancelled = aoc_2024::cancel::is_cancelled();
+    (Some(result).filter(|_| !cancelled), elapsed, peak)
+}
+
+/// Counts the parts that went wrong while solving.
+#[derive(Debug, Default)]
+struct Failures {
+    /// Answers that didn't match the expected ones.
+    wrong: usize,
+    /// Parts that were cancelled for running too long.
+    timeouts: usize,
+    /// Inputs that couldn't be parsed.
+    invalid: usize,
+}
+
+impl std::ops::AddAssign for Failures {
+    fn add_assign(&mut self, rhs: Self) {
+        self.wrong += rhs.wrong;
+        self.timeouts += rhs.timeouts;
+        self.invalid += rhs.invalid;
+    }
+}
+
+impl Failures {
+    /// Reports any failures as an error.
+    fn into_result(self) -> Result<(), String> {
+        let mut failures = Vec::new();
+
+        if self.wrong > 0 {
+            failures.push(format!("{} answers did not match", self.wrong));
+        }
+
+        if self.timeouts > 0 {
+            failures.push(format!("{} parts timed out", self.timeouts));
+        }
+
+        if self.invalid > 0 {
+            failures.push(format!("{} inputs could not be parsed", self.invalid));
+        }
+
+        match failures.is_empty() {
+            true => Ok(()),
+            false => Err(failures.join(", ")),
+        }
+    }
+}
+
+/// Formats an optional peak memory usage for a table cell.
+fn format_peak(peak: Option<usize>) -> String {
+    peak.map_or_else(|| "-".to_string(), memory::format_bytes)
+}
+
+/// Formats an optional allocation count for a table cell.
+fn format_allocations(allocations: Option<usize>) -> String {
+    allocations.map_or_else(|| "-".to_string(), |count| count.to_string())
+}
+
+fn all(
+    force: bool,
+    concurrent: bool,
+    verify: Option<PathBuf>,
+    verbose: bool,
+    format: Format,
+    refresh: bool,
+) -> Result<(), String> {
+    // answers are checked against the known ones if there are any, and
+    // otherwise against those accepted by adventofcode.com
+    let known = match &verify {
+        Some(path) => Some(Answers::load(path).map_err(|err| err.to_string())?),
+        None => None,
+    };
+
+    if concurrent {
+        // cancellation and the inputs' statistics are process-wide, so they
+        // would get mixed up between the days
+        if TIMEOUT.get().is_some() {
+            return Err("--timeout can't be used with --concurrent".to_string());
+        } else if verbose {
+            return Err("--verbose can't be used with --concurrent".to_string());
+        }
+
+        return concurrent::all(known, format, refresh);
+    }
+
+    // cached answers were computed by whatever the code was at the time, so
+    // verifying them would prove nothing
+    let force = force || known.is_some();
+
+    if format == Format::Text {
+        println!(
+            "{}",
+            style::heading(format!(
+                "{:>3}  {:>4}  {:<20}  {:>12}  {:>12}  {:>11}  {:>5}",
+                "day", "part", "answer", "time", "peak memory", "allocations", "check"
+            ))
+        );
+    }
+
+    // the time spent parsing, and solving each part
+    let mut totals = [Duration::ZERO; 3];
+    let mut cache = cache::Cache::load();
+    let submissions = submissions::Submissions::load();
+    let mut solves = solves::Solves::load();
+    let mut failures = Failures::default();
+    let mut runs = Vec::new();
+
+    'days: for day in aoc_2024::days(aoc_2024::config::get().year) {
+        let Some(input) = read_input(day.number, refresh)? else {
+            continue;
+        };
+
+        // the input is only parsed once some part actually needs it, and the
+        // parse gets its own row in the table
+        let mut parsed = None;
+        let parse = |totals: &mut [Duration; 3], failures: &mut Failures| {
+            let (parsed, elapsed, peak) = measure(|| (day.parse)(&input));
+            let allocations = memory::allocations();
+            totals[0] += elapsed;
+
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    report_parse_error(day.number, err, format);
+                    failures.invalid += 1;
+                    return None;
+                }
+            };
+
+            match format {
+                Format::Text => println!(
+                    "{:>3}  {:>4}  {:<20}  {}  {:>12}  {:>11}  {:>5}",
+                    day.number,
+                    "-",
+                    "(parse)",
+                    style::time(elapsed, 12),
+                    format_peak(peak),
+                    format_allocations(allocations),
+                    "-"
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "day": day.number,
+                        "part": null,
+                        "time_ns": elapsed.as_nanos(),
+                        "peak_bytes": peak,
+                        "allocations": allocations,
+                    })
+                ),
+            }
+
+            Some(parsed)
+        };
+
+        if verbose {
+            let Some(parsed) = parsed.insert(parse(&mut totals, &mut failures)) else {
+                continue;
+            };
+
+            print_stats(day, &input, parsed, format);
+        }
+
+        let hash = cache::hash(&input);
+        let parts = [Some(day.part_1), day.part_2];
+        for (index, part) in parts.into_iter().enumerate() {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let number = index as u8 + 1;
+            let cached = cache.get(day.number, number, &hash).filter(|_| !force);
+
+            let (answer, elapsed, peak, allocations) = match cached {
+                Some(answer) => (answer.to_string(), None, None, None),
+                None => {
+                    let parsed = match &parsed {
+                        Some(parsed) => parsed,
+                        None => parsed.insert(parse(&mut totals, &mut failures)),
+                    };
+
+                    let Some(parsed) = parsed else {
+                        continue 'days;
+                    };
+
+                    let (answer, elapsed, peak) = measure_part(|| part(&**parsed));
+                    let allocations = memory::allocations();
+                    let Some(answer) = answer else {
+                        failures.timeouts += 1;
+
+                        match format {
+                            Format::Text => println!(
+                                "{:>3}  {:>4}  {:<20}  {}  {:>12}  {:>11}  {:>5}",
+                                day.number,
+                                number,
+                                "(timed out)",
+                                style::time(elapsed, 12),
+                                format_peak(peak),
+                                format_allocations(allocations),
+                                "-"
+                            ),
+                            Format::Json => println!(
+                                "{}",
+                                json!({
+                                    "day": day.number,
+                                    "part": number,
+                                    "time_ns": elapsed.as_nanos(),
+                                    "timed_out": true,
+                                })
+                            ),
+                        }
+
+                        continue;
+                    };
+
+                    let answer = answer.to_string();
+                    cache.insert(day.number, number, &hash, &answer);
+                    runs.push((day.number, number, elapsed));
+                    totals[number as usize] += elapsed;
+
+                    (answer, Some(elapsed), peak, allocations)
+                }
+            };
+
+            let correct = match &known {
+                Some(known) => known.get(day.number, number).map(|known| known == answer),
+                None => submissions.check(day.number, number, &answer),
+            };
+
+            // a mismatch with the submissions is only shown, not a failure
+            if known.is_some() {
+                failures.wrong += usize::from(correct == Some(false));
+            }
+
+            if correct == Some(true) {
+                solves.record(day.number, number)?;
+            }
+
+            match format {
+                Format::Text => println!(
+                    "{:>3}  {:>4}  {:<20}  {}  {:>12}  {:>11}  {}",
+                    day.number,
+                    number,
+                    answer,
+                    elapsed.map_or_else(
+                        || format!("{:>12}", "cached"),
+                        |elapsed| style::time(elapsed, 12)
+                    ),
+                    format_peak(peak),
+                    format_allocations(allocations),
+                    style::check(correct, 5)
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "day": day.number,
+                        "part": number,
+                        "answer": answer,
+                        "time_ns": elapsed.map(|elapsed| elapsed.as_nanos()),
+                        "peak_bytes": peak,
+                        "allocations": allocations,
+                        "cached": elapsed.is_none(),
+                        "correct": correct,
+                    })
+                ),
+            }
+        }
+    }
+
+    if format == Format::Text {
+        let [parsing, part_1, part_2] = totals;
+        println!(
+            "total time: {:.2?} (parsing {parsing:.2?}, part 1 {part_1:.2?}, part 2 {part_2:.2?})",
+            parsing + part_1 + part_2
+        );
+
+        let mut slowest = runs.clone();
+        slowest.sort_by_key(|&(_, _, elapsed)| std::cmp::Reverse(elapsed));
+
+        if !slowest.is_empty() {
+            let slowest = slowest
+                .iter()
+                .take(3)
+                .map(|&(day, part, elapsed)| {
+                    format!("day {day} part {part} ({})", style::time(elapsed, 0))
+                })
+                .collect::<Vec<_>>();
+
+            println!("slowest: {}", slowest.join(", "));
+        }
+    }
+
+    cache.save()?;
+    history::append(&runs)?;
+
+    failures.into_result()
+}
+
+/// Reads the expected answers for the input at `path`, if there are any.
+fn expected_answers(path: &std::path::Path) -> [Option<String>; 2] {
+    let mut expected = [None, None];
+    let Ok(answers) = std::fs::read_to_string(path.with_extension("answers")) else {
+        return expected;
+    };
+
+    for line in answers.lines() {
+        if let Some(answer) = line.strip_prefix("part 1: ") {
+            expected[0] = Some(answer.trim().to_string());
+        } else if let Some(answer) = line.strip_prefix("part 2: ") {
+            expected[1] = Some(answer.trim().to_string());
+        }
+    }
+
+    expected
+}
+
+/// Replaces each directory in `paths` with the input files in it, in order,
+/// skipping any expected answers.
+fn expand_inputs(paths: Vec<PathBuf>) -> Result<Vec<PathBuf>, String> {
+    let mut inputs = Vec::new();
+
+    for path in paths {
+        if !path.is_dir() {
+            inputs.push(path);
+            continue;
+        }
+
+        let mut files = std::fs::read_dir(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|file| file.is_file())
+            .filter(|file| {
+                file.extension()
+                    .is_none_or(|extension| extension != "answers")
+            })
+            .collect::<Vec<_>>();
+
+        if files.is_empty() {
+            return Err(format!("there are no inputs in {}", path.display()));
+        }
+
+        files.sort();
+        inputs.append(&mut files);
+    }
+
+    Ok(inputs)
+}
+
+fn batch(day: u8, inputs: Vec<PathBuf>, verbose: bool, format: Format) -> Result<(), String> {
+    let solution = aoc_2024::day(day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+
+    if format == Format::Text {
+        println!(
+            "{}",
+            style::heading(format!(
+                "{:<24}  {:>4}  {:<20}  {:>12}  {:>5}",
+                "input", "part", "answer", "time", "check"
+            ))
+        );
+    }
+
+    let mut failures = Failures::default();
+
+    for path in expand_inputs(inputs)? {
+        let input = std::fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+
+        let parsed = match (solution.parse)(&input) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                report_parse_error(day, format!("{}: {err}", path.display()), format);
+                failures.invalid += 1;
+                continue;
+            }
+        };
+
+        if verbose {
+            print_stats(solution, &input, &parsed, format);
+        }
+
+        let expected = expected_answers(&path);
+        let parts = [Some(solution.part_1), solution.part_2];
+
+        for (index, (part, expected)) in parts.into_iter().zip(expected).enumerate() {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let (answer, elapsed, _) = measure_part(|| part(&*parsed));
+            let Some(answer) = answer else {
+                failures.timeouts += 1;
+
+                match format {
+                    Format::Text => println!(
+                        "{:<24}  {:>4}  {:<20}  {}  {:>5}",
+                        path.display(),
+                        index + 1,
+                        "(timed out)",
+                        style::time(elapsed, 12),
+                        "-"
+                    ),
+                    Format::Json => println!(
+                        "{}",
+                        json!({
+                            "input": path.display().to_string(),
+                            "day": day,
+                            "part": index + 1,
+                            "time_ns": elapsed.as_nanos(),
+                            "timed_out": true,
+                        })
+                    ),
+                }
+
+                continue;
+            };
+
+            let answer = answer.to_string();
+            let correct = expected.as_ref().map(|expected| *expected == answer);
+            failures.wrong += usize::from(correct == Some(false));
+
+            match format {
+                Format::Text => println!(
+                    "{:<24}  {:>4}  {:<20}  {}  {}",
+                    path.display(),
+                    index + 1,
+                    answer,
+                    style::time(elapsed, 12),
+                    style::check(correct, 5)
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "input": path.display().to_string(),
+                        "day": day,
+                        "part": index + 1,
+                        "answer": answer,
+                        "time_ns": elapsed.as_nanos(),
+                        "expected": expected,
+                        "correct": correct,
+                    })
+                ),
+            }
+        }
+    }
+
+    failures.into_result()
+}
+
+fn run(
+    day: u8,
+    only: Option<u8>,
+    path: Option<PathBuf>,
+    example: Option<Option<usize>>,
+    verbose: bool,
+    format: Format,
+    refresh: bool,
+) -> Result<(), String> {
+    let solution = aoc_2024::day(day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+
+    if only == Some(2) && solution.part_2.is_none() {
+        return Err(format!("day {day} has no part 2"));
+    }
+
+    // each input to solve, with its number and documented answers if it's an
+    // example
+    let inputs = match (&path, example) {
+        (Some(path), _) => {
+            let input = std::fs::read_to_string(path)
+                .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+
+            vec![(input, None)]
+        }
+        (None, None) => {
+            let input =
+                read_input(day, refresh)?.ok_or_else(|| format!("missing input for day {day}"))?;
+
+            vec![(input, None)]
+        }
+        (None, Some(number)) => {
+            let mut examples = solution
+                .examples
+                .map_or_else(Vec::new, |examples| examples())
+                .into_iter()
+                .map(|example| (example.input.to_string(), example.answers))
+                .collect::<Vec<_>>();
+
+            // days without examples of their own can still be tried on those
+            // in their puzzle's page, though without any answers to check
+            if examples.is_empty() {
+                let page = aoc_2024::puzzle::read(day).unwrap_or_default();
+                examples = aoc_2024::puzzle::Puzzle::parse(&page)
+                    .examples
+                    .into_iter()
+                    .map(|example| (example, [None, None]))
+                    .collect();
+            }
+
+            let count = examples.len();
+            let examples = examples
+                .into_iter()
+                .enumerate()
+                .map(|(index, (input, answers))| (input, Some((index + 1, answers))))
+                .filter(|(_, example)| {
+                    number.is_none_or(|number| example.as_ref().unwrap().0 == number)
+                })
+                .collect::<Vec<_>>();
+
+            match (count, examples.is_empty()) {
+                (0, _) => return Err(format!("day {day} has no examples")),
+                (1, true) => return Err(format!("day {day} has only 1 example")),
+                (count, true) => return Err(format!("day {day} has only {count} examples")),
+                _ => examples,
+            }
+        }
+    };
+
+    let mut failures = Failures::default();
+    let mut runs = Vec::new();
+
+    for (input, example) in &inputs {
+        let example = example.as_ref().map(|(number, answers)| (*number, answers));
+
+        if let Some((number, _)) = example.filter(|_| inputs.len() > 1 && format == Format::Text) {
+            println!("example {number}:");
+        }
+
+        failures += solve(solution, only, input, example, verbose, format, &mut runs);
+    }
+
+    // only the real input's timings belong in the history
+    if path.is_none() && example.is_none() {
+        history::append(&runs)?;
+    }
+
+    failures.into_result()
+}
+
+/// Solves `input` for `run`, checking the answers against those documented
+/// if it's an example, and returns the parts that went wrong.
+fn solve(
+    solution: &aoc_2024::Day,
+    only: Option<u8>,
+    input: &str,
+    example: Option<(usize, &[Option<String>; 2])>,
+    verbose: bool,
+    format: Format,
+    runs: &mut Vec<(u8, u8, Duration)>,
+) -> Failures {
+    let day = solution.number;
+    let parsed = match (solution.parse)(input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            report_parse_error(day, err, format);
+            return Failures {
+                invalid: 1,
+                ..Failures::default()
+            };
+        }
+    };
+
+    if verbose {
+        print_stats(solution, input, &parsed, format);
+    }
+
+    let parts = [Some(solution.part_1), solution.part_2];
+    let mut failures = Failures::default();
+
+    for (index, part) in parts.into_iter().enumerate() {
+        let Some(part) = part.filter(|_| only.is_none_or(|only| only == index as u8 + 1)) else {
+            continue;
+        };
+
+        // an example might only document the answer to one part
+        let expected = match example {
+            Some((_, answers)) => match &answers[index] {
+                Some(answer) => Some(answer),
+                None => continue,
+            },
+            None => None,
+        };
+
+        let (answer, elapsed, peak) = measure_part(|| part(&*parsed));
+        let allocations = memory::allocations();
+        let Some(answer) = answer else {
+            failures.timeouts += 1;
+
+            match format {
+                Format::Text => println!(
+                    "part {}: timed out after {}",
+                    index + 1,
+                    style::time(elapsed, 0)
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "day": day,
+                        "part": index + 1,
+                        "example": example.map(|(number, _)| number),
+                        "time_ns": elapsed.as_nanos(),
+                        "timed_out": true,
+                    })
+                ),
+            }
+
+            continue;
+        };
+
+        runs.push((day, index as u8 + 1, elapsed));
+
+        let answer = answer.to_string();
+        let correct = expected.map(|expected| *expected == answer);
+        failures.wrong += usize::from(correct == Some(false));
+
+        match format {
+            Format::Text => {
+                let check = match expected {
+                    Some(expected) if *expected == answer => {
+                        format!(" ({})", style::check(correct, 0))
+                    }
+                    Some(expected) => {
+                        format!(" ({}, expected {expected})", style::check(correct, 0))
+                    }
+                    None => String::new(),
+                };
+
+                // the peak is only worth showing next to the allocations, since
+                // without the counting allocator it's the whole process's
+                let memory = match allocations {
+                    Some(count) => format!(" ({} peak, {count} allocations)", format_peak(peak)),
+                    None => String::new(),
+                };
+
+                println!(
+                    "part {}: {answer} in {}{memory}{check}",
+                    index + 1,
+                    style::time(elapsed, 0)
+                );
+            }
+            Format::Json => println!(
+                "{}",
+                json!({
+                    "day": day,
+                    "part": index + 1,
+                    "example": example.map(|(number, _)| number),
+                    "answer": answer,
+                    "time_ns": elapsed.as_nanos(),
+                    "peak_bytes": peak,
+                    "allocations": allocations,
+                    "expected": expected,
+                    "correct": correct,
+                })
+            ),
+        }
+    }
+
+    failures
+}
+
+/// Formats a duration as a leaderboard time, with millisecond precision.
+fn format_split(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    format!("{millis:.3}ms")
+}
+
+fn bench(
+    day: Option<u8>,
+    runs: u32,
+    csv: Option<PathBuf>,
+    format: Format,
+    refresh: bool,
+) -> Result<(), String> {
+    let days = match day {
+        Some(day) => {
+            let solution =
+                aoc_2024::day(day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+            let input =
+                read_input(day, refresh)?.ok_or_else(|| format!("missing input for day {day}"))?;
+
+            vec![(solution, input)]
+        }
+        None => {
+            let mut days = Vec::new();
+            for solution in aoc_2024::days(aoc_2024::config::get().year) {
+                if let Some(input) = read_input(solution.number, refresh)? {
+                    days.push((solution, input));
+                }
+            }
+
+            days
+        }
+    };
+
+    if format == Format::Text {
+        println!(
+            "{}",
+            style::heading(format!(
+                "{:>3}  {:>4}  {:>12}  {:>12}  {:>11}",
+                "day", "part", "mean", "p95", "allocations"
+            ))
+        );
+    }
+
+    let mut timings = Vec::new();
+
+    for (solution, input) in days {
+        let parsed = (solution.parse)(&input).map_err(|err| err.to_string())?;
+
+        for (number, part) in (1..).zip([Some(solution.part_1), solution.part_2]) {
+            let Some(part) = part else {
+                continue;
+            };
+
+            let mut allocations = None;
+            let samples = (0..runs)
+                .map(|_| {
+                    let (_, elapsed, _) = measure(|| part(&*parsed));
+                    allocations = memory::allocations();
+                    elapsed
+                })
+                .collect::<Vec<_>>();
+
+            let timing = aoc_2024::timings::Timing::from_samples(
+                solution.year,
+                solution.number,
+                number,
+                &samples,
+                allocations,
+            )
+            .unwrap();
+
+            match format {
+                Format::Text => println!(
+                    "{:>3}  {:>4}  {}  {}  {:>11}",
+                    timing.day,
+                    timing.part,
+                    style::time(timing.mean, 12),
+                    style::time(timing.p95, 12),
+                    timing
+                        .allocations
+                        .map_or("-".to_string(), |count| count.to_string())
+                ),
+                Format::Json => println!(
+                    "{}",
+                    json!({
+                        "day": timing.day,
+                        "part": timing.part,
+                        "runs": timing.runs,
+                        "mean_ns": timing.mean.as_nanos(),
+                        "p95_ns": timing.p95.as_nanos(),
+                        "allocations": timing.allocations,
+                    })
+                ),
+            }
+
+            timings.push(timing);
+        }
+    }
+
+    match csv {
+        Some(path) => aoc_2024::timings::append_csv(&path, &timings)
+            .map_err(|err| format!("failed to append to {}: {err}", path.display())),
+        None => Ok(()),
+    }
+}
+
+fn puzzle(day: u8, refresh: bool) -> Result<(), String> {
+    let page = aoc_2024::puzzle::fetch(day, refresh)
+        .map_err(|err| format!("failed to download the puzzle for day {day}: {err}"))?
+        .ok_or_else(|| format!("no session token to download the puzzle for day {day} with"))?;
+
+    if let Some(path) = aoc_2024::puzzle::path(day) {
+        println!("{}", style::heading(path.display().to_string()));
+    }
+
+    let puzzle = aoc_2024::puzzle::Puzzle::parse(&page);
+
+    for (index, example) in puzzle.examples.iter().enumerate() {
+        println!("example {}:", index + 1);
+        for line in example.lines() {
+            println!("  {line}");
+        }
+    }
+
+    for (index, answer) in puzzle.answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            println!("part {}: {answer}", index + 1);
+        }
+    }
+
+    Ok(())
+}
+
+fn race(day: u8, history: Option<PathBuf>, verbose: bool, refresh: bool) -> Result<(), String> {
+    let solution = aoc_2024::day(day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+
+    let input = read_input(day, refresh)?.ok_or_else(|| format!("missing input for day {day}"))?;
+
+    let (parsed, parse, _) = measure(|| (solution.parse)(&input));
+    let parsed = parsed.map_err(|err| err.to_string())?;
+    let mut splits = vec![("parse", parse)];
+
+    if verbose {
+        print_stats(solution, &input, &parsed, Format::Text);
+    }
+
+    let (answer_1, part_1, _) = measure(|| (solution.part_1)(&*parsed));
+    splits.push(("part 1", part_1));
+
+    let answer_2 = match solution.part_2 {
+        Some(part) => {
+            let (answer, part_2, _) = measure(|| part(&*parsed));
+            splits.push(("part 2", part_2));
+            Some(answer)
+        }
+        None => None,
+    };
+
+    println!("{:<8}  {:>12}  {:>12}", "split", "time", "total");
+
+    let mut total = Duration::ZERO;
+    for (name, split) in &splits {
+        total += *split;
+        println!(
+            "{name:<8}  {:>12}  {:>12}",
+            format_split(*split),
+            format_split(total)
+        );
+    }
+
+    println!("part 1: {answer_1}");
+
+    if let Some(answer_2) = &answer_2 {
+        println!("part 2: {answer_2}");
+    }
+
+    let part_2 = splits.get(2).map(|&(_, split)| split);
+    if let Some(part_2) = part_2 {
+        let delta = part_2.as_secs_f64() - part_1.as_secs_f64();
+        println!("delta:  {:+.3}ms", delta * 1000.0);
+    }
+
+    if let Some(path) = history {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        let mut line = format!("{timestamp}\t{day}");
+        for (_, split) in &splits {
+            line.push_str(&format!("\t{}", split.as_nanos()));
+        }
+        line.push('\n');
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, line.as_bytes()))
+            .map_err(|err| format!("failed to append to {}: {err}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn submit(
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    force: bool,
+    refresh: bool,
+) -> Result<(), String> {
+    let session = aoc_2024::input::session_token()
+        .ok_or_else(|| aoc_2024::client::Error::NoSession.to_string())?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution =
+                aoc_2024::day(day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+            let input =
+                read_input(day, refresh)?.ok_or_else(|| format!("missing input for day {day}"))?;
+            let parsed = (solution.parse)(&input).map_err(|err| err.to_string())?;
+
+            let answer = match part {
+                1 => (solution.part_1)(&*parsed),
+                _ => solution
+                    .part_2
+                    .ok_or_else(|| format!("day {day} has no part 2"))?(
+                    &*parsed
+                ),
+            };
+
+            answer.to_string()
+        }
+    };
+
+    let mut submissions = submissions::Submissions::load();
+    if let Some(refusal) = submissions.refusal(day, part, &answer).filter(|_| !force) {
+        return Err(format!("{refusal} (pass --force to submit anyway)"));
+    }
+
+    println!("submitting {answer} for day {day} part {part}");
+
+    let verdict = aoc_2024::client::submit_answer(day, part, &answer, &session)
+        .map_err(|err| format!("failed to submit: {err}"))?;
+    submissions.record(day, part, &answer, &verdict)?;
+
+    match verdict {
+        aoc_2024::client::Verdict::Correct => {
+            println!("{verdict}");
+            solves::Solves::load().record(day, part)
+        }
+        verdict => Err(verdict.to_string()),
+    }
+}
+
+/// Loads the configuration, overrides it with the command-line flags, and
+/// applies it for the rest of the process.
+fn configure(cli: &Cli) -> Result<(), String> {
+    let mut config = aoc_2024::config::Config::load().map_err(|err| err.to_string())?;
+
+    if let Some(dir) = &cli.input_dir {
+        config.input_dir = dir.clone();
+    }
+
+    config.year = cli.year.unwrap_or(config.year);
+    config.threads = cli.threads.or(config.threads);
+
+    if let Some(threads) = config.threads {
+        aoc_2024::par::set_threads(threads);
+    }
+
+    aoc_2024::par::set_sequential(cli.sequential);
+
+    aoc_2024::config::set(config);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let format = cli.format;
+
+    // the dashboard and visualizer draw over the whole terminal, JSON output
+    // and the server are meant for other programs, and concurrent days would
+    // report over each other, so none of them get a progress bar
+    let progress = match cli.command {
+        Command::All {
+            concurrent: true, ..
+        } => false,
+        #[cfg(feature = "dashboard")]
+        Command::Dashboard => false,
+        #[cfg(feature = "visualize")]
+        Command::Visualize { .. } => false,
+        #[cfg(feature = "serve")]
+        Command::Serve { .. } => false,
+        _ => cli.format == Format::Text,
+    };
+
+    if let Some(timeout) = cli.timeout {
+        let _ = TIMEOUT.set(Duration::from_secs(timeout));
+    }
+
+    if progress && std::io::stderr().is_terminal() {
+        aoc_2024::progress::set_reporter(progress::ProgressBar::default());
+    }
+
+    let result = configure(&cli).and_then(|()| match cli.command {
+        Command::All {
+            force,
+            concurrent,
+            verify,
+        } => {
+            let verify = verify.map(|path| path.unwrap_or_else(Answers::path));
+            all(
+                force,
+                concurrent,
+                verify,
+                cli.verbose,
+                cli.format,
+                cli.refresh,
+            )
+        }
+        #[cfg(feature = "dashboard")]
+        Command::Dashboard => dashboard::dashboard(),
+        Command::Batch { day, inputs } => batch(day, inputs, cli.verbose, cli.format),
+        Command::Run {
+            day,
+            inputs: Some(dir),
+            ..
+        } => batch(day, vec![dir], cli.verbose, cli.format),
+        Command::Run {
+            day,
+            part,
+            input,
+            example,
+            inputs: None,
+        } => run(
+            day,
+            part,
+            input,
+            example,
+            cli.verbose,
+            cli.format,
+            cli.refresh,
+        ),
+        Command::Bench { day, runs, csv } => bench(day, runs, csv, cli.format, cli.refresh),
+        Command::Race { day, history } => race(day, history, cli.verbose, cli.refresh),
+        Command::Report { html, output } => report::report(html, output),
+        Command::Puzzle { day } => puzzle(day, cli.refresh),
+        Command::New { day } => scaffold::new_day(day),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            force,
+        } => submit(day, part, answer, force, cli.refresh),
+        #[cfg(feature = "serve")]
+        Command::Serve { address } => serve::serve(&address),
+        Command::Stats => solves::stats(),
+        #[cfg(feature = "visualize")]
+        Command::Visualize { day, input } => visualize::visualize(day, input, cli.refresh),
+        Command::Watch { day, against } => watch::watch(day, against),
+        Command::Scramble {
+            day,
+            input,
+            output,
+            seed,
+        } => scramble(day, input, output, seed),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            match format {
+                Format::Text => eprintln!("error: {message}"),
+                Format::Json => println!("{}", json!({ "error": message })),
+            }
+
+            ExitCode::FAILURE
+        }
+    }
+}
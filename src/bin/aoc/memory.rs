@@ -0,0 +1,127 @@
+//! Measuring the peak memory used while solving a puzzle.
+//!
+//! With the `counting-alloc` feature, every allocation goes through a global
+//! allocator that tracks the high-water mark of live heap bytes. Otherwise we
+//! fall back to the peak resident set size reported by Linux, which includes
+//! everything else in the process but needs no instrumentation. Allocations
+//! can only be counted with the counting allocator.
+
+#[cfg(feature = "counting-alloc")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    pub struct CountingAlloc {
+        current: AtomicUsize,
+        peak: AtomicUsize,
+        count: AtomicUsize,
+    }
+
+    #[global_allocator]
+    pub static ALLOCATOR: CountingAlloc = CountingAlloc {
+        current: AtomicUsize::new(0),
+        peak: AtomicUsize::new(0),
+        count: AtomicUsize::new(0),
+    };
+
+    impl CountingAlloc {
+        pub fn reset_peak(&self) {
+            self.peak
+                .store(self.current.load(Ordering::Relaxed), Ordering::Relaxed);
+            self.count.store(0, Ordering::Relaxed);
+        }
+
+        pub fn peak(&self) -> usize {
+            self.peak.load(Ordering::Relaxed)
+        }
+
+        pub fn count(&self) -> usize {
+            self.count.load(Ordering::Relaxed)
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+
+            if !ptr.is_null() {
+                let current = self.current.fetch_add(layout.size(), Ordering::Relaxed);
+                self.peak
+                    .fetch_max(current + layout.size(), Ordering::Relaxed);
+                self.count.fetch_add(1, Ordering::Relaxed);
+            }
+
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            self.current.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+}
+
+/// Resets the peak and the allocation count, so that [`peak_bytes`] and
+/// [`allocations`] measure from this point onwards.
+#[cfg(feature = "counting-alloc")]
+pub fn reset_peak() {
+    counting::ALLOCATOR.reset_peak();
+}
+
+/// Returns the peak number of live heap bytes since the last [`reset_peak`].
+#[cfg(feature = "counting-alloc")]
+pub fn peak_bytes() -> Option<usize> {
+    Some(counting::ALLOCATOR.peak())
+}
+
+/// Returns the number of heap allocations since the last [`reset_peak`].
+#[cfg(feature = "counting-alloc")]
+pub fn allocations() -> Option<usize> {
+    Some(counting::ALLOCATOR.count())
+}
+
+/// Resets the peak, so that [`peak_bytes`] measures from this point onwards.
+///
+/// Writing `5` to `clear_refs` resets the kernel's peak RSS counter for the
+/// process; this is a no-op where that isn't supported.
+#[cfg(not(feature = "counting-alloc"))]
+pub fn reset_peak() {
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+/// Returns the peak resident set size of the process since the last
+/// [`reset_peak`], if the platform reports it.
+#[cfg(not(feature = "counting-alloc"))]
+pub fn peak_bytes() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes = line.split_whitespace().nth(1)?.parse::<usize>().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Returns `None`, since allocations are only counted by the counting
+/// allocator.
+#[cfg(not(feature = "counting-alloc"))]
+pub fn allocations() -> Option<usize> {
+    None
+}
+
+/// Formats a number of bytes with a binary unit prefix.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
@@ -0,0 +1,57 @@
+//! A progress bar on stderr for the solvers that report their progress.
+
+use std::{
+    io::Write,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The width of the bar itself, in characters.
+const WIDTH: usize = 40;
+
+#[derive(Debug, Default)]
+pub struct ProgressBar {
+    total: AtomicUsize,
+    done: AtomicUsize,
+    /// The number of filled characters when the bar was last drawn, so that it
+    /// is only redrawn when it visibly changes.
+    filled: AtomicUsize,
+}
+
+impl ProgressBar {
+    fn draw(done: usize, total: usize) {
+        let filled = WIDTH * done / total.max(1);
+        let mut stderr = std::io::stderr().lock();
+
+        let _ = write!(
+            stderr,
+            "\r[{}{}] {done}/{total}",
+            "#".repeat(filled),
+            " ".repeat(WIDTH - filled)
+        );
+        let _ = stderr.flush();
+    }
+}
+
+impl aoc_2024::progress::Progress for ProgressBar {
+    fn start(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+        self.filled.store(0, Ordering::Relaxed);
+        Self::draw(0, total);
+    }
+
+    fn advance(&self, done: usize) {
+        let total = self.total.load(Ordering::Relaxed);
+        let done = (self.done.fetch_add(done, Ordering::Relaxed) + done).min(total);
+        let filled = WIDTH * done / total.max(1);
+
+        if self.filled.swap(filled, Ordering::Relaxed) != filled {
+            Self::draw(done, total);
+        }
+    }
+
+    fn finish(&self) {
+        // clear the line, so that the results are printed where the bar was
+        eprint!("\r\x1b[2K");
+    }
+}
@@ -0,0 +1,204 @@
+//! A results page for the whole calendar, rendered as Markdown or as a
+//! standalone HTML file.
+//!
+//! The report is built from what the other commands have recorded, rather than
+//! by solving anything: answers come from the cache for each day's current
+//! input, timings (and their change since the previous run) from the run
+//! history, and stars from the submission log.
+
+use std::{fmt::Write, path::PathBuf, time::Duration};
+
+use crate::{cache, history::History, submissions::Submissions};
+
+/// What the report says about one part of a day.
+#[derive(Debug, Default)]
+struct Part {
+    answer: Option<String>,
+    latest: Option<Duration>,
+    previous: Option<Duration>,
+    solved: bool,
+}
+
+#[derive(Debug)]
+struct Day {
+    number: u8,
+    compiled: bool,
+    has_input: bool,
+    parts: [Part; 2],
+}
+
+impl Day {
+    fn stars(&self) -> usize {
+        self.parts.iter().filter(|part| part.solved).count()
+    }
+}
+
+/// Renders the report, and writes it to `output` (or stdout).
+pub fn report(html: bool, output: Option<PathBuf>) -> Result<(), String> {
+    let days = collect();
+    let year = aoc_2024::config::get().year;
+
+    let report = match html {
+        true => render_html(year, &days),
+        false => render_markdown(year, &days),
+    };
+
+    match output {
+        Some(path) => std::fs::write(&path, report)
+            .map_err(|err| format!("failed to write {}: {err}", path.display())),
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
+
+/// Gathers the state of every day that is compiled in or has a star.
+fn collect() -> Vec<Day> {
+    let cache = cache::Cache::load();
+    let history = History::load();
+    let submissions = Submissions::load();
+
+    (1..=25)
+        .map(|number| {
+            let input = aoc_2024::input::read(number);
+            let hash = input.as_deref().map(cache::hash);
+
+            let parts = [1, 2].map(|part| Part {
+                answer: hash
+                    .as_deref()
+                    .and_then(|hash| cache.get(number, part, hash))
+                    .map(str::to_string),
+                latest: history.latest(number, part),
+                previous: history.previous(number, part),
+                solved: submissions.solved(number, part),
+            });
+
+            Day {
+                number,
+                compiled: aoc_2024::day(number).is_some(),
+                has_input: input.is_some(),
+                parts,
+            }
+        })
+        .filter(|day| day.compiled || day.stars() > 0)
+        .collect()
+}
+
+/// Describes the overall state of the calendar in a sentence.
+fn summary(days: &[Day]) -> String {
+    let stars = days.iter().map(Day::stars).sum::<usize>();
+    let compiled = days.iter().filter(|day| day.compiled).count();
+
+    format!("{stars} of 50 stars, and {compiled} of 25 days solved by this crate.")
+}
+
+fn format_stars(day: &Day) -> String {
+    "★".repeat(day.stars()) + &"☆".repeat(2 - day.stars())
+}
+
+/// Formats a part's answer, or why it has none.
+fn format_answer(day: &Day, part: &Part) -> String {
+    match &part.answer {
+        Some(answer) => answer.clone(),
+        None if !day.compiled => "not solved".to_string(),
+        None if !day.has_input => "no input".to_string(),
+        None => "not run".to_string(),
+    }
+}
+
+/// Formats a part's latest timing, with its change since the previous one.
+fn format_time(part: &Part) -> String {
+    let Some(latest) = part.latest else {
+        return "-".to_string();
+    };
+
+    match part.previous {
+        Some(previous) if !previous.is_zero() => {
+            let change = 100.0 * (latest.as_secs_f64() / previous.as_secs_f64() - 1.0);
+            format!("{latest:.2?} ({change:+.1}%)")
+        }
+        _ => format!("{latest:.2?}"),
+    }
+}
+
+fn render_markdown(year: u16, days: &[Day]) -> String {
+    let mut report = format!("# Advent of Code {year}\n\n{}\n\n", summary(days));
+
+    report.push_str("| Day | Stars | Part 1 | Time | Part 2 | Time |\n");
+    report.push_str("|----:|:-----:|-------:|-----:|-------:|-----:|\n");
+
+    for day in days {
+        let [part_1, part_2] = &day.parts;
+        writeln!(
+            report,
+            "| {} | {} | {} | {} | {} | {} |",
+            day.number,
+            format_stars(day),
+            format_answer(day, part_1).replace('|', "\\|"),
+            format_time(part_1),
+            format_answer(day, part_2).replace('|', "\\|"),
+            format_time(part_2),
+        )
+        .unwrap();
+    }
+
+    report
+}
+
+fn render_html(year: u16, days: &[Day]) -> String {
+    let mut rows = String::new();
+
+    for day in days {
+        let [part_1, part_2] = &day.parts;
+        writeln!(
+            rows,
+            "      <tr><td>{}</td><td class=\"stars\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            day.number,
+            format_stars(day),
+            escape(&format_answer(day, part_1)),
+            escape(&format_time(part_1)),
+            escape(&format_answer(day, part_2)),
+            escape(&format_time(part_2)),
+        )
+        .unwrap();
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Advent of Code {year}</title>
+  <style>
+    body {{ font-family: monospace; background: #0f0f23; color: #cccccc; margin: 2em; }}
+    h1 {{ color: #00cc00; }}
+    table {{ border-collapse: collapse; }}
+    th, td {{ padding: 0.25em 1em; text-align: right; }}
+    th {{ border-bottom: 1px solid #333340; }}
+    .stars {{ color: #ffff66; text-align: center; }}
+  </style>
+</head>
+<body>
+  <h1>Advent of Code {year}</h1>
+  <p>{}</p>
+  <table>
+    <thead>
+      <tr><th>Day</th><th>Stars</th><th>Part 1</th><th>Time</th><th>Part 2</th><th>Time</th></tr>
+    </thead>
+    <tbody>
+{rows}    </tbody>
+  </table>
+</body>
+</html>
+"#,
+        summary(days)
+    )
+}
+
+/// Escapes the characters that are special in HTML text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,212 @@
+//! Scaffolding for a new day: its module, with a skeleton to fill in, and the
+//! Cargo feature and cargo-aoc wrappers that every day needs.
+//!
+//! The day registry is generated by `build.rs` from the files in `src/`, so
+//! nothing else has to be wired up by hand. Days of years other than 2024 go in
+//! `src/yYYYY/`, and have no feature or wrappers of their own.
+//!
+//! If the puzzle's page can be read (see [`aoc_2024::puzzle`]), the skeleton
+//! starts with its first example and the answer given for it.
+
+use std::path::Path;
+
+/// The skeleton of a day's module, with `{day}` standing in for its number,
+/// `{year}` for the line declaring its year (if it needs one), and `{example}`
+/// and `{answer}` for its first example.
+const TEMPLATE: &str = r##"use std::str::FromStr;
+
+use crate::{error::AocError, Answer};
+
+/// The puzzle input.
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub lines: Vec<String>,
+}
+
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().map(str::to_string).collect();
+        Ok(Self { lines })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Input, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(_input: &Input) -> Answer {
+    todo!()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(_input: &Input) -> Answer {
+    todo!()
+}
+
+pub const EXAMPLE: &str = {example};
+
+aoc_day! {
+{year}    day: {day},
+    parsed: Input,
+    example_part_1: EXAMPLE => {answer},
+}
+"##;
+
+/// Creates the module for `day`, and adds it to `Cargo.toml` and the
+/// cargo-aoc wrappers.
+pub fn new_day(day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day}"));
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let year = aoc_2024::config::get().year;
+
+    if year != 2024 {
+        let dir = root.join("src").join(format!("y{year}"));
+        let module = dir.join(format!("day{day:02}.rs"));
+
+        if module.exists() {
+            return Err(format!("{} already exists", module.display()));
+        }
+
+        std::fs::create_dir_all(&dir)
+            .map_err(|err| format!("failed to create {}: {err}", dir.display()))?;
+
+        let year = format!("    year: {year},\n");
+        write(&module, &skeleton(day, &year))?;
+
+        println!("created {}", module.display());
+        return Ok(());
+    }
+
+    let module = root.join("src").join(format!("day{day:02}.rs"));
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    let manifest = root.join("Cargo.toml");
+    let manifest_source = read(&manifest)?;
+    let manifest_source = add_feature(&manifest_source, day)
+        .ok_or_else(|| format!("couldn't find the day features in {}", manifest.display()))?;
+
+    let cargo_aoc = root.join("src").join("cargo_aoc.rs");
+    let cargo_aoc_source = add_wrappers(&read(&cargo_aoc)?, day);
+
+    write(&module, &skeleton(day, ""))?;
+    write(&manifest, &manifest_source)?;
+    write(&cargo_aoc, &cargo_aoc_source)?;
+
+    println!("created {}", module.display());
+    Ok(())
+}
+
+/// Fills in the [`TEMPLATE`] for `day`, with the first example from its
+/// puzzle's page if there is one.
+fn skeleton(day: u8, year: &str) -> String {
+    let puzzle = match aoc_2024::puzzle::fetch(day, false) {
+        Ok(page) => page.map(|page| aoc_2024::puzzle::Puzzle::parse(&page)),
+        Err(err) => {
+            eprintln!("warning: failed to download the puzzle for day {day}: {err}");
+            None
+        }
+    };
+
+    let example = puzzle
+        .as_ref()
+        .and_then(|puzzle| puzzle.examples.first())
+        .map_or("", String::as_str);
+
+    // answers that aren't numbers are given as string literals
+    let answer = match puzzle
+        .as_ref()
+        .and_then(|puzzle| puzzle.answers[0].as_ref())
+    {
+        Some(answer) if answer.parse::<u64>().is_ok() => answer.clone(),
+        Some(answer) => format!("{answer:?}"),
+        None => "0".to_string(),
+    };
+
+    // the example goes in last, since it could contain any of the placeholders
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{year}", year)
+        .replace("{answer}", &answer)
+        .replace("{example}", &raw_string(example))
+}
+
+/// Quotes `text` as a raw string literal, with enough `#`s that nothing in it
+/// ends the literal early.
+fn raw_string(text: &str) -> String {
+    let hashes = (1..)
+        .map(|count| "#".repeat(count))
+        .find(|hashes| !text.contains(&format!("\"{hashes}")))
+        .unwrap();
+
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("failed to read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents)
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+/// Adds the feature for `day` to the manifest, both on its own and in the
+/// `all-days` list, keeping both in order.
+fn add_feature(manifest: &str, day: u8) -> Option<String> {
+    let feature = format!("day{day:02}");
+    let mut lines = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+
+    // the days are zero-padded, so their lines sort in order
+    let start = lines.iter().position(|line| line == "all-days = [")?;
+    let end = start + lines[start..].iter().position(|line| line == "]")?;
+    let entry = format!("    \"{feature}\",");
+    let at = (start + 1..end).find(|&i| lines[i] > entry).unwrap_or(end);
+    lines.insert(at, entry);
+
+    let definition = format!("{feature} = []");
+    let definitions = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("day") && lines[i].ends_with(" = []"))
+        .collect::<Vec<_>>();
+    let at = definitions
+        .iter()
+        .copied()
+        .find(|&i| lines[i] > definition)
+        .or_else(|| definitions.last().map(|&i| i + 1))?;
+    lines.insert(at, definition);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds the cargo-aoc wrappers for both parts of `day`, before those of any
+/// later day.
+fn add_wrappers(source: &str, day: u8) -> String {
+    let wrappers = (1..=2)
+        .map(|part| {
+            format!(
+                "#[cfg(feature = \"day{day:02}\")]\n\
+                 #[aoc(day{day}, part{part})]\n\
+                 pub fn day{day:02}_part{part}(input: &str) -> String {{\n    \
+                     solve({day}, {part}, input)\n\
+                 }}\n"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let later = (day + 1..=25)
+        .find_map(|later| source.find(&format!("#[cfg(feature = \"day{later:02}\")]")));
+
+    match later {
+        Some(at) => format!("{}{wrappers}\n{}", &source[..at], &source[at..]),
+        None => format!("{}\n\n{wrappers}", source.trim_end()),
+    }
+}
@@ -0,0 +1,271 @@
+//! A small HTTP server that exposes the solutions as a JSON API, so that they
+//! can be called from a web frontend.
+//!
+//! The API has two endpoints:
+//!
+//! - `GET /<year>/days` lists the days compiled in for a year.
+//! - `POST /<year>/day/<day>/part/<part>` solves a part for the input in the
+//!   request body, and returns the answer along with the time taken to parse
+//!   the input and to solve the part.
+//!
+//! Errors are returned as `{"error": ...}` with an appropriate status. Every
+//! response allows cross-origin requests, and closes the connection.
+//!
+//! This only implements as much of HTTP/1.1 as those endpoints need, rather
+//! than pulling in an async runtime and a server framework for them; each
+//! connection is handled on its own thread.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    panic::AssertUnwindSafe,
+    time::Instant,
+};
+
+use serde_json::{json, Value};
+
+/// The largest request body that is accepted, which is far larger than any
+/// puzzle input.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: Some(json!({ "error": message.into() })),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Content Too Large",
+            422 => "Unprocessable Content",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let body = self
+            .body
+            .as_ref()
+            .map_or_else(String::new, |body| body.to_string());
+
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Connection: close\r\n\r\n{body}",
+            self.status,
+            self.reason(),
+            body.len(),
+        )?;
+
+        stream.flush()
+    }
+}
+
+/// Serves the API on `address` until the process is interrupted.
+pub fn serve(address: &str) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(address).map_err(|err| format!("failed to bind {address}: {err}"))?;
+
+    eprintln!("listening on http://{address}");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                std::thread::spawn(move || handle(stream));
+            }
+            Err(err) => eprintln!("failed to accept a connection: {err}"),
+        }
+    }
+
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream) {
+    let start = Instant::now();
+    let response = match read_request(&mut stream) {
+        Ok(request) => {
+            let response = route(&request);
+            eprintln!(
+                "{} {} -> {} in {:.2?}",
+                request.method,
+                request.path,
+                response.status,
+                start.elapsed()
+            );
+
+            response
+        }
+        Err(response) => response,
+    };
+
+    // the client may already have gone away, and there's no one to tell
+    let _ = response.write_to(&mut stream);
+}
+
+/// Reads a request from `stream`, or returns the error response to send if it
+/// can't be read.
+fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let bad_request = || Response::error(400, "malformed request");
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|_| bad_request())?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or_else(bad_request)?.to_string();
+    let path = parts.next().ok_or_else(bad_request)?.to_string();
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(|_| bad_request())?;
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| bad_request())?;
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        return Err(Response::error(413, "the input is too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|_| bad_request())?;
+
+    Ok(Request { method, path, body })
+}
+
+fn route(request: &Request) -> Response {
+    let segments = request
+        .path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    let allowed = match segments.as_slice() {
+        [_, "days"] => "GET",
+        [_, "day", _, "part", _] => "POST",
+        _ => return Response::error(404, format!("no such endpoint: {}", request.path)),
+    };
+
+    // browsers send a preflight request before posting from another origin
+    if request.method == "OPTIONS" {
+        return Response {
+            status: 204,
+            body: None,
+        };
+    }
+
+    if request.method != allowed {
+        return Response::error(405, format!("{} only supports {allowed}", request.path));
+    }
+
+    let Ok(year) = segments[0].parse::<u16>() else {
+        return Response::error(404, format!("no such year: {}", segments[0]));
+    };
+
+    match segments.as_slice() {
+        [_, "days"] => {
+            let days = aoc_2024::days(year)
+                .map(|day| day.number)
+                .collect::<Vec<_>>();
+            Response::ok(json!({ "year": year, "days": days }))
+        }
+        [_, _, day, _, part] => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part)) => solve(year, day, part, &request.body),
+            _ => Response::error(404, format!("no such part: {}", request.path)),
+        },
+        _ => unreachable!(),
+    }
+}
+
+fn solve(year: u16, day: u8, part: u8, body: &[u8]) -> Response {
+    let Some(solution) = aoc_2024::find(year, day) else {
+        return Response::error(404, format!("{year} day {day} is not compiled in"));
+    };
+
+    let part_fn = match part {
+        1 => solution.part_1,
+        2 => match solution.part_2 {
+            Some(part_2) => part_2,
+            None => return Response::error(404, format!("{year} day {day} has no part 2")),
+        },
+        _ => return Response::error(404, format!("there is no part {part}")),
+    };
+
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Response::error(400, "the input is not UTF-8");
+    };
+
+    if input.trim().is_empty() {
+        return Response::error(400, "the input is empty");
+    }
+
+    let start = Instant::now();
+    let parsed = match (solution.parse)(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return Response::error(400, err.to_string()),
+    };
+    let parse_time = start.elapsed();
+
+    // the parts assume that an input that parsed can be solved, and panic if not
+    let solved = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        let start = Instant::now();
+        let answer = part_fn(&*parsed);
+        (answer, start.elapsed())
+    }));
+
+    match solved {
+        Ok((answer, time)) => Response::ok(json!({
+            "year": year,
+            "day": day,
+            "part": part,
+            "answer": answer.to_string(),
+            "parse_ns": parse_time.as_nanos(),
+            "time_ns": time.as_nanos(),
+        })),
+        Err(_) => Response::error(422, "the input could not be solved"),
+    }
+}
@@ -0,0 +1,171 @@
+//! A record of when each part was first solved, for tracking solve times.
+//!
+//! The record lives in `solves.tsv` next to the answer cache, with a line for
+//! the first correct answer to each part: the Unix timestamp, the year, the
+//! day, and the part. An answer counts as correct once adventofcode.com
+//! accepts it, or once `all` verifies it against the known answers.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{style, submissions::Submissions};
+
+fn path() -> Option<PathBuf> {
+    crate::cache::state_dir().map(|dir| dir.join("solves.tsv"))
+}
+
+#[derive(Debug, Default)]
+pub struct Solves {
+    first: HashMap<(u16, u8, u8), u64>,
+}
+
+impl Solves {
+    /// Loads the record, which is empty if nothing has been solved yet.
+    pub fn load() -> Self {
+        let Some(contents) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        let mut solves = Self::default();
+
+        for line in contents.lines() {
+            let mut fields = line.split('\t').map(str::parse::<u64>);
+            let (Some(Ok(timestamp)), Some(Ok(year)), Some(Ok(day)), Some(Ok(part))) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            let key = (year as u16, day as u8, part as u8);
+            solves.first.entry(key).or_insert(timestamp);
+        }
+
+        solves
+    }
+
+    /// Returns when `part` of `day` in the configured year was first solved.
+    pub fn first(&self, day: u8, part: u8) -> Option<u64> {
+        let year = aoc_2024::config::get().year;
+        self.first.get(&(year, day, part)).copied()
+    }
+
+    /// Records that `part` of `day` has been solved now, unless it already
+    /// was.
+    pub fn record(&mut self, day: u8, part: u8) -> Result<(), String> {
+        let year = aoc_2024::config::get().year;
+        if self.first.contains_key(&(year, day, part)) {
+            return Ok(());
+        }
+
+        let path = path().ok_or("no state directory to store the solve times in")?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        self.first.insert((year, day, part), timestamp);
+        let line = format!("{timestamp}\t{year}\t{day}\t{part}\n");
+
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+            })
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|err| format!("failed to append to {}: {err}", path.display()))
+    }
+}
+
+/// Returns the Unix timestamp at which `day` of `year` unlocked, which is
+/// midnight in UTC-5.
+fn unlocked_at(year: u16, day: u8) -> u64 {
+    // the number of days from 1970-01-01 to December `day` of `year`, by
+    // Howard Hinnant's `days_from_civil`
+    let year = u64::from(year);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * 9 + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    days * 86400 + 5 * 3600
+}
+
+/// Formats a solve time like the personal leaderboard does, which gives up
+/// on anything longer than a day.
+fn format_solve_time(seconds: u64) -> String {
+    match seconds {
+        0..86400 => format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        _ => ">24h".to_string(),
+    }
+}
+
+/// Prints how long each part took to solve after its puzzle unlocked, and how
+/// long part 2 took after part 1.
+///
+/// Correct submissions made before the solve times were recorded count too.
+pub fn stats() -> Result<(), String> {
+    let year = aoc_2024::config::get().year;
+    let solves = Solves::load();
+    let submissions = Submissions::load();
+
+    let first = |day, part| {
+        [
+            solves.first(day, part),
+            submissions.first_correct(day, part),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    };
+
+    println!(
+        "{}",
+        style::heading(format!(
+            "{:>3}  {:>9}  {:>9}  {:>9}",
+            "day", "part 1", "part 2", "gap"
+        ))
+    );
+
+    let mut stars = 0;
+
+    for day in 1..=25 {
+        let [part_1, part_2] = [1, 2].map(|part| first(day, part));
+        if part_1.is_none() && part_2.is_none() {
+            continue;
+        }
+
+        stars += usize::from(part_1.is_some()) + usize::from(part_2.is_some());
+
+        let unlocked = unlocked_at(year, day);
+        let since_unlock = |solved: Option<u64>| {
+            solved.map_or("-".to_string(), |solved| {
+                format_solve_time(solved.saturating_sub(unlocked))
+            })
+        };
+
+        let gap = match (part_1, part_2) {
+            (Some(part_1), Some(part_2)) => format_solve_time(part_2.saturating_sub(part_1)),
+            _ => "-".to_string(),
+        };
+
+        println!(
+            "{day:>3}  {:>9}  {:>9}  {gap:>9}",
+            since_unlock(part_1),
+            since_unlock(part_2)
+        );
+    }
+
+    println!("{stars} stars in {year}");
+    Ok(())
+}
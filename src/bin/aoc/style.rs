@@ -0,0 +1,59 @@
+//! Colours for the tables printed in text mode, which are only used when
+//! stdout is a terminal and `NO_COLOR` isn't set.
+//!
+//! Cells are padded before they're painted, so the escape codes don't throw
+//! off the alignment of the columns.
+
+use std::{io::IsTerminal, sync::OnceLock, time::Duration};
+
+use clap::builder::styling::{AnsiColor, Style};
+
+/// Whether to colour the output at all.
+fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+
+    *ENABLED.get_or_init(|| {
+        std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+    })
+}
+
+/// Wraps `text` in the escape codes for `style`, if colours are enabled.
+fn paint(text: String, style: Style) -> String {
+    match enabled() {
+        true => format!("{style}{text}{style:#}"),
+        false => text,
+    }
+}
+
+fn color(color: AnsiColor) -> Style {
+    Style::new().fg_color(Some(color.into()))
+}
+
+/// Formats a check against a known answer in a column of `width`: green if the
+/// answer is verified, and red if it's wrong or can't be verified.
+pub fn check(correct: Option<bool>, width: usize) -> String {
+    match correct {
+        Some(true) => paint(format!("{:>width$}", "ok"), color(AnsiColor::Green)),
+        Some(false) => paint(format!("{:>width$}", "WRONG"), color(AnsiColor::Red).bold()),
+        None => paint(format!("{:>width$}", "-"), color(AnsiColor::Red)),
+    }
+}
+
+/// Formats a time in a column of `width`, highlighting the slow ones: yellow
+/// from 100ms, and red from a second.
+pub fn time(elapsed: Duration, width: usize) -> String {
+    let text = format!("{:>width$}", format!("{elapsed:.2?}"));
+
+    if elapsed >= Duration::from_secs(1) {
+        paint(text, color(AnsiColor::Red).bold())
+    } else if elapsed >= Duration::from_millis(100) {
+        paint(text, color(AnsiColor::Yellow))
+    } else {
+        text
+    }
+}
+
+/// Formats a table heading.
+pub fn heading(text: String) -> String {
+    paint(text, Style::new().bold())
+}
@@ -0,0 +1,176 @@
+//! A log of the answers submitted to adventofcode.com and how they were
+//! judged, so that the same answer is never submitted twice and a solved part
+//! is never submitted again.
+//!
+//! The log lives in `submissions.tsv` next to the answer cache, with a line for
+//! each judged submission: the Unix timestamp, the year, the day, the part, the
+//! verdict, and the answer. Lines without a year are from before there were
+//! other years, and are for 2024.
+
+use std::{
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc_2024::client::Verdict;
+
+fn path() -> Option<PathBuf> {
+    crate::cache::state_dir().map(|dir| dir.join("submissions.tsv"))
+}
+
+/// A judged submission.
+#[derive(Debug)]
+struct Submission {
+    timestamp: u64,
+    year: u16,
+    day: u8,
+    part: u8,
+    verdict: String,
+    answer: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Submissions {
+    submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Loads the log, which is empty if nothing has been submitted yet.
+    pub fn load() -> Self {
+        let Some(contents) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        let submissions = contents
+            .lines()
+            .filter_map(|line| {
+                let (timestamp, rest) = line.split_once('\t')?;
+                let mut fields = rest.split('\t').collect::<Vec<_>>();
+                if fields.len() == 4 {
+                    fields.insert(0, "2024");
+                }
+
+                let mut fields = fields.into_iter();
+                Some(Submission {
+                    timestamp: timestamp.parse().ok()?,
+                    year: fields.next()?.parse().ok()?,
+                    day: fields.next()?.parse().ok()?,
+                    part: fields.next()?.parse().ok()?,
+                    verdict: fields.next()?.to_string(),
+                    answer: fields.next()?.to_string(),
+                })
+            })
+            .collect();
+
+        Self { submissions }
+    }
+
+    /// Returns the reason not to submit `answer` to `part` of `day`, if the
+    /// part is already solved or the answer has already been judged.
+    pub fn refusal(&self, day: u8, part: u8, answer: &str) -> Option<String> {
+        let mut previous = self.previous(day, part);
+
+        if let Some(solved) = previous
+            .clone()
+            .find(|submission| submission.verdict == Verdict::Correct.to_string())
+        {
+            return Some(format!(
+                "day {day} part {part} was already solved with {}",
+                solved.answer
+            ));
+        }
+
+        previous
+            .find(|submission| submission.answer == answer)
+            .map(|submission| {
+                format!(
+                    "{answer} was already submitted, and was {}",
+                    submission.verdict
+                )
+            })
+    }
+
+    /// Checks `answer` against the log: it's correct if it was accepted, and
+    /// wrong if it was rejected or the part was solved with another answer.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Option<bool> {
+        let correct = Verdict::Correct.to_string();
+
+        self.previous(day, part)
+            .find(|submission| submission.answer == answer || submission.verdict == correct)
+            .map(|submission| submission.answer == answer && submission.verdict == correct)
+    }
+
+    /// Returns whether `part` of `day` has been solved.
+    pub fn solved(&self, day: u8, part: u8) -> bool {
+        let correct = Verdict::Correct.to_string();
+        self.previous(day, part)
+            .any(|submission| submission.verdict == correct)
+    }
+
+    /// Returns when `part` of `day` was first solved, if it has been.
+    pub fn first_correct(&self, day: u8, part: u8) -> Option<u64> {
+        let correct = Verdict::Correct.to_string();
+        self.previous(day, part)
+            .filter(|submission| submission.verdict == correct)
+            .map(|submission| submission.timestamp)
+            .min()
+    }
+
+    /// Returns the submissions for `part` of `day` in the configured year.
+    fn previous(&self, day: u8, part: u8) -> impl Iterator<Item = &Submission> + Clone {
+        let year = aoc_2024::config::get().year;
+
+        self.submissions.iter().filter(move |submission| {
+            submission.year == year && submission.day == day && submission.part == part
+        })
+    }
+
+    /// Appends a submission to the log, if its answer was actually judged.
+    pub fn record(
+        &mut self,
+        day: u8,
+        part: u8,
+        answer: &str,
+        verdict: &Verdict,
+    ) -> Result<(), String> {
+        let judged = matches!(
+            verdict,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        );
+
+        if !judged {
+            return Ok(());
+        }
+
+        let path = path().ok_or("no state directory to store the submissions in")?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        let submission = Submission {
+            timestamp,
+            year: aoc_2024::config::get().year,
+            day,
+            part,
+            verdict: verdict.to_string(),
+            answer: answer.to_string(),
+        };
+
+        let line = format!(
+            "{timestamp}\t{}\t{}\t{}\t{}\t{}\n",
+            submission.year, submission.day, submission.part, submission.verdict, submission.answer
+        );
+        self.submissions.push(submission);
+
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+            })
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|err| format!("failed to append to {}: {err}", path.display()))
+    }
+}
@@ -0,0 +1,324 @@
+//! An interactive view of the days that simulate something, which steps
+//! through the simulation a state at a time.
+//!
+//! Each simulation is driven by the stepping API that its day already has
+//! (like day 6's `Area::next_state`), and drawn with the day's own text
+//! rendering of its state.
+
+use std::{path::PathBuf, time::Duration};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::Stylize,
+    widgets::{Block, Paragraph, Row, Table},
+    DefaultTerminal, Frame,
+};
+
+const HELP: &str = "space play/pause  right/n step  +/- speed  q quit";
+
+/// The number of times the state is redrawn each second while playing, at
+/// most. Faster speeds take several steps between frames.
+const FRAME_RATE: u32 = 30;
+
+/// The fastest speed, in steps per second.
+const MAX_SPEED: u32 = 4096;
+
+/// A simulation that can be stepped through.
+trait Simulation {
+    /// Advances the simulation by a step, returning `false` (and leaving the
+    /// state alone) if it has already finished.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state as text.
+    fn render(&self) -> String;
+
+    /// Returns named counters describing the current state.
+    fn counters(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Day 6's guard, with the positions they have visited marked like in the
+/// puzzle's description.
+#[cfg(feature = "day06")]
+struct Patrol {
+    area: aoc_2024::day06::Area,
+    visited: std::collections::HashSet<(usize, usize)>,
+    turns: usize,
+}
+
+#[cfg(feature = "day06")]
+impl Simulation for Patrol {
+    fn step(&mut self) -> bool {
+        use aoc_2024::day06::Action;
+
+        if self.area.guard_position().is_none() {
+            return false;
+        }
+
+        match self.area.next_state() {
+            Action::Advance { position } => {
+                self.visited.insert(position);
+            }
+            Action::Rotate => self.turns += 1,
+            Action::Leave => {}
+        }
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let guard = self.area.guard_position();
+
+        self.area
+            .to_string()
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        if guard != Some((row, col)) && self.visited.contains(&(row, col)) {
+                            'X'
+                        } else {
+                            c
+                        }
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("visited", self.visited.len().to_string()),
+            ("turns", self.turns.to_string()),
+            (
+                "guard",
+                self.area
+                    .guard_position()
+                    .map_or("gone".to_string(), |(row, col)| format!("({row}, {col})")),
+            ),
+        ]
+    }
+}
+
+/// Day 14's robots, a second at a time. They never stop moving, but their
+/// positions repeat after `WIDTH * HEIGHT` seconds.
+#[cfg(feature = "day14")]
+struct Robots {
+    lobby: aoc_2024::day14::Lobby,
+    seconds: i64,
+}
+
+#[cfg(feature = "day14")]
+impl Simulation for Robots {
+    fn step(&mut self) -> bool {
+        self.seconds += 1;
+        true
+    }
+
+    fn render(&self) -> String {
+        self.lobby.render(self.seconds)
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("seconds", self.seconds.to_string()),
+            (
+                "safety factor",
+                self.lobby.safety_factor(self.seconds).to_string(),
+            ),
+        ]
+    }
+}
+
+/// Day 15's robot, making one of its moves at a time.
+#[cfg(feature = "day15")]
+struct Moves {
+    warehouse: aoc_2024::day15::Warehouse,
+    moves: Vec<aoc_2024::geom::Direction>,
+    /// The number of moves made, whether or not the robot actually moved.
+    made: usize,
+    /// The number of moves that actually moved the robot.
+    moved: usize,
+}
+
+#[cfg(feature = "day15")]
+impl Simulation for Moves {
+    fn step(&mut self) -> bool {
+        let Some(&direction) = self.moves.get(self.made) else {
+            return false;
+        };
+
+        self.made += 1;
+        self.moved += usize::from(self.warehouse.step(direction));
+        true
+    }
+
+    fn render(&self) -> String {
+        self.warehouse.render()
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("moves", format!("{}/{}", self.made, self.moves.len())),
+            ("moved", self.moved.to_string()),
+            (
+                "next move",
+                self.moves
+                    .get(self.made)
+                    .map_or("-".to_string(), |direction| format!("{direction:?}")),
+            ),
+            ("GPS sum", self.warehouse.gps_sum().to_string()),
+        ]
+    }
+}
+
+/// Parses `input` into the simulation for `day`.
+fn simulation(day: u8, input: &str) -> Result<Box<dyn Simulation>, String> {
+    let invalid = |err: aoc_2024::error::AocError| err.in_day(2024, day).to_string();
+
+    match day {
+        #[cfg(feature = "day06")]
+        6 => {
+            let area = aoc_2024::day06::parse(input).map_err(invalid)?;
+
+            Ok(Box::new(Patrol {
+                visited: area.guard_position().into_iter().collect(),
+                area,
+                turns: 0,
+            }))
+        }
+        #[cfg(feature = "day14")]
+        14 => Ok(Box::new(Robots {
+            lobby: aoc_2024::day14::parse(input).map_err(invalid)?,
+            seconds: 0,
+        })),
+        #[cfg(feature = "day15")]
+        15 => {
+            let (warehouse, moves) = aoc_2024::day15::parse(input).map_err(invalid)?;
+
+            Ok(Box::new(Moves {
+                warehouse,
+                moves,
+                made: 0,
+                moved: 0,
+            }))
+        }
+        _ => Err(format!("day {day} has no simulation to visualize")),
+    }
+}
+
+struct Visualizer {
+    day: u8,
+    simulation: Box<dyn Simulation>,
+    steps: usize,
+    playing: bool,
+    finished: bool,
+    /// The number of steps taken each second while playing.
+    speed: u32,
+}
+
+impl Visualizer {
+    /// Takes up to `count` steps, pausing once the simulation finishes.
+    fn step(&mut self, count: u32) {
+        for _ in 0..count {
+            if !self.simulation.step() {
+                self.finished = true;
+                self.playing = false;
+                return;
+            }
+
+            self.steps += 1;
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            // while playing, a frame passes whenever no key is pressed in time
+            if self.playing {
+                let delay = Duration::from_secs(1) / self.speed.min(FRAME_RATE);
+                if !event::poll(delay)? {
+                    self.step((self.speed / FRAME_RATE).max(1));
+                    continue;
+                }
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => self.playing = !self.playing && !self.finished,
+                KeyCode::Right | KeyCode::Char('n') => self.step(1),
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    self.speed = (self.speed * 2).min(MAX_SPEED);
+                }
+                KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(8), Constraint::Length(1)]).areas(frame.area());
+        let [state, counters] =
+            Layout::horizontal([Constraint::Min(20), Constraint::Length(32)]).areas(main);
+
+        let status = match (self.finished, self.playing) {
+            (true, _) => "finished",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+
+        let rows = [
+            ("status", status.to_string()),
+            ("speed", format!("{} steps/s", self.speed)),
+            ("steps", self.steps.to_string()),
+        ]
+        .into_iter()
+        .chain(self.simulation.counters())
+        .map(|(name, value)| Row::new([name.to_string(), value]));
+
+        let table = Table::new(rows, [Constraint::Length(13), Constraint::Min(8)])
+            .block(Block::bordered().title(" counters "));
+
+        let title = format!(" day {} ", self.day);
+        frame.render_widget(
+            Paragraph::new(self.simulation.render()).block(Block::bordered().title(title)),
+            state,
+        );
+        frame.render_widget(table, counters);
+        frame.render_widget(Paragraph::new(HELP).dark_gray(), help);
+    }
+}
+
+pub fn visualize(day: u8, input: Option<PathBuf>, refresh: bool) -> Result<(), String> {
+    let input = match input {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?,
+        None => crate::read_input(day, refresh)?
+            .ok_or_else(|| format!("missing input for day {day}"))?,
+    };
+
+    let mut visualizer = Visualizer {
+        day,
+        simulation: simulation(day, &input)?,
+        steps: 0,
+        playing: false,
+        finished: false,
+        speed: 8,
+    };
+
+    ratatui::run(|terminal| visualizer.run(terminal))
+        .map_err(|err| format!("terminal error: {err}"))
+}
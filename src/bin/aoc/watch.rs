@@ -0,0 +1,153 @@
+//! Re-solves a day whenever its source or input changes, for a faster loop
+//! than `cargo test` while working on a puzzle.
+//!
+//! A change to the source means that this binary is out of date, so each run
+//! goes through `cargo run`, which rebuilds it first. Compiler errors are
+//! passed straight through to stderr.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use clap::ValueEnum;
+
+/// How often to check for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The inputs to solve on each change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Target {
+    /// The first example from the puzzle description
+    Example,
+    /// The real input
+    Input,
+    Both,
+}
+
+/// The latest answer to each part, and whether it matched the documented
+/// answer (for an example).
+type Answers = HashMap<u8, (String, Option<bool>)>;
+
+/// Watches the source of `day` and its input, solving it for `target` at the
+/// start and after each change, until interrupted.
+pub fn watch(day: u8, target: Target) -> Result<(), String> {
+    if aoc_2024::day(day).is_none() {
+        return Err(format!("day {day} is not compiled in"));
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let input = aoc_2024::input::find(day);
+    let examples = [Target::Example, Target::Both].contains(&target);
+    let inputs = [Target::Input, Target::Both].contains(&target);
+
+    let mut previous = [Answers::new(), Answers::new()];
+    let mut modified = last_modified(&root.join("src"), &input);
+
+    println!("watching src/ and {}", input.display());
+
+    loop {
+        let targets = [("example", examples), ("input", inputs)];
+
+        for (index, (name, _)) in targets.into_iter().enumerate().filter(|(_, (_, on))| *on) {
+            let example = index == 0;
+            let Some(answers) = solve(root, day, example) else {
+                println!("{name}: failed to build or solve");
+                continue;
+            };
+
+            let mut parts = answers.keys().copied().collect::<Vec<_>>();
+            parts.sort_unstable();
+
+            for part in parts {
+                let (answer, correct) = &answers[&part];
+                let check = match correct {
+                    Some(_) => format!(" ({})", crate::style::check(*correct, 0)),
+                    None => String::new(),
+                };
+                let change = match previous[index].get(&part) {
+                    Some((old, _)) if old != answer => format!(" (was {old})"),
+                    Some(_) => " (unchanged)".to_string(),
+                    None => String::new(),
+                };
+
+                println!("{name} part {part}: {answer}{check}{change}");
+            }
+
+            previous[index] = answers;
+        }
+
+        // wait for the next change
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let latest = last_modified(&root.join("src"), &input);
+            if latest > modified {
+                modified = latest;
+                break;
+            }
+        }
+
+        println!();
+    }
+}
+
+/// Rebuilds the binary and solves `day`, returning its answers, or `None` if
+/// it didn't build or didn't report any.
+fn solve(root: &Path, day: u8, example: bool) -> Option<Answers> {
+    let config = aoc_2024::config::get();
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(root)
+        .args(["run", "--quiet", "--bin", "aoc"])
+        .args((!cfg!(debug_assertions)).then_some("--release"))
+        .args(["--", "--format", "json", "run", "--day", &day.to_string()])
+        .args(example.then_some("--example"))
+        // carry over any settings from this process's flags
+        .env("AOC_INPUT_DIR", &config.input_dir)
+        .env("AOC_YEAR", config.year.to_string())
+        .stdout(Stdio::piped());
+
+    let output = command.output().ok()?;
+    let answers = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let value = serde_json::from_str::<serde_json::Value>(line).ok()?;
+            let part = value["part"].as_u64()? as u8;
+            let answer = value["answer"].as_str()?.to_string();
+
+            Some((part, (answer, value["correct"].as_bool())))
+        })
+        .collect::<Answers>();
+
+    (!answers.is_empty()).then_some(answers)
+}
+
+/// Returns the latest modification time of `input` and every file under `src`.
+fn last_modified(src: &Path, input: &Path) -> Option<SystemTime> {
+    let mut pending = vec![src.to_path_buf()];
+    let mut latest = modified(input);
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+            match path.is_dir() {
+                true => pending.push(path),
+                false => latest = latest.max(modified(&path)),
+            }
+        }
+    }
+
+    latest
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
@@ -0,0 +1,27 @@
+//! Cooperative cancellation for slow solvers.
+//!
+//! Like [`progress`](crate::progress), this is process-wide rather than
+//! threaded through every part: a caller that gives up on a part calls
+//! [`cancel`], and the slow loops check [`is_cancelled`] and return early. The
+//! answer returned by a cancelled part is meaningless, so the caller should
+//! discard it, and then [`reset`] the flag before starting the next part.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Asks any running solver to stop as soon as it can.
+pub fn cancel() {
+    CANCELLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether the running solver has been asked to stop.
+#[inline]
+pub fn is_cancelled() -> bool {
+    CANCELLED.load(Ordering::Relaxed)
+}
+
+/// Clears a previous cancellation.
+pub fn reset() {
+    CANCELLED.store(false, Ordering::Relaxed);
+}
@@ -0,0 +1,312 @@
+//! Registers every part with cargo-aoc's runner, so that this crate can be
+//! dropped into a `cargo aoc` project as is.
+//!
+//! Each wrapper parses its own input through the [`DAYS`] registry, so no
+//! `#[aoc_generator]` functions are needed.
+//!
+//! [`DAYS`]: crate::DAYS
+
+use aoc_runner_derive::aoc;
+
+/// Runs the given part of a registered day.
+fn solve(day: u8, part: u8, input: &str) -> String {
+    let day = crate::find(2024, day).expect("day is not compiled in");
+
+    // cargo-aoc wants a plain answer, so a bad input can only be a panic
+    match part {
+        1 => day.solve_part_1(input),
+        _ => day
+            .solve_part_2(input)
+            .map(|answer| answer.expect("day has no second part")),
+    }
+    .unwrap_or_else(|err| panic!("{err}"))
+    .to_string()
+}
+
+#[cfg(feature = "day01")]
+#[aoc(day1, part1)]
+pub fn day01_part1(input: &str) -> String {
+    solve(1, 1, input)
+}
+
+#[cfg(feature = "day01")]
+#[aoc(day1, part2)]
+pub fn day01_part2(input: &str) -> String {
+    solve(1, 2, input)
+}
+
+#[cfg(feature = "day02")]
+#[aoc(day2, part1)]
+pub fn day02_part1(input: &str) -> String {
+    solve(2, 1, input)
+}
+
+#[cfg(feature = "day02")]
+#[aoc(day2, part2)]
+pub fn day02_part2(input: &str) -> String {
+    solve(2, 2, input)
+}
+
+#[cfg(feature = "day03")]
+#[aoc(day3, part1)]
+pub fn day03_part1(input: &str) -> String {
+    solve(3, 1, input)
+}
+
+#[cfg(feature = "day03")]
+#[aoc(day3, part2)]
+pub fn day03_part2(input: &str) -> String {
+    solve(3, 2, input)
+}
+
+#[cfg(feature = "day04")]
+#[aoc(day4, part1)]
+pub fn day04_part1(input: &str) -> String {
+    solve(4, 1, input)
+}
+
+#[cfg(feature = "day04")]
+#[aoc(day4, part2)]
+pub fn day04_part2(input: &str) -> String {
+    solve(4, 2, input)
+}
+
+#[cfg(feature = "day05")]
+#[aoc(day5, part1)]
+pub fn day05_part1(input: &str) -> String {
+    solve(5, 1, input)
+}
+
+#[cfg(feature = "day05")]
+#[aoc(day5, part2)]
+pub fn day05_part2(input: &str) -> String {
+    solve(5, 2, input)
+}
+
+#[cfg(feature = "day06")]
+#[aoc(day6, part1)]
+pub fn day06_part1(input: &str) -> String {
+    solve(6, 1, input)
+}
+
+#[cfg(feature = "day06")]
+#[aoc(day6, part2)]
+pub fn day06_part2(input: &str) -> String {
+    solve(6, 2, input)
+}
+
+#[cfg(feature = "day07")]
+#[aoc(day7, part1)]
+pub fn day07_part1(input: &str) -> String {
+    solve(7, 1, input)
+}
+
+#[cfg(feature = "day07")]
+#[aoc(day7, part2)]
+pub fn day07_part2(input: &str) -> String {
+    solve(7, 2, input)
+}
+
+#[cfg(feature = "day08")]
+#[aoc(day8, part1)]
+pub fn day08_part1(input: &str) -> String {
+    solve(8, 1, input)
+}
+
+#[cfg(feature = "day08")]
+#[aoc(day8, part2)]
+pub fn day08_part2(input: &str) -> String {
+    solve(8, 2, input)
+}
+
+#[cfg(feature = "day09")]
+#[aoc(day9, part1)]
+pub fn day09_part1(input: &str) -> String {
+    solve(9, 1, input)
+}
+
+#[cfg(feature = "day09")]
+#[aoc(day9, part2)]
+pub fn day09_part2(input: &str) -> String {
+    solve(9, 2, input)
+}
+
+#[cfg(feature = "day10")]
+#[aoc(day10, part1)]
+pub fn day10_part1(input: &str) -> String {
+    solve(10, 1, input)
+}
+
+#[cfg(feature = "day10")]
+#[aoc(day10, part2)]
+pub fn day10_part2(input: &str) -> String {
+    solve(10, 2, input)
+}
+
+#[cfg(feature = "day11")]
+#[aoc(day11, part1)]
+pub fn day11_part1(input: &str) -> String {
+    solve(11, 1, input)
+}
+
+#[cfg(feature = "day11")]
+#[aoc(day11, part2)]
+pub fn day11_part2(input: &str) -> String {
+    solve(11, 2, input)
+}
+
+#[cfg(feature = "day12")]
+#[aoc(day12, part1)]
+pub fn day12_part1(input: &str) -> String {
+    solve(12, 1, input)
+}
+
+#[cfg(feature = "day12")]
+#[aoc(day12, part2)]
+pub fn day12_part2(input: &str) -> String {
+    solve(12, 2, input)
+}
+
+#[cfg(feature = "day13")]
+#[aoc(day13, part1)]
+pub fn day13_part1(input: &str) -> String {
+    solve(13, 1, input)
+}
+
+#[cfg(feature = "day13")]
+#[aoc(day13, part2)]
+pub fn day13_part2(input: &str) -> String {
+    solve(13, 2, input)
+}
+
+#[cfg(feature = "day14")]
+#[aoc(day14, part1)]
+pub fn day14_part1(input: &str) -> String {
+    solve(14, 1, input)
+}
+
+#[cfg(feature = "day14")]
+#[aoc(day14, part2)]
+pub fn day14_part2(input: &str) -> String {
+    solve(14, 2, input)
+}
+
+#[cfg(feature = "day15")]
+#[aoc(day15, part1)]
+pub fn day15_part1(input: &str) -> String {
+    solve(15, 1, input)
+}
+
+#[cfg(feature = "day15")]
+#[aoc(day15, part2)]
+pub fn day15_part2(input: &str) -> String {
+    solve(15, 2, input)
+}
+
+#[cfg(feature = "day16")]
+#[aoc(day16, part1)]
+pub fn day16_part1(input: &str) -> String {
+    solve(16, 1, input)
+}
+
+#[cfg(feature = "day16")]
+#[aoc(day16, part2)]
+pub fn day16_part2(input: &str) -> String {
+    solve(16, 2, input)
+}
+
+#[cfg(feature = "day17")]
+#[aoc(day17, part1)]
+pub fn day17_part1(input: &str) -> String {
+    solve(17, 1, input)
+}
+
+#[cfg(feature = "day17")]
+#[aoc(day17, part2)]
+pub fn day17_part2(input: &str) -> String {
+    solve(17, 2, input)
+}
+
+#[cfg(feature = "day18")]
+#[aoc(day18, part1)]
+pub fn day18_part1(input: &str) -> String {
+    solve(18, 1, input)
+}
+
+#[cfg(feature = "day18")]
+#[aoc(day18, part2)]
+pub fn day18_part2(input: &str) -> String {
+    solve(18, 2, input)
+}
+
+#[cfg(feature = "day19")]
+#[aoc(day19, part1)]
+pub fn day19_part1(input: &str) -> String {
+    solve(19, 1, input)
+}
+
+#[cfg(feature = "day19")]
+#[aoc(day19, part2)]
+pub fn day19_part2(input: &str) -> String {
+    solve(19, 2, input)
+}
+
+#[cfg(feature = "day20")]
+#[aoc(day20, part1)]
+pub fn day20_part1(input: &str) -> String {
+    solve(20, 1, input)
+}
+
+#[cfg(feature = "day20")]
+#[aoc(day20, part2)]
+pub fn day20_part2(input: &str) -> String {
+    solve(20, 2, input)
+}
+
+#[cfg(feature = "day21")]
+#[aoc(day21, part1)]
+pub fn day21_part1(input: &str) -> String {
+    solve(21, 1, input)
+}
+
+#[cfg(feature = "day21")]
+#[aoc(day21, part2)]
+pub fn day21_part2(input: &str) -> String {
+    solve(21, 2, input)
+}
+
+#[cfg(feature = "day22")]
+#[aoc(day22, part1)]
+pub fn day22_part1(input: &str) -> String {
+    solve(22, 1, input)
+}
+
+#[cfg(feature = "day22")]
+#[aoc(day22, part2)]
+pub fn day22_part2(input: &str) -> String {
+    solve(22, 2, input)
+}
+
+#[cfg(feature = "day23")]
+#[aoc(day23, part1)]
+pub fn day23_part1(input: &str) -> String {
+    solve(23, 1, input)
+}
+
+#[cfg(feature = "day23")]
+#[aoc(day23, part2)]
+pub fn day23_part2(input: &str) -> String {
+    solve(23, 2, input)
+}
+
+#[cfg(feature = "day24")]
+#[aoc(day24, part1)]
+pub fn day24_part1(input: &str) -> String {
+    solve(24, 1, input)
+}
+
+#[cfg(feature = "day24")]
+#[aoc(day24, part2)]
+pub fn day24_part2(input: &str) -> String {
+    solve(24, 2, input)
+}
@@ -0,0 +1,227 @@
+//! A minimal client for adventofcode.com, which can download inputs and submit
+//! answers.
+//!
+//! Requests are made by running `curl`, rather than by pulling an HTTP and TLS
+//! stack into the crate for the sake of a handful of requests. The session
+//! token is passed to `curl` on stdin so that it never shows up in the
+//! process list.
+
+use std::{
+    fmt::Display,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::config;
+
+/// Identifies this tool to the AoC servers, as their automation guidelines ask.
+pub const USER_AGENT: &str = "github.com/eikopf/advent-of-code-2024 via curl";
+
+#[derive(Debug)]
+pub enum Error {
+    /// There is no session token to authenticate with.
+    NoSession,
+    /// `curl` could not be run at all.
+    Curl(std::io::Error),
+    /// The request failed, with `curl`'s description of why.
+    Request(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSession => write!(f, "no session token (set AOC_SESSION)"),
+            Self::Curl(err) => write!(f, "failed to run curl: {err}"),
+            Self::Request(message) => write!(f, "request failed: {message}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(v: std::io::Error) -> Self {
+        Self::Curl(v)
+    }
+}
+
+/// Returns the URL of the configured year's event.
+fn event_url() -> String {
+    format!("https://adventofcode.com/{}", config::get().year)
+}
+
+/// Makes a request to `url` as the user with the given `session` token, and
+/// returns the body of the response.
+fn request(url: &str, session: &str, args: &[&str]) -> Result<String, Error> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", USER_AGENT])
+        // read the cookie header from stdin
+        .args(["--header", "@-"])
+        .args(args)
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "Cookie: session={session}")?;
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Request(message.trim().to_string()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| Error::Request("body is not UTF-8".to_string()))
+}
+
+/// Downloads the puzzle input for `day`.
+pub fn download_input(day: u8, session: &str) -> Result<String, Error> {
+    request(&format!("{}/day/{day}/input", event_url()), session, &[])
+}
+
+/// Downloads the HTML page of the puzzle for `day`, which includes the
+/// description of part 2 once part 1 is solved.
+pub fn download_puzzle(day: u8, session: &str) -> Result<String, Error> {
+    request(&format!("{}/day/{day}", event_url()), session, &[])
+}
+
+/// The outcome of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, but the response doesn't say in which direction.
+    Incorrect,
+    /// An answer was submitted too recently, with the remaining wait (e.g.
+    /// `"1m 2s"`) if the response includes it.
+    RateLimited(Option<String>),
+    /// The part has already been solved, or isn't unlocked yet.
+    WrongLevel,
+    /// The response wasn't recognised; this holds its text.
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong (too high)"),
+            Self::TooLow => write!(f, "wrong (too low)"),
+            Self::Incorrect => write!(f, "wrong"),
+            Self::RateLimited(Some(wait)) => write!(f, "rate limited ({wait} left to wait)"),
+            Self::RateLimited(None) => write!(f, "rate limited"),
+            Self::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Self::Unknown(text) => write!(f, "unrecognised response: {text}"),
+        }
+    }
+}
+
+/// Extracts the verdict from the HTML page returned for a submission.
+///
+/// The verdict is the text of the page's `<article>`, whose wording has been
+/// stable for years, so this just searches it for the usual phrases.
+pub fn parse_verdict(page: &str) -> Verdict {
+    let article = page
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    if article.contains("That's the right answer") {
+        Verdict::Correct
+    } else if article.contains("That's not the right answer") {
+        if article.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if article.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if article.contains("You gave an answer too recently") {
+        let wait = article
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+
+        Verdict::RateLimited(wait)
+    } else if article.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(strip_tags(article).trim().to_string())
+    }
+}
+
+/// Removes everything between angle brackets from `html`.
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Submits `answer` as the solution to `part` of `day`.
+pub fn submit_answer(day: u8, part: u8, answer: &str, session: &str) -> Result<Verdict, Error> {
+    let level = format!("level={part}");
+    let answer = format!("answer={answer}");
+    let page = request(
+        &format!("{}/day/{day}/answer", event_url()),
+        session,
+        &["--data-urlencode", &level, "--data-urlencode", &answer],
+    )?;
+
+    Ok(parse_verdict(&page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><main><article><p>{article}</p></article></main></html>")
+    }
+
+    #[test]
+    fn verdicts() {
+        let verdict = |article| parse_verdict(&page(article));
+
+        assert_eq!(
+            verdict("That's the right answer! You are <em>one gold star</em> closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high. Please wait."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            verdict("You gave an answer too recently. You have 1m 2s left to wait."),
+            Verdict::RateLimited(Some("1m 2s".to_string()))
+        );
+        assert_eq!(
+            verdict("You don't seem to be solving the right level.  Did you already complete it?"),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            verdict("Something <em>else</em> happened."),
+            Verdict::Unknown("Something else happened.".to_string())
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Layered configuration for the tooling around the solutions.
+//!
+//! Settings are read from `$XDG_CONFIG_HOME/aoc2024/config.toml` (or
+//! `~/.config/aoc2024/config.toml`), then overridden by environment variables,
+//! and finally by anything the caller changes before passing the result to
+//! [`set`], which is how the `aoc` CLI applies its flags.
+//!
+//! The file is a flat list of TOML `key = value` lines:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! input_dir = "/home/me/aoc/inputs"
+//! year = 2024
+//! threads = 8
+//! ```
+//!
+//! | key         | variable        | default                      |
+//! |-------------|-----------------|------------------------------|
+//! | `session`   | `AOC_SESSION`   | the `token` in aocd's cache  |
+//! | `input_dir` | `AOC_INPUT_DIR` | the crate's `input/`         |
+//! | `year`      | `AOC_YEAR`      | 2024                         |
+//! | `threads`   | `AOC_THREADS`   | one per core                 |
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The session token for adventofcode.com, if it's configured here rather
+    /// than left to aocd.
+    pub session: Option<String>,
+    /// The directory holding the `dayNN.txt` inputs.
+    pub input_dir: PathBuf,
+    /// The year of the event, used in requests and cached file names.
+    pub year: u16,
+    /// The number of threads for parallel solvers, or `None` for one per core.
+    pub threads: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            input_dir: Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+            year: 2024,
+            threads: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The config file couldn't be read.
+    Io(PathBuf, String),
+    /// A line of the config file isn't a `key = value` pair.
+    Malformed(usize),
+    /// A line of the config file sets a key that doesn't exist.
+    UnknownKey(usize, String),
+    /// A key in the config file, or an environment variable, has an invalid
+    /// value.
+    InvalidValue(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "failed to read {}: {err}", path.display()),
+            Self::Malformed(line) => write!(f, "line {line} of the config is not `key = value`"),
+            Self::UnknownKey(line, key) => {
+                write!(f, "line {line} of the config sets unknown key {key}")
+            }
+            Self::InvalidValue(key) => write!(f, "invalid value for {key}"),
+        }
+    }
+}
+
+impl Config {
+    /// Returns the path of the config file, if it can be determined.
+    pub fn path() -> Option<PathBuf> {
+        let base = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(&std::env::var_os("HOME")?).join(".config"),
+        };
+
+        Some(base.join("aoc2024").join("config.toml"))
+    }
+
+    /// Loads the defaults, overridden by the config file (if it exists) and
+    /// then by the environment.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+
+        if let Some(path) = Self::path().filter(|path| path.is_file()) {
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| ConfigError::Io(path.clone(), err.to_string()))?;
+
+            config.apply_file(&contents)?;
+        }
+
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// Applies the settings in the contents of a config file.
+    fn apply_file(&mut self, contents: &str) -> Result<(), ConfigError> {
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(ConfigError::Malformed(index + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+            let invalid = || ConfigError::InvalidValue(key.to_string());
+
+            match key {
+                "session" => self.session = Some(parse_string(value).ok_or_else(invalid)?),
+                "input_dir" => self.input_dir = parse_string(value).ok_or_else(invalid)?.into(),
+                "year" => self.year = parse_integer(value).ok_or_else(invalid)?,
+                "threads" => self.threads = Some(parse_integer(value).ok_or_else(invalid)?),
+                _ => return Err(ConfigError::UnknownKey(index + 1, key.to_string())),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the settings given by environment variables.
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let invalid = |name: &str| ConfigError::InvalidValue(name.to_string());
+
+        if let Some(session) = var("AOC_SESSION") {
+            self.session = Some(session.trim().to_string());
+        }
+
+        if let Some(dir) = var("AOC_INPUT_DIR") {
+            self.input_dir = dir.into();
+        }
+
+        if let Some(year) = var("AOC_YEAR") {
+            self.year = year.trim().parse().map_err(|_| invalid("AOC_YEAR"))?;
+        }
+
+        if let Some(threads) = var("AOC_THREADS") {
+            self.threads = Some(threads.trim().parse().map_err(|_| invalid("AOC_THREADS"))?);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a basic TOML string (without escapes), followed by an optional
+/// comment.
+pub(crate) fn parse_string(value: &str) -> Option<String> {
+    let (string, rest) = value.strip_prefix('"')?.split_once('"')?;
+    let rest = rest.trim();
+
+    (rest.is_empty() || rest.starts_with('#')).then(|| string.to_string())
+}
+
+/// Parses a TOML integer, which may contain underscores between its digits,
+/// followed by an optional comment.
+pub(crate) fn parse_integer<T: FromStr>(value: &str) -> Option<T> {
+    let (digits, _) = value.split_once('#').unwrap_or((value, ""));
+    digits.trim().replace('_', "").parse().ok()
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Returns the configuration for this process, loading it on first use if
+/// [`set`] hasn't been called.
+///
+/// # Panics
+///
+/// Panics if the configuration has to be loaded, and the config file or the
+/// environment is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().unwrap_or_else(|err| panic!("{err}")))
+}
+
+/// Sets the configuration for this process, returning `false` (and leaving it
+/// unchanged) if it has already been set or used.
+pub fn set(config: Config) -> bool {
+    CONFIG.set(config).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_file() {
+        let mut config = Config::default();
+        let contents = r#"
+            # comments and blank lines are ignored
+            session = "abc123" # so are trailing comments
+            input_dir = "/tmp/inputs"
+            year = 2_023
+            threads = 4
+        "#;
+
+        config.apply_file(contents).unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.input_dir, Path::new("/tmp/inputs"));
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.threads, Some(4));
+    }
+
+    #[test]
+    fn invalid_config_file() {
+        let apply = |contents| Config::default().apply_file(contents);
+
+        assert_eq!(apply("year 2024"), Err(ConfigError::Malformed(1)));
+        assert_eq!(
+            apply("\nyears = 2024"),
+            Err(ConfigError::UnknownKey(2, "years".to_string()))
+        );
+        assert_eq!(
+            apply("session = abc"),
+            Err(ConfigError::InvalidValue("session".to_string()))
+        );
+        assert_eq!(
+            apply("threads = many"),
+            Err(ConfigError::InvalidValue("threads".to_string()))
+        );
+    }
+}
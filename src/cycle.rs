@@ -0,0 +1,200 @@
+//! Finding where a sequence of states starts repeating, given its first state
+//! and a function from each state to the next.
+//!
+//! Some days ask about the state of something after far more steps than could
+//! be simulated, or whether it ever comes back to a state it has been in. If
+//! each state is determined by the one before it, then once a state repeats
+//! the whole sequence goes around a [`Cycle`] forever, and both questions come
+//! down to finding it. A step function can also return `None` to end the
+//! sequence, which then has no cycle.
+//!
+//! [`brent`] finds the cycle in constant memory, but steps through the
+//! sequence about three times over, and only recovers the states afterwards by
+//! stepping again. If states are hashable and stepping is expensive,
+//! [`with_history`] steps once and keeps every state instead.
+
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use hashbrown::hash_map::Entry;
+
+use crate::hash::HashMap;
+
+/// The cycle at the end of an infinite sequence of states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state in the cycle.
+    pub start: usize,
+    /// The number of states in the cycle, which is never 0.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Returns the index of the first state equal to the one at index `n`,
+    /// which is always less than `start + period`.
+    pub fn reduce(&self, n: usize) -> usize {
+        match n.checked_sub(self.start) {
+            Some(offset) => self.start + offset % self.period,
+            None => n,
+        }
+    }
+}
+
+/// Finds the cycle in the sequence starting at `initial` with Brent's
+/// algorithm, or returns `None` if `step` ends the sequence first.
+///
+/// `step` is called on states more than once, so it must always return the
+/// same next state for the same state.
+pub fn brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    // the hare runs ahead while the tortoise waits at each power of two, and
+    // once the hare meets it again they're a period apart
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        hare = step(&hare)?;
+        period += 1;
+    }
+
+    // the cycle starts where two states a period apart are first equal
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare)?;
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, period })
+}
+
+/// Every distinct state of a sequence, and its cycle if it has one.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Option<Cycle>,
+}
+
+impl<S> History<S> {
+    /// Returns the sequence's cycle, or `None` if it ended.
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Returns the states up to the end of the first time around the cycle,
+    /// or all of them if the sequence ended.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// Returns the state at index `n`, or `None` if the sequence ended first.
+    pub fn get(&self, n: usize) -> Option<&S> {
+        match self.cycle {
+            Some(cycle) => self.states.get(cycle.reduce(n)),
+            None => self.states.get(n),
+        }
+    }
+}
+
+/// Steps through the sequence starting at `initial` until a state repeats or
+/// `step` ends it, remembering every state on the way.
+pub fn with_history<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> History<S> {
+    let mut indices = HashMap::default();
+    let mut states = Vec::new();
+    let mut state = Some(initial);
+
+    while let Some(current) = state {
+        match indices.entry(current.clone()) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let cycle = Cycle {
+                    start,
+                    period: states.len() - start,
+                };
+
+                return History {
+                    states,
+                    cycle: Some(cycle),
+                };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(states.len());
+            }
+        }
+
+        state = step(&current);
+        states.push(current);
+    }
+
+    History {
+        states,
+        cycle: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Squaring modulo 1000 from 3 goes 3, 9, 81, 561, 721, 841, 281, 961, 521,
+    /// 441, 481, 361, 321, 41, 681, 761, 121, 641, 881, 161, 921, 241, 81, ...
+    fn square(&x: &u64) -> Option<u64> {
+        Some(x * x % 1000)
+    }
+
+    #[test]
+    fn cycles() {
+        let expected = Cycle {
+            start: 2,
+            period: 20,
+        };
+        assert_eq!(brent(3, square), Some(expected));
+
+        let history = with_history(3, square);
+        assert_eq!(history.cycle(), Some(expected));
+        assert_eq!(history.states().len(), 22);
+        assert_eq!(history.get(1), Some(&9));
+        assert_eq!(history.get(1_000_000_002), Some(&81));
+        assert_eq!(history.get(1_000_000_003), Some(&561));
+
+        // a fixed point is a cycle of one
+        assert_eq!(
+            brent(0, square),
+            Some(Cycle {
+                start: 0,
+                period: 1
+            })
+        );
+    }
+
+    #[test]
+    fn endings() {
+        let countdown = |&x: &u32| x.checked_sub(1);
+        assert_eq!(brent(5, countdown), None);
+
+        let history = with_history(5, countdown);
+        assert_eq!(history.cycle(), None);
+        assert_eq!(history.states(), [5, 4, 3, 2, 1, 0]);
+        assert_eq!(history.get(5), Some(&0));
+        assert_eq!(history.get(6), None);
+    }
+}
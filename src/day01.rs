@@ -1,10 +1,16 @@
-use std::{collections::HashMap, str::FromStr};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use crate::stream::{LineReader, StreamError};
+use crate::{arith, error::AocError, hash::HashMap, parse, Answer};
 
 /// The number of lines in the problem input.
 const LINES: usize = 1000;
 
-/// The two lists in the input.
+/// The two lists in the input, each sorted in ascending order.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Data {
     left: Vec<u32>,
     right: Vec<u32>,
@@ -24,66 +30,124 @@ impl Data {
     }
 
     /// Computes the solution for part 1 of the problem.
-    pub fn total_difference(mut self) -> u32 {
-        self.sort_unstable();
-
+    pub fn total_difference(&self) -> u32 {
         self.left
-            .into_iter()
-            .zip(self.right)
-            .fold(0u32, |total, (left, right)| total + left.abs_diff(right))
+            .iter()
+            .zip(&self.right)
+            .fold(0u32, |total, (&left, &right)| {
+                arith::add(total, left.abs_diff(right))
+            })
     }
 
     /// Computes the solution for part 2 of the problem
-    pub fn similarity_score(self) -> u32 {
-        let Data { left, mut right } = self;
-        right.sort_unstable();
+    pub fn similarity_score(&self) -> u32 {
+        let Data { left, right } = self;
 
         // 574 is the exact number of unique IDs in the right list
-        let mut occurrences = HashMap::with_capacity(574);
+        let mut occurrences = HashMap::with_capacity_and_hasher(574, Default::default());
 
-        for n in right {
+        for &n in right {
             let prev = *occurrences.get(&n).unwrap_or(&0);
-            occurrences.insert(n, prev + n);
+            occurrences.insert(n, arith::add(prev, n));
         }
 
-        dbg!(occurrences.len());
-
         left.iter()
-            .fold(0, |total, n| total + occurrences.get(n).unwrap_or(&0))
+            .fold(0, |total, n| arith::add(total, *occurrences.get(n).unwrap_or(&0)))
+    }
+}
+
+/// Parses the location ID at the start of `bytes` (a slice of `input`), after
+/// any whitespace.
+fn parse_id<'a>(input: &[u8], bytes: &'a [u8]) -> Result<(u32, &'a [u8]), AocError> {
+    let bytes = bytes.trim_ascii_start();
+
+    match parse::parse_uint_checked(bytes) {
+        (_, rest) if rest.len() == bytes.len() => {
+            Err(AocError::at_bytes(input, bytes, "expected a location ID"))
+        }
+        (id, rest) => {
+            let id = id
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(|| AocError::at_bytes(input, bytes, "location ID is too large"))?;
+
+            Ok((id, rest))
+        }
     }
 }
 
 impl FromStr for Data {
-    type Err = std::num::ParseIntError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut data = Data::with_capacity(LINES);
-        let mut raw_digits = s.split_whitespace();
+        let mut rest = s.as_bytes().trim_ascii();
 
         // we assume the input lists are of equal length, so we can always
         // take two elements at a time
-        loop {
-            match raw_digits.next() {
-                None => break,
-                Some(first) => {
-                    let first = first.parse::<u32>()?;
-                    let second = raw_digits.next().unwrap().parse::<u32>()?;
-
-                    data.left.push(first);
-                    data.right.push(second);
-                }
-            }
+        while !rest.is_empty() {
+            let (first, tail) = parse_id(s.as_bytes(), rest)?;
+            let (second, tail) = parse_id(s.as_bytes(), tail)?;
+
+            data.left.push(first);
+            data.right.push(second);
+            rest = tail.trim_ascii_start();
         }
 
+        data.sort_unstable();
         Ok(data)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn parse(input: &str) -> Result<Data, AocError> {
+    input.parse()
+}
+
+/// Solves both parts from `reader`, a line at a time.
+///
+/// Both parts need the whole of each list, but only as numbers, so this holds
+/// much less in memory than [`parse`] does with the input's text.
+#[cfg(feature = "std")]
+pub fn solve_reader(reader: impl std::io::BufRead) -> Result<(Answer, Answer), StreamError> {
+    let mut data = Data::with_capacity(LINES);
+
+    LineReader::new(reader).for_each(|line| {
+        let bytes = line.as_bytes();
+        if bytes.trim_ascii().is_empty() {
+            return Ok(());
+        }
+
+        let (first, rest) = parse_id(bytes, bytes)?;
+        let (second, rest) = parse_id(bytes, rest)?;
+
+        let rest = rest.trim_ascii();
+        if !rest.is_empty() {
+            return Err(AocError::at_bytes(
+                bytes,
+                rest,
+                "expected two location IDs per line",
+            ));
+        }
 
-    const EXAMPLE: &str = r#"
+        data.left.push(first);
+        data.right.push(second);
+        Ok(())
+    })?;
+
+    data.sort_unstable();
+    Ok((part1(&data), part2(&data)))
+}
+
+/// Computes the solution to part 1.
+pub fn part1(data: &Data) -> Answer {
+    data.total_difference().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(data: &Data) -> Answer {
+    data.similarity_score().into()
+}
+
+pub const EXAMPLE: &str = r#"
             3   4
             4   3
             2   5
@@ -92,29 +156,30 @@ mod tests {
             3   3
             "#;
 
-    const INPUT: &str = include_str!("../input/day01.txt");
-
-    #[test]
-    fn example_part_1() {
-        let data: Data = EXAMPLE.parse().unwrap();
-        assert_eq!(data.total_difference(), 11);
-    }
-
-    #[test]
-    fn example_part_2() {
-        let data: Data = EXAMPLE.parse().unwrap();
-        assert_eq!(data.similarity_score(), 31);
-    }
+aoc_day! {
+    day: 1,
+    parsed: Data,
+    example_part_1: EXAMPLE => 11,
+    example_part_2: EXAMPLE => 31,
+    answer_part_1: 1320851,
+    answer_part_2: 26859182,
+}
 
-    #[test]
-    fn part_1() {
-        let data: Data = INPUT.parse().unwrap();
-        assert_eq!(data.total_difference(), 1320851);
-    }
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
 
     #[test]
-    fn part_2() {
-        let data: Data = INPUT.parse().unwrap();
-        assert_eq!(data.similarity_score(), 26859182);
+    fn example_reader() {
+        assert_eq!(
+            solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            (11u32.into(), 31u32.into())
+        );
+        assert_eq!(
+            solve_reader("3   4\n4   3 5\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 7: expected two location IDs per line"
+        );
     }
 }
@@ -1,3 +1,9 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::stream::{LineReader, StreamError};
+use crate::{error::AocError, parse, Answer};
+
 /// Computes the first difference of the given vector.
 ///
 /// # Safety
@@ -17,7 +23,7 @@ unsafe fn diff(mut elems: Vec<u8>) -> Vec<i8> {
     elems.pop();
 
     // SAFETY: all u8s are valid i8s
-    unsafe { std::mem::transmute(elems) }
+    unsafe { core::mem::transmute(elems) }
 }
 
 /// Returns the index of the single problematic item in the report (if any)
@@ -57,14 +63,15 @@ fn find_first_problem(differences: &[i8]) -> Option<usize> {
     })
 }
 
+/// Whether the levels of a safe report are all increasing or all decreasing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i8)]
-pub enum Direction {
+pub enum Trend {
     Increasing = 1,
     Decreasing = -1,
 }
 
-impl TryFrom<i8> for Direction {
+impl TryFrom<i8> for Trend {
     type Error = ();
 
     fn try_from(value: i8) -> Result<Self, Self::Error> {
@@ -76,13 +83,13 @@ impl TryFrom<i8> for Direction {
     }
 }
 
-impl Direction {
+impl Trend {
     pub fn from_report(report: Vec<u8>) -> Option<Self> {
         let differences = unsafe { diff(report) };
         let (first, tail) = differences.split_first()?;
 
-        // get the direction from the first element
-        let direction = match first.signum() {
+        // get the trend from the first element
+        let trend = match first.signum() {
             -1 => Some(Self::Decreasing),
             1 => Some(Self::Increasing),
             _ => None,
@@ -95,13 +102,13 @@ impl Direction {
 
         // check that all remaining elements are correctly signed and bounded
         for d in tail {
-            match d.signum() == (direction as i8) && (1..=3).contains(&d.unsigned_abs()) {
+            match d.signum() == (trend as i8) && (1..=3).contains(&d.unsigned_abs()) {
                 false => return None,
                 true => continue,
             }
         }
 
-        Some(direction)
+        Some(trend)
     }
 
     pub fn from_report_with_dampener(mut report: Vec<u8>) -> Option<Self> {
@@ -123,41 +130,67 @@ impl Direction {
     }
 }
 
+/// Parses the levels of the report on `line`, a line of `input`.
+fn parse_report(input: &str, line: &str) -> Result<Vec<u8>, AocError> {
+    line.split_whitespace()
+        .map(|level| match parse::number(input, level)? {
+            // `diff` relies on the levels fitting in an `i8`
+            101.. => Err(AocError::at(input, level, "levels can't be above 100")),
+            level => Ok(level),
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u8>>, AocError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_report(input, line))
+        .collect()
+}
+
+/// Solves both parts from `reader`, checking each report as it's read.
+#[cfg(feature = "std")]
+pub fn solve_reader(reader: impl std::io::BufRead) -> Result<(Answer, Answer), StreamError> {
+    let (mut safe, mut dampened) = (0usize, 0usize);
+
+    LineReader::new(reader).for_each(|line| {
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let report = parse_report(line, line)?;
+        safe += usize::from(Trend::from_report(report.clone()).is_some());
+        dampened += usize::from(Trend::from_report_with_dampener(report).is_some());
+        Ok(())
+    })?;
+
+    Ok((safe.into(), dampened.into()))
+}
+
 /// Computes the solution to part 1.
-pub fn count_safe_reports(reports: &str) -> usize {
+pub fn part1(reports: &[Vec<u8>]) -> Answer {
     reports
-        .split_terminator('\n')
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<u8>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .filter(|v| !v.is_empty())
-        .map(Direction::from_report)
+        .iter()
+        .cloned()
+        .map(Trend::from_report)
         .filter(Option::is_some)
         .count()
+        .into()
 }
 
 /// Computes the solution to part 2.
-pub fn count_safe_dampened_reports(reports: &str) -> usize {
+pub fn part2(reports: &[Vec<u8>]) -> Answer {
     reports
-        .split_terminator('\n')
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<u8>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .filter(|v| !v.is_empty())
-        .map(Direction::from_report_with_dampener)
+        .iter()
+        .cloned()
+        .map(Trend::from_report_with_dampener)
         .filter(Option::is_some)
         .count()
+        .into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE: &str = r#"
+pub const EXAMPLE: &str = r#"
         7 6 4 2 1
         1 2 7 8 9
         9 7 6 2 1
@@ -166,25 +199,30 @@ mod tests {
         1 3 6 7 9
         "#;
 
-    const INPUT: &str = include_str!("../input/day02.txt");
-
-    #[test]
-    fn example_part_1() {
-        assert_eq!(count_safe_reports(EXAMPLE), 2);
-    }
-
-    #[test]
-    fn part_1() {
-        assert_eq!(count_safe_reports(INPUT), 591);
-    }
+aoc_day! {
+    day: 2,
+    parsed: Vec<Vec<u8>>,
+    example_part_1: EXAMPLE => 2,
+    example_part_2: EXAMPLE => 4,
+    answer_part_1: 591,
+    answer_part_2: 621,
+}
 
-    #[test]
-    fn example_part_2() {
-        assert_eq!(count_safe_dampened_reports(EXAMPLE), 4);
-    }
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
 
     #[test]
-    fn part_2() {
-        assert_eq!(count_safe_dampened_reports(INPUT), 621);
+    fn example_reader() {
+        assert_eq!(
+            solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            (2usize.into(), 4usize.into())
+        );
+        assert_eq!(
+            solve_reader("1 2 3\n\n4 5 101\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 5: levels can't be above 100"
+        );
     }
 }
@@ -1,28 +1,33 @@
+use alloc::vec::Vec;
+#[cfg(feature = "logos")]
 use logos::{Lexer, Logos};
 
+use crate::{error::AocError, Answer};
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum LexError {
-    Int(std::num::ParseIntError),
+    Int(core::num::ParseIntError),
     #[default]
     Unknown,
 }
 
-impl From<std::num::ParseIntError> for LexError {
-    fn from(v: std::num::ParseIntError) -> Self {
+impl From<core::num::ParseIntError> for LexError {
+    fn from(v: core::num::ParseIntError) -> Self {
         Self::Int(v)
     }
 }
 
-#[derive(Debug, Clone, Copy, Logos)]
-#[logos(error = LexError)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "logos", derive(Logos), logos(error = LexError))]
 pub enum Token {
-    #[regex(r#"mul\([0-9]+\,[0-9]+\)"#, process_mul)]
+    #[cfg_attr(feature = "logos", regex(r#"mul\([0-9]+\,[0-9]+\)"#, process_mul))]
     Mul((usize, usize)),
-    #[token(r#"do()"#)]
+    #[cfg_attr(feature = "logos", token(r#"do()"#))]
     Do,
-    #[token(r#"don't()"#)]
+    #[cfg_attr(feature = "logos", token(r#"don't()"#))]
     Dont,
-    #[regex(".")]
+    #[cfg_attr(feature = "logos", regex("."))]
     Junk,
 }
 
@@ -36,7 +41,8 @@ impl Token {
     }
 }
 
-fn process_mul(lexer: &mut Lexer<Token>) -> Result<(usize, usize), std::num::ParseIntError> {
+#[cfg(feature = "logos")]
+fn process_mul(lexer: &mut Lexer<Token>) -> Result<(usize, usize), core::num::ParseIntError> {
     let (lhs, tail) = lexer
         .slice()
         .strip_prefix("mul(")
@@ -49,58 +55,105 @@ fn process_mul(lexer: &mut Lexer<Token>) -> Result<(usize, usize), std::num::Par
     Ok((lhs, rhs))
 }
 
-/// Computes the solution to part 1.
-pub fn uncorrupted_mul_sum(input: &str) -> usize {
+/// Returns an iterator over the tokens in `input`.
+#[cfg(feature = "logos")]
+pub fn tokens(input: &str) -> impl Iterator<Item = Result<Token, LexError>> + '_ {
     Token::lexer(input)
-        .filter_map(|tok| tok.ok().and_then(Token::as_mul))
-        .map(|(lhs, rhs)| lhs * rhs)
-        .sum()
 }
 
-/// Computes the answer to part 2.
-pub fn enabled_mul_sum(input: &str) -> usize {
-    Token::lexer(input)
+/// Returns an iterator over the tokens in `input`.
+///
+/// This hand-rolled scanner matches the same language as the `logos` lexer:
+/// at each position it tries the three instructions in turn, and otherwise
+/// skips a single character of junk.
+#[cfg(not(feature = "logos"))]
+pub fn tokens(input: &str) -> impl Iterator<Item = Result<Token, LexError>> + '_ {
+    let mut rest = input;
+
+    core::iter::from_fn(move || {
+        let c = rest.chars().next()?;
+
+        let (token, len) = if let Some((mul, len)) = scan_mul(rest) {
+            (mul, len)
+        } else if rest.starts_with("do()") {
+            (Ok(Token::Do), "do()".len())
+        } else if rest.starts_with("don't()") {
+            (Ok(Token::Dont), "don't()".len())
+        } else {
+            (Ok(Token::Junk), c.len_utf8())
+        };
+
+        rest = &rest[len..];
+        Some(token)
+    })
+}
+
+/// Scans a `mul(X,Y)` instruction at the start of `s`, returning the token
+/// and its length in bytes.
+#[cfg(not(feature = "logos"))]
+fn scan_mul(s: &str) -> Option<(Result<Token, LexError>, usize)> {
+    fn digits(s: &str) -> Option<(&str, &str)> {
+        let len = s.bytes().take_while(u8::is_ascii_digit).count();
+        Some(s.split_at(len)).filter(|_| len > 0)
+    }
+
+    let tail = s.strip_prefix("mul(")?;
+    let (lhs, tail) = digits(tail)?;
+    let (rhs, tail) = digits(tail.strip_prefix(',')?)?;
+    let tail = tail.strip_prefix(')')?;
+
+    let mul = || Ok(Token::Mul((lhs.parse()?, rhs.parse()?)));
+    Some((mul(), s.len() - tail.len()))
+}
+
+/// Returns the well-formed instructions in `input`, skipping the junk.
+///
+/// The input is corrupted by design, so this never fails: anything that isn't
+/// an instruction (including a `mul` whose operands overflow) is junk.
+pub fn parse(input: &str) -> Result<Vec<Token>, AocError> {
+    Ok(tokens(input)
         .filter_map(Result::ok)
-        .fold((true, 0usize), |(state, total), next| match next {
+        .filter(|token| !matches!(token, Token::Junk))
+        .collect())
+}
+
+/// Computes the solution to part 1.
+pub fn part1(tokens: &[Token]) -> Answer {
+    tokens
+        .iter()
+        .filter_map(|tok| tok.as_mul())
+        .map(|(lhs, rhs)| lhs * rhs)
+        .sum::<usize>()
+        .into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(tokens: &[Token]) -> Answer {
+    tokens
+        .iter()
+        .fold((true, 0usize), |(state, total), next| match *next {
             Token::Mul((lhs, rhs)) if state => (state, total + (lhs * rhs)),
             Token::Do => (true, total),
             Token::Dont => (false, total),
             _ => (state, total),
         })
         .1
+        .into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_PART1: &str = r#"
+pub const EXAMPLE_PART1: &str = r#"
         xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
             "#;
 
-    const EXAMPLE_PART2: &str = r#"
+pub const EXAMPLE_PART2: &str = r#"
         xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
         "#;
 
-    const INPUT: &str = include_str!("../input/day03.txt");
-
-    #[test]
-    fn example_part_1() {
-        assert_eq!(uncorrupted_mul_sum(EXAMPLE_PART1), 161);
-    }
-
-    #[test]
-    fn part_1() {
-        assert_eq!(uncorrupted_mul_sum(INPUT), 170068701);
-    }
-
-    #[test]
-    fn example_part_2() {
-        assert_eq!(enabled_mul_sum(EXAMPLE_PART2), 48);
-    }
-
-    #[test]
-    fn part_2() {
-        assert_eq!(enabled_mul_sum(INPUT), 78683433);
-    }
+aoc_day! {
+    day: 3,
+    parsed: Vec<Token>,
+    example_part_1: EXAMPLE_PART1 => 161,
+    example_part_2: EXAMPLE_PART2 => 48,
+    answer_part_1: 170068701,
+    answer_part_2: 78683433,
 }
@@ -1,8 +1,13 @@
-use std::str::FromStr;
+use alloc::{format, vec};
+use core::str::FromStr;
 
-use nalgebra as na;
+use crate::error::AocError;
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::Answer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Xmas {
     X,
@@ -26,150 +31,113 @@ impl TryFrom<char> for Xmas {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XmasGrid {
-    grid: na::DMatrix<Xmas>,
+    grid: Grid<Xmas>,
 }
 
 impl FromStr for XmasGrid {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s
-            .split_whitespace()
-            .flat_map(str::chars)
-            .map(|c| Xmas::try_from(c).unwrap())
-            .collect::<Vec<_>>();
-
-        let nrows = s.find('\n').unwrap();
-        let ncols = data.len() / nrows;
-
-        // we initialize the matrix in column-major order, so we then need
-        // to transpose it to get the data correctly formed
-        let mut grid = na::DMatrix::from_vec(ncols, nrows, data);
-        grid.transpose_mut();
-
+        let grid = Grid::parse(s, |c| Xmas::try_from(c).ok())?;
         Ok(Self { grid })
     }
 }
 
 impl XmasGrid {
-    /// Returns an iterator over the column-major indices for all occurrences of `token` in `self`.
-    pub fn iter_positions_of(&self, token: Xmas) -> impl Iterator<Item = usize> + use<'_> {
-        self.grid
-            .iter()
-            .enumerate()
-            .filter_map(move |(n, elem)| Some(n).filter(|_| *elem == token))
+    /// Returns an iterator over the positions of all occurrences of `token` in `self`.
+    pub fn iter_positions_of(&self, token: Xmas) -> impl Iterator<Item = (usize, usize)> + use<'_> {
+        self.grid.positions(move |&elem| elem == token)
     }
 
-    /// Counts the number of `XMAS` sequences in `self` that begin at `index`.
-    pub fn count_xmas_sequences_at_index(&self, index: usize) -> usize {
-        let nrows: isize = self.grid.nrows().try_into().unwrap();
-
-        let offsets = [
-            [-1, -2, -3],                                 // N
-            [nrows - 1, 2 * nrows - 2, 3 * nrows - 3],    // NE
-            [nrows, 2 * nrows, 3 * nrows],                // E
-            [nrows + 1, 2 * nrows + 2, 3 * nrows + 3],    // SE
-            [1, 2, 3],                                    // S
-            [-nrows + 1, -2 * nrows + 2, -3 * nrows + 3], // SW
-            [-nrows, -2 * nrows, -3 * nrows],             // W
-            [-nrows - 1, -2 * nrows - 2, -3 * nrows - 3], // NW
-        ];
-
-        let mut total = 0;
-
-        let x: isize = index.try_into().unwrap();
-        for [m, a, s] in offsets {
-            let Ok(m): Result<usize, _> = (x + m).try_into() else {
-                continue;
-            };
-            let Ok(a): Result<usize, _> = (x + a).try_into() else {
-                continue;
-            };
-            let Ok(s): Result<usize, _> = (x + s).try_into() else {
-                continue;
-            };
-
-            // check that the distances between the sequence elements are correct
-            if self.chebyshev(index, m) > 1 || self.chebyshev(m, a) > 1 || self.chebyshev(a, s) > 1
-            {
-                continue;
-            }
-
-            if self.grid.get(m).is_some_and(|t| t == &Xmas::M)
-                && self.grid.get(a).is_some_and(|t| t == &Xmas::A)
-                && self.grid.get(s).is_some_and(|t| t == &Xmas::S)
-            {
-                total += 1;
-            }
-        }
-
-        total
+    /// Counts the number of `XMAS` sequences in `self` that begin at `position`.
+    pub fn count_xmas_sequences_at(&self, position: (usize, usize)) -> usize {
+        Direction::ALL
+            .into_iter()
+            .filter(|direction| {
+                [Xmas::M, Xmas::A, Xmas::S]
+                    .into_iter()
+                    .zip(1..)
+                    .all(|(token, k)| {
+                        self.grid
+                            .offset(position, direction.offset() * k)
+                            .is_some_and(|next| self.grid[next] == token)
+                    })
+            })
+            .count()
     }
 
-    /// Checks whether `index` is the `A` in an overlapping pair of `MAS` sequences.
-    pub fn mas_cross_occurs_at(&self, index: usize) -> bool {
-        let nrows = self.grid.nrows();
-        let ncols = self.grid.ncols();
-
-        // if `index` is in the first or last column
-        if index < nrows || index + nrows >= ncols * nrows {
-            return false;
-        }
+    /// Checks whether `position` is the `A` in an overlapping pair of `MAS` sequences.
+    pub fn mas_cross_occurs_at(&self, position: (usize, usize)) -> bool {
+        let corner = |direction: Direction| {
+            self.grid
+                .offset(position, direction)
+                .map(|at| self.grid[at])
+        };
 
-        // if `index` is in the first or last row
-        if index % nrows == 0 || index % nrows == nrows - 1 {
+        // get adjacent diagonals, which are missing on the edges of the grid
+        let [Some(ne), Some(se), Some(sw), Some(nw)] = Direction::DIAGONAL.map(corner) else {
             return false;
-        }
-
-        // get adjacent diagonals
-        let nw = self.grid[index - nrows - 1];
-        let ne = self.grid[index + nrows - 1];
-        let sw = self.grid[index - nrows + 1];
-        let se = self.grid[index + nrows + 1];
+        };
 
         let aligned = |a, b| (a == Xmas::M && b == Xmas::S) || (a == Xmas::S && b == Xmas::M);
 
         aligned(nw, se) && aligned(ne, sw)
     }
 
-    /// Computes the Chebyshev distance between `a` and `b` on `self`.
-    #[inline(always)]
-    pub fn chebyshev(&self, a: usize, b: usize) -> usize {
-        let (row_a, col_a) = self.index_to_position(a);
-        let (row_b, col_b) = self.index_to_position(b);
+    /// Counts the number of `X-MAS` crosses in `self`, i.e. the `A`s whose
+    /// diagonal arms both read `MAS` or `SAM`.
+    pub fn count_x_mas_occurrences(&self) -> usize {
+        self.iter_positions_of(Xmas::A)
+            .filter(|&position| self.mas_cross_occurs_at(position))
+            .count()
+    }
+}
 
-        usize::max(row_a.abs_diff(row_b), col_a.abs_diff(col_b))
+/// Draws each letter in its own colour.
+#[cfg(feature = "render")]
+impl crate::render::Renderable for XmasGrid {
+    fn shape(&self) -> (usize, usize) {
+        self.grid.shape()
     }
 
-    #[inline(always)]
-    fn index_to_position(&self, index: usize) -> (usize, usize) {
-        let ncols = self.grid.ncols();
-        (index % ncols, index / ncols)
+    fn color(&self, position: (usize, usize)) -> crate::render::Rgb {
+        use crate::render::Rgb;
+
+        match self.grid[position] {
+            Xmas::X => Rgb(200, 40, 40),
+            Xmas::M => Rgb(40, 140, 60),
+            Xmas::A => Rgb(230, 190, 50),
+            Xmas::S => Rgb(50, 90, 190),
+        }
     }
 }
 
+pub fn parse(input: &str) -> Result<XmasGrid, AocError> {
+    input.parse()
+}
+
+/// Describes the structure of the input.
+pub fn stats(grid: &XmasGrid) -> crate::Stats {
+    let (nrows, ncols) = grid.grid.shape();
+    vec![("grid", format!("{nrows}x{ncols}"))]
+}
+
 /// Computes the solution to part 1.
-pub fn count_xmas_occurrences(input: &str) -> usize {
-    let grid = input.parse::<XmasGrid>().unwrap();
+pub fn part1(grid: &XmasGrid) -> Answer {
     grid.iter_positions_of(Xmas::X)
-        .map(|index| grid.count_xmas_sequences_at_index(index))
-        .sum()
+        .map(|position| grid.count_xmas_sequences_at(position))
+        .sum::<usize>()
+        .into()
 }
 
 /// Computes the solution to part 2.
-pub fn count_x_mas_occurrences(input: &str) -> usize {
-    let grid = input.parse::<XmasGrid>().unwrap();
-    grid.iter_positions_of(Xmas::A)
-        .filter(|&index| grid.mas_cross_occurs_at(index))
-        .count()
+pub fn part2(grid: &XmasGrid) -> Answer {
+    grid.count_x_mas_occurrences().into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE: &str = r#"MMMSXXMASM
+pub const EXAMPLE: &str = r#"MMMSXXMASM
                              MSAMXMSMSA
                              AMXSXMAAMM
                              MSAMASMSMX
@@ -180,25 +148,11 @@ mod tests {
                              MAMMMXMMMM
                              MXMXAXMASX"#;
 
-    const INPUT: &str = include_str!("../input/day04.txt");
-
-    #[test]
-    fn example_part_1() {
-        assert_eq!(count_xmas_occurrences(EXAMPLE), 18);
-    }
-
-    #[test]
-    fn part_1() {
-        assert_eq!(count_xmas_occurrences(INPUT), 2514);
-    }
-
-    #[test]
-    fn example_part_2() {
-        assert_eq!(count_x_mas_occurrences(EXAMPLE), 9);
-    }
-
-    #[test]
-    fn part_2() {
-        assert_eq!(count_x_mas_occurrences(INPUT), 1888);
-    }
+aoc_day! {
+    day: 4,
+    parsed: XmasGrid,
+    example_part_1: EXAMPLE => 18,
+    example_part_2: EXAMPLE => 9,
+    answer_part_1: 2514,
+    answer_part_2: 1888,
 }
@@ -1,7 +1,17 @@
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-    str::FromStr,
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, fmt::Write, str::FromStr};
+
+#[cfg(feature = "std")]
+use crate::stream::{LineReader, StreamError};
+use crate::{
+    error::AocError,
+    hash::{HashMap, HashSet},
+    parse, Answer,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,81 +21,275 @@ pub struct Rule {
 }
 
 impl FromStr for Rule {
-    type Err = ParseRuleError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (lhs, rhs) = s.split_once('|').ok_or(ParseRuleError::MissingBar)?;
+        let input = s.as_bytes();
+        let error = |at, reason| AocError::at_bytes(input, at, reason);
 
-        Ok(Self {
-            first: lhs.parse::<u8>()?,
-            second: rhs.parse::<u8>()?,
-        })
+        let (first, rest) = parse_page(input, input)?;
+        let rest = rest
+            .strip_prefix(b"|")
+            .ok_or_else(|| error(rest, "expected a rule like `47|53`"))?;
+        let (second, rest) = parse_page(input, rest)?;
+
+        match rest.is_empty() {
+            true => Ok(Self { first, second }),
+            false => Err(error(rest, "expected the end of the rule")),
+        }
     }
 }
 
-#[derive(Debug, Default, Clone)]
-pub enum ParseRuleError {
-    Int(std::num::ParseIntError),
-    MissingBar,
-    #[default]
-    Unknown,
-}
+/// Parses the page number at the start of `bytes` (a slice of `input`).
+fn parse_page<'a>(input: &[u8], bytes: &'a [u8]) -> Result<(u8, &'a [u8]), AocError> {
+    match parse::parse_uint_checked(bytes) {
+        (_, rest) if rest.len() == bytes.len() => {
+            Err(AocError::at_bytes(input, bytes, "expected a page number"))
+        }
+        (page, rest) => {
+            let page = page
+                .and_then(|page| u8::try_from(page).ok())
+                .ok_or_else(|| AocError::at_bytes(input, bytes, "page number is too large"))?;
 
-impl From<std::num::ParseIntError> for ParseRuleError {
-    fn from(v: std::num::ParseIntError) -> Self {
-        Self::Int(v)
+            Ok((page, rest))
+        }
     }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RuleTable {
     successors: HashMap<u8, HashSet<u8>>,
 }
 
 impl RuleTable {
+    /// Returns the number of rules in the table.
+    pub fn len(&self) -> usize {
+        self.successors.values().map(HashSet::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    /// Returns the pages that must come after each page, in ascending order.
+    pub fn ordering(&self) -> BTreeMap<u8, Vec<u8>> {
+        self.successors
+            .iter()
+            .map(|(&page, successors)| {
+                let mut successors = successors.iter().copied().collect::<Vec<_>>();
+                successors.sort_unstable();
+                (page, successors)
+            })
+            .collect()
+    }
+
+    /// Renders the rules in the Graphviz DOT language, with an edge from each
+    /// page to every page that must come after it.
+    ///
+    /// If `update` is given then only its pages and the rules between them are
+    /// drawn, and each page is labelled with its position in the update, so
+    /// the order that part 2 should produce can be read off the graph.
+    pub fn to_dot(&self, update: Option<&[u8]>) -> String {
+        let mut dot = String::from("digraph rules {\n");
+
+        if let Some(update) = update {
+            for (position, page) in update.iter().enumerate() {
+                writeln!(dot, "    {page} [label=\"{page} (#{position})\"];").unwrap();
+            }
+        }
+
+        let included = |page: &u8| update.is_none_or(|update| update.contains(page));
+
+        for (page, successors) in self.ordering().into_iter().filter(|(page, _)| included(page)) {
+            for successor in successors.into_iter().filter(included) {
+                writeln!(dot, "    {page} -> {successor};").unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
     fn check_order(&self, first: u8, second: u8) -> bool {
         self.successors
             .get(&first)
             .is_some_and(|set| set.contains(&second))
     }
+
+    fn insert(&mut self, Rule { first, second }: Rule) {
+        if let Some(set) = self.successors.get_mut(&first) {
+            set.insert(second);
+        } else {
+            self.successors.insert(first, HashSet::from_iter([second]));
+        }
+    }
+
+    /// Sorts `update` into the order that the rules ask for.
+    fn reorder(&self, update: &mut [u8]) {
+        update.sort_by(|&a, &b| {
+            if a == b {
+                Ordering::Equal
+            } else if self.check_order(a, b) {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        });
+    }
+}
+
+impl Default for RuleTable {
+    fn default() -> Self {
+        Self {
+            successors: HashMap::with_capacity_and_hasher(100, Default::default()),
+        }
+    }
 }
 
 impl FromStr for RuleTable {
-    type Err = ParseRuleError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut successors = HashMap::<_, HashSet<_>>::with_capacity(100);
+        let mut table = Self::default();
 
-        for rule in s.split('\n') {
-            let Rule { first, second } = rule.parse()?;
+        for rule in parse::parse_lines(s)? {
+            table.insert(rule);
+        }
 
-            if let Some(set) = successors.get_mut(&first) {
-                set.insert(second);
-            } else {
-                successors.insert(first, HashSet::from([second]));
+        Ok(table)
+    }
+}
+
+/// The page ordering rules and the updates to check against them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrintQueue {
+    rules: RuleTable,
+    updates: Vec<Vec<u8>>,
+}
+
+pub fn parse(input: &str) -> Result<PrintQueue, AocError> {
+    let mut blocks = parse::blocks(input);
+    let missing = |what| AocError::at_end(input, format_args!("missing the {what}"));
+
+    let rules = blocks.next().ok_or_else(|| missing("rules"))?.text;
+    let rules = rules
+        .parse::<RuleTable>()
+        .map_err(|error| error.within(input, rules))?;
+
+    let updates = blocks
+        .next()
+        .ok_or_else(|| missing("updates"))?
+        .text
+        .lines()
+        .map(|update| parse_update(input, update))
+        .collect::<Result<_, _>>()?;
+
+    Ok(PrintQueue { rules, updates })
+}
+
+/// Parses the pages of the update on `line`, a line of `input`.
+fn parse_update(input: &str, line: &str) -> Result<Vec<u8>, AocError> {
+    let input = input.as_bytes();
+    let mut rest = line.trim().as_bytes();
+    let mut pages = Vec::new();
+
+    loop {
+        let (page, tail) = parse_page(input, rest)?;
+        pages.push(page);
+
+        match tail {
+            [] => return Ok(pages),
+            [b',', tail @ ..] => rest = tail,
+            _ => {
+                return Err(AocError::at_bytes(
+                    input,
+                    tail,
+                    "expected `,` between pages",
+                ))
             }
         }
+    }
+}
+
+/// Solves both parts from `reader`, reading the rules and then checking each
+/// update as it's read.
+#[cfg(feature = "std")]
+pub fn solve_reader(reader: impl std::io::BufRead) -> Result<(Answer, Answer), StreamError> {
+    let mut reader = LineReader::new(reader);
+    let mut rules = RuleTable::default();
+
+    // the rules end at the first blank line after them
+    while let Some(line) = reader.next_line()? {
+        let rule = line.trim();
+        if rule.is_empty() && rules.is_empty() {
+            continue;
+        } else if rule.is_empty() {
+            break;
+        }
 
-        Ok(Self { successors })
+        match rule.parse::<Rule>() {
+            Ok(rule) => rules.insert(rule),
+            Err(error) => {
+                let error = error.within(line, rule);
+                return Err(reader.locate(error).into());
+            }
+        }
     }
+
+    let (mut ordered, mut reordered) = (0, 0);
+    let mut updates = 0;
+
+    reader.for_each(|line| {
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+
+        let mut update = parse_update(line, line)?;
+        updates += 1;
+
+        if update.is_sorted_by(|&a, &b| rules.check_order(a, b)) {
+            ordered += update[update.len() / 2] as usize;
+        } else {
+            rules.reorder(&mut update);
+            reordered += update[update.len() / 2] as usize;
+        }
+
+        Ok(())
+    })?;
+
+    if updates == 0 {
+        return Err(reader
+            .locate(AocError::at_end("", "missing the updates"))
+            .into());
+    }
+
+    Ok((ordered.into(), reordered.into()))
+}
+
+/// Describes the structure of the input.
+pub fn stats(queue: &PrintQueue) -> crate::Stats {
+    let pages = queue.rules.ordering().len();
+
+    vec![
+        ("rules", queue.rules.len().to_string()),
+        ("pages with rules", pages.to_string()),
+        ("updates", queue.updates.len().to_string()),
+    ]
 }
 
 /// Computes the solution to part 1.
-pub fn sum_of_middle_page_numbers(input: &str) -> usize {
-    let (rules, updates) = input.split_once("\n\n").unwrap();
-    let rules = rules.parse::<RuleTable>().unwrap();
-
-    updates
-        .split_terminator("\n")
-        .map(|raw_update| {
-            raw_update
-                .split(',')
-                .map(u8::from_str)
-                .map(Result::unwrap)
-                .collect::<Box<[_]>>()
-        })
+pub fn part1(queue: &PrintQueue) -> Answer {
+    let rules = &queue.rules;
+
+    queue
+        .updates
+        .iter()
         .filter(|update| {
-            let (first, tail) = update.split_first().unwrap();
+            let Some((first, tail)) = update.split_first() else {
+                return false;
+            };
 
             tail.iter()
                 .try_fold(first, |prev, next| {
@@ -94,50 +298,30 @@ pub fn sum_of_middle_page_numbers(input: &str) -> usize {
                 .is_some()
         })
         .map(|update| update[update.len() / 2] as usize)
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
 /// Computes the solution to part 2.
-pub fn sum_of_malformed_middle_page_numbers(input: &str) -> usize {
-    let (rules, updates) = input.split_once("\n\n").unwrap();
-    let rules = rules.parse::<RuleTable>().unwrap();
-
-    let updates = updates.split_terminator("\n").map(|raw_update| {
-        raw_update
-            .split(',')
-            .map(u8::from_str)
-            .map(Result::unwrap)
-            .collect::<Vec<_>>()
-    });
-
+pub fn part2(queue: &PrintQueue) -> Answer {
+    let rules = &queue.rules;
     let mut sum = 0;
 
-    for mut update in updates {
+    for update in &queue.updates {
         if update.is_sorted_by(|&a, &b| rules.check_order(a, b)) {
             continue;
         }
 
-        update.sort_by(|&a, &b| {
-            if a == b {
-                Ordering::Equal
-            } else if rules.check_order(a, b) {
-                Ordering::Greater
-            } else {
-                Ordering::Less
-            }
-        });
+        let mut update = update.clone();
+        rules.reorder(&mut update);
 
         sum += update[update.len() / 2] as usize;
     }
 
-    sum
+    sum.into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE: &str = r#"47|53
+pub const EXAMPLE: &str = r#"47|53
 97|13
 97|61
 97|47
@@ -166,25 +350,89 @@ mod tests {
 61,13,29
 97,13,75,29,47"#;
 
-    const INPUT: &str = include_str!("../input/day05.txt");
+aoc_day! {
+    day: 5,
+    parsed: PrintQueue,
+    example_part_1: EXAMPLE => 143,
+    example_part_2: EXAMPLE => 123,
+    answer_part_1: 6242,
+    answer_part_2: 5169,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part_1() {
-        assert_eq!(sum_of_middle_page_numbers(EXAMPLE), 143);
+    fn example_rule_ordering() {
+        let rules = parse(EXAMPLE).unwrap().rules;
+        let ordering = rules
+            .ordering()
+            .into_iter()
+            .map(|(page, successors)| format!("{page} -> {successors:?}\n"))
+            .collect::<String>();
+
+        insta::assert_snapshot!(ordering);
     }
 
     #[test]
-    fn part_1() {
-        assert_eq!(sum_of_middle_page_numbers(INPUT), 6242);
+    fn example_dot() {
+        let rules = parse(EXAMPLE).unwrap().rules;
+
+        let dot = rules.to_dot(None);
+        assert!(dot.starts_with("digraph rules {\n"));
+        assert_eq!(dot.matches(" -> ").count(), rules.len());
+
+        // a correctly-ordered update has a rule between every pair of pages
+        let dot = rules.to_dot(Some(&[75, 47, 61, 53, 29]));
+        assert!(dot.contains("    61 [label=\"61 (#2)\"];\n"));
+        assert!(dot.contains("    75 -> 47;\n"));
+        assert!(!dot.contains("97"));
+        assert_eq!(dot.matches(" -> ").count(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn example_reader() {
+        assert_eq!(
+            solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            (143usize.into(), 123usize.into())
+        );
+        assert_eq!(
+            solve_reader("47|53\n\n47,x\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 4: expected a page number"
+        );
     }
 
     #[test]
-    fn example_part_2() {
-        assert_eq!(sum_of_malformed_middle_page_numbers(EXAMPLE), 123);
+    fn invalid_input() {
+        let error = |input: &str| parse(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error("47|53\n47x53\n\n75,47\n"),
+            "line 2, column 3: expected a rule like `47|53`"
+        );
+        assert_eq!(
+            error("47|53\n47|300\n\n75,47\n"),
+            "line 2, column 4: page number is too large"
+        );
+        assert_eq!(
+            error("47|53\n\n75,47\n75,47;61\n"),
+            "line 4, column 6: expected `,` between pages"
+        );
     }
 
     #[test]
-    fn part_2() {
-        assert_eq!(sum_of_malformed_middle_page_numbers(INPUT), 5169);
+    fn example_stats() {
+        assert_eq!(
+            stats(&parse(EXAMPLE).unwrap()),
+            [
+                ("rules", "21".to_string()),
+                ("pages with rules", "6".to_string()),
+                ("updates", "6".to_string()),
+            ]
+        );
     }
 }
@@ -1,11 +1,25 @@
-use std::{collections::HashSet, str::FromStr};
-
-use nalgebra as na;
-use rayon::iter::{IntoParallelIterator, ParallelIterator as _};
+use alloc::{format, string::ToString, vec};
+use core::{fmt::Display, str::FromStr};
+
+use crate::cancel;
+use crate::cycle;
+use crate::error::AocError;
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::hash::HashSet;
+use crate::par::*;
+use crate::progress;
+use crate::Answer;
+
+/// An experimental compute shader backend for part 2.
+#[cfg(feature = "gpu")]
+#[path = "day06/gpu.rs"]
+pub mod gpu;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Area {
-    map: na::DMatrix<Position>,
+    map: Grid<Position>,
     guard: Guard,
 }
 
@@ -16,101 +30,188 @@ impl Area {
         action
     }
 
-    pub fn next_guard_index(&self) -> Option<usize> {
-        if self.guard_will_leave() {
-            return None;
-        }
-
-        let index: isize = self.guard.index.try_into().unwrap();
-
-        let offset = match self.guard.direction {
-            Direction::N => -1,
-            Direction::E => self.map.nrows() as isize,
-            Direction::S => 1,
-            Direction::W => -(self.map.nrows() as isize),
-        };
+    /// Returns the guard's position, or `None` once they have left the area.
+    pub fn guard_position(&self) -> Option<(usize, usize)> {
+        self.guard.position
+    }
 
-        usize::try_from(index + offset).ok()
+    /// Returns the position in front of the guard, or `None` if the guard is
+    /// about to leave (or has left) the area.
+    pub fn next_guard_position(&self) -> Option<(usize, usize)> {
+        self.map.offset(self.guard.position?, self.guard.direction)
     }
 
     pub fn next_guard_action(&self) -> Action {
-        match self.next_guard_index() {
+        match self.next_guard_position() {
             None => Action::Leave,
-            Some(index) => match self.map[index] {
-                Position::Clear => Action::Advance { index },
+            Some(position) => match self.map[position] {
+                Position::Clear => Action::Advance { position },
                 Position::Obstructed => Action::Rotate,
             },
         }
     }
 
+    /// Returns where `guard` would be after their next action, or `None` if
+    /// they would leave the area (or already have).
+    fn guard_after(&self, guard: Guard) -> Option<Guard> {
+        let next = self.map.offset(guard.position?, guard.direction)?;
+
+        Some(match self.map[next] {
+            Position::Clear => Guard {
+                position: Some(next),
+                ..guard
+            },
+            Position::Obstructed => Guard {
+                direction: guard.direction.turn_right(),
+                ..guard
+            },
+        })
+    }
+
     pub fn run_action(&mut self, action: Action) {
         match action {
-            Action::Advance { index } => {
-                self.guard.index = index;
+            Action::Advance { position } => {
+                self.guard.position = Some(position);
             }
             Action::Rotate => {
                 self.guard.direction = self.guard.direction.turn_right();
             }
             Action::Leave => {
-                self.guard.index = usize::MAX;
+                self.guard.position = None;
             }
         }
     }
+}
+
+impl FromStr for Area {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut guard = None;
+        let mut cells = 0;
+
+        let map = Grid::parse(s, |c| {
+            if let Some(direction) = Direction::from_arrow(c) {
+                guard = Some((cells, direction));
+            }
+
+            cells += 1;
+            Position::try_from(c).ok()
+        })?;
+
+        let (index, direction) = guard.ok_or_else(|| AocError::at_end(s, "there is no guard"))?;
+        let position = (index / map.cols(), index % map.cols());
+
+        Ok(Area {
+            map,
+            guard: Guard {
+                position: Some(position),
+                direction,
+            },
+        })
+    }
+}
 
-    pub fn guard_will_leave(&self) -> bool {
-        let ncols = self.map.ncols();
-        let nrows = self.map.nrows();
-        let index = self.guard.index;
-
-        match self.guard.direction {
-            Direction::N if index % nrows == 0 => true,
-            Direction::E if index / ncols == ncols - 1 => true,
-            Direction::S if index % nrows == nrows - 1 => true,
-            Direction::W if index / ncols == 0 => true,
-            _ => false,
+impl Display for Area {
+    /// Renders the map in the same format as the puzzle input, with the guard
+    /// drawn at its current position (unless it has left the area).
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (row, line) in self.map.row_iter().enumerate() {
+            for (col, position) in line.iter().enumerate() {
+                let c = match position {
+                    _ if self.guard.position == Some((row, col)) => self.guard.arrow(),
+                    Position::Clear => '.',
+                    Position::Obstructed => '#',
+                };
+
+                write!(f, "{c}")?;
+            }
+
+            writeln!(f)?;
         }
+
+        Ok(())
     }
 }
 
-impl FromStr for Area {
-    type Err = ();
+/// Draws the obstructions dark and the guard red, if they haven't left.
+#[cfg(feature = "render")]
+impl crate::render::Renderable for Area {
+    fn shape(&self) -> (usize, usize) {
+        self.map.shape()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let ncols = s.find('\n').ok_or(())?;
-        let nrows = s.chars().filter(|&c| c != '\n').count() / ncols;
-
-        let map = na::DMatrix::from_row_iterator(
-            nrows,
-            ncols,
-            s.split('\n')
-                .flat_map(|s| s.chars().map(|c| Position::try_from(c).unwrap())),
-        );
+    fn color(&self, position: (usize, usize)) -> crate::render::Rgb {
+        use crate::render::Rgb;
+
+        match self.map[position] {
+            _ if self.guard.position == Some(position) => Rgb(220, 40, 40),
+            Position::Clear => Rgb(235, 235, 225),
+            Position::Obstructed => Rgb(40, 40, 50),
+        }
+    }
+}
+
+/// An area with the path its guard has taken so far, for drawing their
+/// patrol.
+#[cfg(feature = "render")]
+struct Patrol {
+    area: Area,
+    visited: HashSet<(usize, usize)>,
+}
+
+#[cfg(feature = "render")]
+impl crate::render::Renderable for Patrol {
+    fn shape(&self) -> (usize, usize) {
+        self.area.shape()
+    }
+
+    fn color(&self, position: (usize, usize)) -> crate::render::Rgb {
+        use crate::render::Rgb;
+
+        if self.visited.contains(&position) && self.area.guard.position != Some(position) {
+            Rgb(240, 180, 90)
+        } else {
+            self.area.color(position)
+        }
+    }
+}
+
+/// Animates the guard's patrol of `area` as a GIF, with a frame for each step
+/// they take and the path behind them drawn in. The guard has to leave the
+/// area eventually, as they do in the puzzle.
+#[cfg(feature = "render")]
+pub fn patrol_gif(area: &Area, scale: usize, delay: core::time::Duration) -> alloc::vec::Vec<u8> {
+    use crate::render::{GifEncoder, Renderable};
+
+    let (rows, cols) = area.map.shape();
+    let mut gif = GifEncoder::new(cols * scale, rows * scale, delay);
+    let mut patrol = Patrol {
+        area: area.clone(),
+        visited: area.guard.position.into_iter().collect(),
+    };
+
+    gif.push(&patrol.render(scale));
+
+    loop {
+        match patrol.area.next_state() {
+            Action::Advance { position } => {
+                patrol.visited.insert(position);
+            }
+            Action::Rotate => continue,
+            Action::Leave => break,
+        }
 
-        let guard = {
-            // find raw index in the input
-            let raw_index = s.find(Guard::is_guard_char).ok_or(())?;
-            // adjust for newline characters to get the row-major index
-            let row_index = raw_index - (raw_index / nrows) + 1;
-            // convert to column major index
-            let index = (row_index % ncols) * nrows + (row_index / nrows);
-
-            let direction = s
-                .chars()
-                .nth(raw_index) // use raw_index to access the source char
-                .ok_or(())?
-                .try_into()
-                .map_err(|_| ())?;
-
-            Guard { index, direction }
-        };
-
-        Ok(Area { map, guard })
+        gif.push(&patrol.render(scale));
     }
+
+    gif.push(&patrol.render(scale));
+    gif.finish()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
-    Advance { index: usize },
+    Advance { position: (usize, usize) },
     Rotate,
     Leave,
 }
@@ -122,18 +223,28 @@ impl Action {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Guard {
-    index: usize,
+    /// The guard's `(row, col)`, or `None` once they have left the area.
+    position: Option<(usize, usize)>,
     direction: Direction,
 }
 
 impl Guard {
     pub fn is_guard_char(c: char) -> bool {
-        matches!(c, '^' | '>' | 'V' | '<')
+        Direction::from_arrow(c).is_some()
+    }
+
+    /// Returns the arrow the guard is drawn as.
+    pub fn arrow(&self) -> char {
+        self.direction
+            .arrow()
+            .expect("the guard only faces cardinal directions")
     }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Position {
     #[default]
     Clear,
@@ -152,113 +263,92 @@ impl TryFrom<char> for Position {
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '#' => Ok(Position::Obstructed),
-            '.' | '^' | '>' | 'V' | '<' => Ok(Position::Clear),
+            '.' => Ok(Position::Clear),
+            c if Guard::is_guard_char(c) => Ok(Position::Clear),
             _ => Err(()),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Direction {
-    N,
-    E,
-    S,
-    W,
+pub fn parse(input: &str) -> Result<Area, AocError> {
+    input.parse()
 }
 
-impl TryFrom<char> for Direction {
-    type Error = ();
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '^' => Ok(Direction::N),
-            '>' => Ok(Direction::E),
-            'V' => Ok(Direction::S),
-            '<' => Ok(Direction::W),
-            _ => Err(()),
-        }
-    }
+/// Describes the structure of the input.
+pub fn stats(area: &Area) -> crate::Stats {
+    let (nrows, ncols) = area.map.shape();
+    let (row, col) = area.guard.position.expect("the guard starts in the area");
+    let obstructions = area.map.iter().filter(|position| position.is_obstructed());
+
+    vec![
+        ("grid", format!("{nrows}x{ncols}")),
+        ("obstructions", obstructions.count().to_string()),
+        (
+            "guard start",
+            format!("({row}, {col}) facing {}", area.guard.arrow()),
+        ),
+    ]
 }
 
-impl Direction {
-    pub fn turn_right(self) -> Self {
-        match self {
-            Direction::N => Direction::E,
-            Direction::E => Direction::S,
-            Direction::S => Direction::W,
-            Direction::W => Direction::N,
-        }
+/// Returns every position the guard visits before leaving `area`.
+fn patrol_positions(area: &Area) -> HashSet<(usize, usize)> {
+    let mut area = area.clone();
+    let mut positions = HashSet::default();
+
+    while let Some(position) = area.guard.position {
+        positions.insert(position);
+        area.next_state();
     }
+
+    positions
 }
 
 /// Computes the solution to part 1.
-pub fn count_distinct_patrol_positions(input: &str) -> usize {
-    let mut area = input.parse::<Area>().unwrap();
-    let mut positions = HashSet::new();
-
-    loop {
-        positions.insert(area.guard.index);
-
-        if area.next_state().is_leave() {
-            break;
-        }
-    }
-
-    positions.len()
+pub fn part1(area: &Area) -> Answer {
+    patrol_positions(area).len().into()
 }
 
 /// Computes the solution to part 2.
-pub fn count_possible_loops(input: &str) -> usize {
-    // brute force because i kinda hate this problem
-
-    // roughly the lowest fuel value that produces a valid answer
-    const FUEL: usize = 6000;
-    let area = input.parse::<Area>().unwrap();
-
+pub fn part2(area: &Area) -> Answer {
     // obstructions have to be placed on the guard's path, so we grab them first
     // to reduce the number of permutations that actually need to be checked
-    let positions = {
-        let mut set = HashSet::new();
-        let mut area = area.clone();
+    let positions = patrol_positions(area);
 
-        loop {
-            set.insert(area.guard.index);
+    progress::start(positions.len());
 
-            if area.next_state().is_leave() {
-                break;
-            }
+    let creates_loop = |area: &Area, position: (usize, usize)| {
+        if cancel::is_cancelled() {
+            return false;
         }
 
-        set
+        let mut area = area.clone();
+        area.map[position] = Position::Obstructed;
+
+        let loops = cycle::brent(area.guard, |&guard| area.guard_after(guard)).is_some();
+
+        progress::advance(1);
+        loops
     };
 
     // rayon drops the processing time in the full input case from ~5s to 0.16s
     // on my 2021 macbook pro
-    positions
-        .into_par_iter()
-        .map_with(area, |area, i| {
-            let mut area = area.clone();
-            area.map[i] = Position::Obstructed;
-
-            let mut not_a_loop = false;
-            for _ in 0..FUEL {
-                if area.next_state().is_leave() {
-                    not_a_loop = true;
-                    break;
-                }
-            }
+    let loops = match is_sequential() {
+        true => positions
+            .into_iter()
+            .filter(|&position| creates_loop(area, position))
+            .count(),
+        false => positions
+            .into_par_iter()
+            .map_with(area.clone(), |area, position| creates_loop(area, position))
+            .filter(|&x| x)
+            .count(),
+    };
 
-            !not_a_loop
-        })
-        .filter(|&x| x)
-        .count()
+    progress::finish();
+    loops.into()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE: &str = r#"....#.....
+pub const EXAMPLE: &str = r#"....#.....
 .........#
 ..........
 ..#.......
@@ -269,25 +359,63 @@ mod tests {
 #.........
 ......#..."#;
 
-    const INPUT: &str = include_str!("../input/day06.txt");
+aoc_day! {
+    day: 6,
+    parsed: Area,
+    example_part_1: EXAMPLE => 41,
+    example_part_2: EXAMPLE => 6,
+    answer_part_1: 5030,
+    answer_part_2: 1928,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn example_part_1() {
-        assert_eq!(count_distinct_patrol_positions(EXAMPLE), 41);
+    fn example_rendering() {
+        let mut area = EXAMPLE.parse::<Area>().unwrap();
+        insta::assert_snapshot!("example_start", area.to_string());
+
+        while !area.next_state().is_leave() {}
+        insta::assert_snapshot!("example_end", area.to_string());
     }
 
     #[test]
-    fn part_1() {
-        assert_eq!(count_distinct_patrol_positions(INPUT), 5030);
+    fn example_stats() {
+        assert_eq!(
+            stats(&parse(EXAMPLE).unwrap()),
+            [
+                ("grid", "10x10".to_string()),
+                ("obstructions", "8".to_string()),
+                ("guard start", "(6, 4) facing ^".to_string()),
+            ]
+        );
     }
 
+    #[cfg(feature = "render")]
     #[test]
-    fn example_part_2() {
-        assert_eq!(count_possible_loops(EXAMPLE), 6);
+    fn example_patrol_gif() {
+        use crate::render::{Renderable, Rgb};
+
+        let area = parse(EXAMPLE).unwrap();
+        let image = area.render(2);
+        assert_eq!(image.pixel((13, 9)), Some(Rgb(220, 40, 40)));
+        assert_eq!(image.pixel((0, 8)), Some(Rgb(40, 40, 50)));
+
+        let gif = patrol_gif(&area, 2, core::time::Duration::from_millis(50));
+        assert!(gif.starts_with(b"GIF89a\x14\x00\x14\x00"));
+        assert_eq!(gif.last(), Some(&0x3b));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn part_2() {
-        assert_eq!(count_possible_loops(INPUT), 1928);
+    fn json_round_trip() {
+        let area = parse(EXAMPLE).unwrap();
+        let json = serde_json::to_string(&area).unwrap();
+        let restored = serde_json::from_str::<Area>(&json).unwrap();
+
+        assert_eq!(restored.to_string(), area.to_string());
+        assert_eq!(part1(&restored), part1(&area));
     }
 }
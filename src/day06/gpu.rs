@@ -0,0 +1,207 @@
+//! Part 2 on the GPU, with a compute shader invocation per candidate
+//! obstruction.
+//!
+//! Unlike the CPU version, which gives up on each candidate after a fixed
+//! amount of fuel, the shader runs each guard for as many steps as it has
+//! distinct states, so it detects loops exactly.
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use super::{patrol_positions, Area, Direction};
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// The uniform parameters of the shader, matching `Params` in `loops.wgsl`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct Params {
+    nrows: u32,
+    ncols: u32,
+    start: u32,
+    direction: u32,
+    candidates: u32,
+    // uniform buffers must be a multiple of 16 bytes long
+    _padding: [u32; 3],
+}
+
+/// A GPU device with the loop detection pipeline compiled for it.
+pub struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl Gpu {
+    /// Creates a pipeline on the default adapter, or returns `None` if there
+    /// isn't one.
+    pub fn new() -> Option<Self> {
+        pollster::block_on(Self::new_async())
+    }
+
+    async fn new_async() -> Option<Self> {
+        let instance = wgpu::Instance::default();
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+            .ok()?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor::default())
+            .await
+            .ok()?;
+
+        let module = device.create_shader_module(wgpu::include_wgsl!("loops.wgsl"));
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("day06 loops"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Some(Self {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    /// Counts the candidate obstructions in `area` that cause the guard to
+    /// loop, using the same candidates as [`super::part2`].
+    pub fn count_possible_loops(&self, area: &Area) -> usize {
+        let (nrows, ncols) = area.map.shape();
+
+        // the shader works with row-major indices
+        let to_index = |(row, col): (usize, usize)| (row * ncols + col) as u32;
+
+        let candidates = patrol_positions(area)
+            .into_iter()
+            .map(to_index)
+            .collect::<Vec<_>>();
+
+        let map = area
+            .map
+            .iter()
+            .map(|position| position.is_obstructed() as u32)
+            .collect::<Vec<_>>();
+
+        let params = Params {
+            nrows: nrows as u32,
+            ncols: ncols as u32,
+            start: to_index(area.guard.position.unwrap()),
+            direction: match area.guard.direction {
+                Direction::N => 0,
+                Direction::E => 1,
+                Direction::S => 2,
+                Direction::W => 3,
+                _ => unreachable!("the guard only faces cardinal directions"),
+            },
+            candidates: candidates.len() as u32,
+            _padding: [0; 3],
+        };
+
+        self.run(&params, &map, &candidates)
+            .into_iter()
+            .filter(|&looped| looped != 0)
+            .count()
+    }
+
+    /// Dispatches the shader, and reads back its results.
+    fn run(&self, params: &Params, map: &[u32], candidates: &[u32]) -> Vec<u32> {
+        let buffer = |label, contents: &[u8], usage| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents,
+                    usage,
+                })
+        };
+
+        let results_size = std::mem::size_of_val(candidates) as u64;
+        let params = buffer("params", bytemuck::bytes_of(params), wgpu::BufferUsages::UNIFORM);
+        let map = buffer("map", bytemuck::cast_slice(map), wgpu::BufferUsages::STORAGE);
+        let candidates_buffer = buffer(
+            "candidates",
+            bytemuck::cast_slice(candidates),
+            wgpu::BufferUsages::STORAGE,
+        );
+
+        let results = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("results"),
+            size: results_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: results_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: map.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: candidates_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: results.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups((candidates.len() as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+
+        encoder.copy_buffer_to_buffer(&results, 0, &readback, 0, results_size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        self.device.poll(wgpu::PollType::wait_indefinitely()).unwrap();
+
+        let data = slice.get_mapped_range().unwrap();
+        bytemuck::cast_slice(&data).to_vec()
+    }
+}
+
+/// Computes the solution to part 2 on the GPU, or returns `None` if no GPU
+/// adapter is available.
+pub fn part2(area: &Area) -> Option<usize> {
+    Some(Gpu::new()?.count_possible_loops(area))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_2() {
+        // not every machine that runs the tests has a GPU
+        if let Some(loops) = part2(&super::super::parse(super::super::EXAMPLE).unwrap()) {
+            assert_eq!(loops, 6);
+        }
+    }
+}
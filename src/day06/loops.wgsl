@@ -0,0 +1,62 @@
+// Each invocation simulates the guard with one extra obstruction, and writes 1
+// to its result if the guard ends up in a loop.
+
+struct Params {
+    nrows: u32,
+    ncols: u32,
+    start: u32,
+    direction: u32,
+    candidates: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+// one entry per position in row-major order, nonzero if it is obstructed
+@group(0) @binding(1) var<storage, read> map: array<u32>;
+// the row-major index of each candidate obstruction
+@group(0) @binding(2) var<storage, read> candidates: array<u32>;
+@group(0) @binding(3) var<storage, read_write> results: array<u32>;
+
+// row and column offsets for N, E, S, and W
+const DR = array<i32, 4>(-1, 0, 1, 0);
+const DC = array<i32, 4>(0, 1, 0, -1);
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let invocation = id.x;
+    if invocation >= params.candidates {
+        return;
+    }
+
+    let obstruction = candidates[invocation];
+    let nrows = i32(params.nrows);
+    let ncols = i32(params.ncols);
+
+    var row = i32(params.start / params.ncols);
+    var col = i32(params.start % params.ncols);
+    var direction = params.direction;
+
+    // the guard has at most 4 * nrows * ncols distinct states, so if it takes
+    // more steps than that without leaving then it must be in a loop
+    let fuel = 4u * params.nrows * params.ncols;
+
+    for (var step = 0u; step < fuel; step++) {
+        let next_row = row + DR[direction];
+        let next_col = col + DC[direction];
+
+        if next_row < 0 || next_row >= nrows || next_col < 0 || next_col >= ncols {
+            results[invocation] = 0u;
+            return;
+        }
+
+        let next = u32(next_row * ncols + next_col);
+
+        if map[next] != 0u || next == obstruction {
+            direction = (direction + 1u) % 4u;
+        } else {
+            row = next_row;
+            col = next_col;
+        }
+    }
+
+    results[invocation] = 1u;
+}
@@ -1,7 +1,32 @@
-use rayon::{iter::ParallelIterator, str::ParallelString};
+use alloc::{string::ToString, vec, vec::Vec};
+
+use crate::arith;
+use crate::error::AocError;
+use crate::par::*;
+use crate::parse;
+#[cfg(feature = "std")]
+use crate::stream::{LineReader, StreamError};
+use crate::Answer;
 
 const OPERAND_BUFFER_CAPACITY: usize = 16;
 
+/// An equation with its own operands, which can be borrowed as an [`EqnRef`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equation {
+    value: usize,
+    args: Vec<u16>,
+}
+
+impl Equation {
+    pub fn as_ref(&self) -> EqnRef<'_> {
+        EqnRef {
+            value: self.value,
+            args: &self.args,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct EqnRef<'a> {
     value: usize,
@@ -10,26 +35,53 @@ pub struct EqnRef<'a> {
 
 impl<'a> EqnRef<'a> {
     /// Parses the next equation from `s` (if any), using `buf` as a backing buffer
-    /// for the `EqnRef` it returns.
-    pub fn parse_next<'b: 'a>(s: &mut &str, buf: &'b mut Vec<u16>) -> Option<Self> {
-        if s.is_empty() {
-            return None;
+    /// for the `EqnRef` it returns. Errors are located in `input`, of which `s`
+    /// is the unparsed tail.
+    pub fn parse_next<'b: 'a>(
+        input: &[u8],
+        s: &mut &[u8],
+        buf: &'b mut Vec<u16>,
+    ) -> Result<Option<Self>, AocError> {
+        let eqn = s.trim_ascii_start();
+        if eqn.is_empty() {
+            return Ok(None);
         }
 
-        let (eqn, tail) = s.split_once('\n').unwrap_or((*s, ""));
-        *s = tail;
+        let error = |at, reason| AocError::at_bytes(input, at, reason);
 
-        let (raw_value, operands) = eqn.split_once(": ").unwrap();
-        let value = raw_value.parse::<usize>().unwrap();
+        let (value, mut rest) = parse::parse_uint_checked(eqn);
+        if rest.len() == eqn.len() {
+            return Err(error(eqn, "expected a test value"));
+        }
 
-        let operands = operands
-            .split_whitespace()
-            .map(|s| s.parse::<u16>().unwrap());
+        let value = value
+            .and_then(|value| usize::try_from(value).ok())
+            .ok_or_else(|| error(eqn, "test value is too large"))?;
 
+        rest = rest
+            .strip_prefix(b":")
+            .ok_or_else(|| error(rest, "expected `:` after the test value"))?;
         buf.clear();
-        buf.extend(operands);
 
-        Some(EqnRef { value, args: buf })
+        // each operand is preceded by a single space
+        while let [b' ', tail @ ..] = rest {
+            let (operand, tail) = parse::parse_uint_checked(tail);
+            // concatenation counts an operand's digits, so 0 isn't one
+            let operand = operand
+                .and_then(|operand| u16::try_from(operand).ok())
+                .filter(|&operand| operand != 0 && tail.len() < rest.len() - 1)
+                .ok_or_else(|| error(&rest[1..], "expected an operand from 1 to 65535"))?;
+
+            buf.push(operand);
+            rest = tail;
+        }
+
+        if buf.is_empty() {
+            return Err(error(rest, "expected at least one operand"));
+        }
+
+        *s = rest;
+        Ok(Some(EqnRef { value, args: buf }))
     }
 
     /// Computes for *just* part 1.
@@ -123,47 +175,104 @@ fn unconcat(lhs: usize, rhs: usize) -> usize {
 /// Returns `true` iff `rhs` is a factor of `lhs`.
 #[inline(always)]
 fn divides(lhs: usize, rhs: usize) -> bool {
-    let quot = (lhs as f64) / (rhs as f64);
-    quot.floor() == quot
+    lhs.checked_rem(rhs) == Some(0)
 }
 
-/// Computes the solution to part 1.
-pub fn total_calibration_result(input: &str) -> usize {
-    let mut source = input;
+pub fn parse(input: &str) -> Result<Vec<Equation>, AocError> {
+    let mut source = input.as_bytes();
     let mut buf = Vec::with_capacity(OPERAND_BUFFER_CAPACITY);
+    let mut equations = Vec::new();
+
+    while let Some(eqn) = EqnRef::parse_next(input.as_bytes(), &mut source, &mut buf)? {
+        equations.push(Equation {
+            value: eqn.value,
+            args: eqn.args.to_vec(),
+        });
+    }
+
+    Ok(equations)
+}
+
+/// Solves both parts from `reader`, testing each equation as it's read.
+#[cfg(feature = "std")]
+pub fn solve_reader(reader: impl std::io::BufRead) -> Result<(Answer, Answer), StreamError> {
+    let mut buf = Vec::with_capacity(OPERAND_BUFFER_CAPACITY);
+    let (mut total, mut total_with_concatenation) = (0, 0);
+
+    LineReader::new(reader).for_each(|line| {
+        let mut rest = line.as_bytes();
+        let Some(eqn) = EqnRef::parse_next(line.as_bytes(), &mut rest, &mut buf)? else {
+            return Ok(());
+        };
+
+        let trailing = rest.trim_ascii();
+        if !trailing.is_empty() {
+            return Err(AocError::at_bytes(
+                line.as_bytes(),
+                trailing,
+                "expected an operand",
+            ));
+        }
 
-    let mut sum = 0;
-    while let Some(eqn) = EqnRef::parse_next(&mut source, &mut buf) {
         if eqn.is_solvable() {
-            sum += eqn.value;
+            total = arith::add(total, eqn.value);
         }
-    }
 
-    sum
+        if eqn.is_solvable_with_concatenation() {
+            total_with_concatenation = arith::add(total_with_concatenation, eqn.value);
+        }
+
+        Ok(())
+    })?;
+
+    Ok((total.into(), total_with_concatenation.into()))
 }
 
-/// Computes the solution to part 2.
-pub fn total_calibration_result_with_concatenation(input: &str) -> usize {
-    input
-        .par_split_terminator('\n')
-        .map(|mut s| {
-            let mut buf = Vec::with_capacity(OPERAND_BUFFER_CAPACITY / 8);
-            let eqn = EqnRef::parse_next(&mut s, &mut buf).unwrap();
-
-            if eqn.is_solvable_with_concatenation() {
-                eqn.value
-            } else {
-                0
-            }
-        })
-        .sum()
+/// Describes the structure of the input.
+pub fn stats(equations: &[Equation]) -> crate::Stats {
+    let most_operands = equations.iter().map(|eqn| eqn.args.len()).max();
+
+    vec![
+        ("equations", equations.len().to_string()),
+        ("most operands", most_operands.unwrap_or(0).to_string()),
+    ]
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Computes the solution to part 1.
+pub fn part1(equations: &[Equation]) -> Answer {
+    equations
+        .iter()
+        .map(Equation::as_ref)
+        .filter(EqnRef::is_solvable)
+        .fold(0, |sum, eqn| arith::add(sum, eqn.value))
+        .into()
+}
 
-    const EXAMPLE: &str = r#"190: 10 19
+/// Computes the solution to part 2.
+pub fn part2(equations: &[Equation]) -> Answer {
+    let value = |eqn: &Equation| {
+        let eqn = eqn.as_ref();
+
+        if eqn.is_solvable_with_concatenation() {
+            eqn.value
+        } else {
+            0
+        }
+    };
+
+    let total = match is_sequential() {
+        true => equations.iter().map(value).fold(0, arith::add),
+        false => equations
+            .par_iter()
+            .map(value)
+            .reduce_with(arith::add)
+            .unwrap_or(0),
+    };
+
+    total.into()
+}
+
+pub const EXAMPLE: &str = r#"190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -173,28 +282,48 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20"#;
 
-    const INPUT: &str = include_str!("../input/day07.txt");
+aoc_day! {
+    day: 7,
+    parsed: Vec<Equation>,
+    example_part_1: EXAMPLE => 3749,
+    example_part_2: EXAMPLE => 11387,
+    answer_part_1: 538191549061,
+    answer_part_2: 34612812972206,
+}
 
-    #[test]
-    fn example_part_1() {
-        assert_eq!(total_calibration_result(EXAMPLE), 3749);
-    }
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
 
     #[test]
-    fn part_1() {
-        assert_eq!(total_calibration_result(INPUT), 538191549061);
+    fn example_reader() {
+        assert_eq!(
+            solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            (3749usize.into(), 11387usize.into())
+        );
+        assert_eq!(
+            solve_reader("190: 10 19\n83 17 5\n".as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected `:` after the test value"
+        );
     }
 
     #[test]
-    fn example_part_2() {
-        assert_eq!(total_calibration_result_with_concatenation(EXAMPLE), 11387);
-    }
+    fn invalid_equations() {
+        let error = |input: &str| parse(input).unwrap_err().to_string();
 
-    #[test]
-    fn part_2() {
         assert_eq!(
-            total_calibration_result_with_concatenation(INPUT),
-            34612812972206
+            error("190: 10 19\n99999999999999999999: 1 2\n"),
+            "line 2, column 1: test value is too large"
+        );
+        assert_eq!(
+            error("190: 10 0 19\n"),
+            "line 1, column 9: expected an operand from 1 to 65535"
+        );
+        assert_eq!(
+            error("190: 10 65536\n"),
+            "line 1, column 9: expected an operand from 1 to 65535"
         );
     }
 }
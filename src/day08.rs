@@ -0,0 +1,178 @@
+use alloc::{collections::BTreeMap, format, string::ToString, vec, vec::Vec};
+use core::{ops::RangeInclusive, str::FromStr};
+
+use crate::error::AocError;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::hash::HashSet;
+use crate::Answer;
+
+/// A map of the antennas in a grid, grouped by their frequency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AntennaMap {
+    nrows: usize,
+    ncols: usize,
+    antennas: BTreeMap<u8, Vec<Point>>,
+}
+
+impl FromStr for AntennaMap {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(s, |c| match c {
+            '.' => Some(None),
+            c if c.is_ascii_alphanumeric() => Some(Some(c as u8)),
+            _ => None,
+        })?;
+
+        if grid.rows() == 0 {
+            return Err(AocError::at_end(s, "the map is empty"));
+        }
+
+        let mut antennas = BTreeMap::<_, Vec<_>>::new();
+
+        for ((row, col), &frequency) in grid.enumerate() {
+            if let Some(frequency) = frequency {
+                antennas
+                    .entry(frequency)
+                    .or_default()
+                    .push(Point::from((row, col)));
+            }
+        }
+
+        Ok(Self {
+            nrows: grid.rows(),
+            ncols: grid.cols(),
+            antennas,
+        })
+    }
+}
+
+impl AntennaMap {
+    /// Returns the grid position of `point`, if it lies inside the grid.
+    fn position(&self, point: Point) -> Option<(usize, usize)> {
+        point
+            .to_position()
+            .filter(|&(row, col)| row < self.nrows && col < self.ncols)
+    }
+}
+
+/// Which multiples of the offset between a pair of antennas produce antinodes.
+///
+/// For antennas `a` and `b` with the same frequency, the `k`th harmonic is
+/// the position `b + k * (b - a)` (and likewise with `a` and `b` swapped), so
+/// the 0th harmonic is the antenna itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Harmonics {
+    /// Only the 1st harmonic, at twice the distance from one antenna as from
+    /// the other (part 1).
+    Pair,
+    /// Every harmonic, i.e. every grid position in line with the pair (part 2).
+    Line,
+    /// Only the harmonics in the given range.
+    Range(RangeInclusive<usize>),
+}
+
+impl Harmonics {
+    /// Returns the first harmonic and the last one, if there is a last one.
+    fn bounds(&self) -> (usize, Option<usize>) {
+        match self {
+            Harmonics::Pair => (1, Some(1)),
+            Harmonics::Line => (0, None),
+            Harmonics::Range(range) => (*range.start(), Some(*range.end())),
+        }
+    }
+}
+
+/// Returns an iterator over the positions of the antinodes in `map`, as
+/// `(row, col)` pairs. Antinodes produced by several pairs of antennas are
+/// yielded once for each pair.
+pub fn antinodes(
+    map: &AntennaMap,
+    harmonics: Harmonics,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let (first, last) = harmonics.bounds();
+
+    let pairs = map.antennas.values().flat_map(|antennas| {
+        antennas.iter().flat_map(move |&a| {
+            antennas
+                .iter()
+                .filter(move |&&b| b != a)
+                .map(move |&b| (a, b))
+        })
+    });
+
+    pairs.flat_map(move |(a, b)| {
+        let offset = b - a;
+
+        (first..=last.unwrap_or(usize::MAX))
+            .map(move |k| b + offset * k as isize)
+            .map_while(|point| map.position(point))
+    })
+}
+
+pub fn parse(input: &str) -> Result<AntennaMap, AocError> {
+    input.parse()
+}
+
+/// Describes the structure of the input.
+pub fn stats(map: &AntennaMap) -> crate::Stats {
+    let antennas = map.antennas.values().map(Vec::len).sum::<usize>();
+
+    vec![
+        ("grid", format!("{}x{}", map.nrows, map.ncols)),
+        ("antennas", antennas.to_string()),
+        ("frequencies", map.antennas.len().to_string()),
+    ]
+}
+
+/// Counts the distinct positions of the antinodes in `map`.
+fn count_antinodes(map: &AntennaMap, harmonics: Harmonics) -> usize {
+    antinodes(map, harmonics).collect::<HashSet<_>>().len()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(map: &AntennaMap) -> Answer {
+    count_antinodes(map, Harmonics::Pair).into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(map: &AntennaMap) -> Answer {
+    count_antinodes(map, Harmonics::Line).into()
+}
+
+pub const EXAMPLE: &str = r#"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............"#;
+
+aoc_day! {
+    day: 8,
+    parsed: AntennaMap,
+    example_part_1: EXAMPLE => 14,
+    example_part_2: EXAMPLE => 34,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harmonic_ranges() {
+        let map = parse(EXAMPLE).unwrap();
+
+        assert_eq!(count_antinodes(&map, Harmonics::Range(1..=1)), 14);
+        // the 0th harmonic of each pair is just the antenna itself
+        assert_eq!(count_antinodes(&map, Harmonics::Range(0..=0)), 7);
+        assert_eq!(count_antinodes(&map, Harmonics::Range(0..=100)), 34);
+    }
+}
@@ -0,0 +1,213 @@
+use alloc::{collections::BinaryHeap, string::ToString, vec, vec::Vec};
+use core::{cmp::Reverse, str::FromStr};
+
+use crate::arith;
+use crate::error::AocError;
+use crate::Answer;
+
+/// The longest span that a single digit of the disk map can describe.
+const MAX_SPAN: usize = 9;
+
+/// A contiguous run of blocks on the disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Span {
+    start: usize,
+    len: usize,
+}
+
+/// The layout of the files and free space on a disk, as described by its
+/// disk map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiskMap {
+    /// The span occupied by each file, indexed by its ID.
+    files: Vec<Span>,
+    /// The free spans between the files, in order.
+    free: Vec<Span>,
+}
+
+impl FromStr for DiskMap {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut files = Vec::new();
+        let mut free = Vec::new();
+        let mut start = 0;
+
+        let map = s.trim();
+        for (index, digit) in map.bytes().enumerate() {
+            if !digit.is_ascii_digit() {
+                return Err(AocError::at(s, &map[index..], "expected a digit"));
+            }
+
+            let span = Span {
+                start,
+                len: (digit - b'0') as usize,
+            };
+
+            // the map alternates between files and free space
+            match index % 2 {
+                0 => files.push(span),
+                _ => free.push(span),
+            }
+
+            start += span.len;
+        }
+
+        Ok(Self { files, free })
+    }
+}
+
+impl DiskMap {
+    /// Returns the ID of the file in each block, or `None` for free blocks.
+    fn blocks(&self) -> Vec<Option<usize>> {
+        let len = self.files.last().map_or(0, |file| file.start + file.len);
+        let mut blocks = vec![None; len];
+
+        for (id, file) in self.files.iter().enumerate() {
+            blocks[file.start..file.start + file.len].fill(Some(id));
+        }
+
+        blocks
+    }
+
+    /// Moves file blocks one at a time from the end of the disk into the
+    /// leftmost free block, and returns the resulting checksum.
+    pub fn checksum_after_compaction(&self) -> u64 {
+        let mut blocks = self.blocks();
+        let (mut left, mut right) = (0, blocks.len());
+
+        loop {
+            while left < right && blocks[left].is_some() {
+                left += 1;
+            }
+
+            while left < right && blocks[right - 1].is_none() {
+                right -= 1;
+            }
+
+            if left >= right {
+                break;
+            }
+
+            blocks.swap(left, right - 1);
+        }
+
+        arith::sum(
+            blocks
+                .iter()
+                .enumerate()
+                .filter_map(|(position, id)| Some((position * (*id)?) as u64)),
+        )
+    }
+
+    /// Moves each whole file, in decreasing order of ID, into the leftmost
+    /// free span that can hold it (if that span is to the left of the file),
+    /// and returns the resulting checksum.
+    ///
+    /// The free spans are kept in a min-heap of start positions for each span
+    /// length, so finding the leftmost span that fits only needs to look at
+    /// the top of [`MAX_SPAN`] heaps. Space that a file moves out of is never
+    /// reused, since it lies to the right of every file that hasn't moved yet.
+    pub fn checksum_after_file_compaction(&self) -> u64 {
+        let mut free_lists = vec![BinaryHeap::new(); MAX_SPAN + 1];
+        for span in self.free.iter().filter(|span| span.len > 0) {
+            free_lists[span.len].push(Reverse(span.start));
+        }
+
+        let mut checksum = 0;
+
+        for (id, file) in self.files.iter().enumerate().rev() {
+            let leftmost = (file.len..=MAX_SPAN)
+                .filter_map(|len| Some((free_lists[len].peek()?.0, len)))
+                .filter(|&(start, _)| start < file.start)
+                .min();
+
+            let start = match leftmost {
+                None => file.start,
+                Some((start, len)) => {
+                    free_lists[len].pop();
+
+                    let remaining = len - file.len;
+                    if remaining > 0 {
+                        free_lists[remaining].push(Reverse(start + file.len));
+                    }
+
+                    start
+                }
+            };
+
+            let positions = file.len * start + file.len * file.len.saturating_sub(1) / 2;
+            checksum = arith::add(checksum, (id * positions) as u64);
+        }
+
+        checksum
+    }
+}
+
+pub fn parse(input: &str) -> Result<DiskMap, AocError> {
+    input.parse()
+}
+
+/// Describes the structure of the input.
+pub fn stats(disk: &DiskMap) -> crate::Stats {
+    let used = disk.files.iter().map(|file| file.len).sum::<usize>();
+    let free = disk.free.iter().map(|span| span.len).sum::<usize>();
+
+    vec![
+        ("files", disk.files.len().to_string()),
+        ("used blocks", used.to_string()),
+        ("free blocks", free.to_string()),
+    ]
+}
+
+/// Computes the solution to part 1.
+pub fn part1(disk: &DiskMap) -> Answer {
+    disk.checksum_after_compaction().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(disk: &DiskMap) -> Answer {
+    disk.checksum_after_file_compaction().into()
+}
+
+pub const EXAMPLE: &str = "2333133121414131402";
+
+aoc_day! {
+    day: 9,
+    parsed: DiskMap,
+    example_part_1: EXAMPLE => 1928,
+    example_part_2: EXAMPLE => 2858,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders the blocks of `disk` like the puzzle description does.
+    fn render(disk: &DiskMap) -> String {
+        disk.blocks()
+            .into_iter()
+            .map(|id| id.map_or('.', |id| char::from_digit(id as u32, 10).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn example_layout() {
+        assert_eq!(render(&parse("12345").unwrap()), "0..111....22222");
+        assert_eq!(
+            render(&parse(EXAMPLE).unwrap()),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+    }
+
+    #[test]
+    fn files_only_move_left() {
+        // file 2 fills the first gap, and then the only gap that could hold
+        // file 1 is to its right
+        let disk = parse("12332").unwrap();
+        assert_eq!(render(&disk), "0..111...22");
+        assert_eq!(disk.checksum_after_file_compaction(), 2 * (1 + 2) + 3 + 4 + 5);
+    }
+}
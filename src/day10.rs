@@ -0,0 +1,144 @@
+use alloc::{format, string::ToString, vec};
+use core::str::FromStr;
+
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::{arith, error::AocError, search, Answer};
+
+/// The height of a trailhead.
+const TRAILHEAD: u8 = 0;
+
+/// The height of the end of a trail.
+const PEAK: u8 = 9;
+
+/// A `(row, col)` position on the map.
+type Position = (usize, usize);
+
+/// A topographic map of the heights of a lava island.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopoMap {
+    heights: Grid<u8>,
+}
+
+impl FromStr for TopoMap {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heights = Grid::parse(s, |c| c.to_digit(10).map(|height| height as u8))?;
+
+        if heights.rows() == 0 {
+            return Err(AocError::at_end(s, "the map is empty"));
+        }
+
+        Ok(Self { heights })
+    }
+}
+
+impl TopoMap {
+    /// Returns an iterator over the positions of the trailheads.
+    pub fn trailheads(&self) -> impl Iterator<Item = Position> + '_ {
+        self.heights.positions(|&height| height == TRAILHEAD)
+    }
+
+    /// Returns the in-bounds positions one step uphill from `position`.
+    fn uphill(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        let target = self.heights[position] + 1;
+
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.heights.offset(position, direction))
+            .filter(move |&next| self.heights[next] == target)
+    }
+
+    /// Counts the distinct peaks reachable from `trailhead` by hiking trails,
+    /// using a breadth-first search.
+    pub fn score(&self, trailhead: Position) -> usize {
+        search::bfs(trailhead, |&position| self.uphill(position))
+            .iter()
+            .filter(|&(&position, _)| self.heights[position] == PEAK)
+            .count()
+    }
+
+    /// Counts the distinct hiking trails that start at `trailhead`, using a
+    /// depth-first search.
+    pub fn rating(&self, trailhead: Position) -> usize {
+        match self.heights[trailhead] {
+            PEAK => 1,
+            _ => arith::sum(self.uphill(trailhead).map(|next| self.rating(next))),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<TopoMap, AocError> {
+    input.parse()
+}
+
+/// Describes the structure of the input.
+pub fn stats(map: &TopoMap) -> crate::Stats {
+    let (nrows, ncols) = map.heights.shape();
+    let peaks = map.heights.iter().filter(|&&height| height == PEAK).count();
+
+    vec![
+        ("grid", format!("{nrows}x{ncols}")),
+        ("trailheads", map.trailheads().count().to_string()),
+        ("peaks", peaks.to_string()),
+    ]
+}
+
+/// Computes the solution to part 1.
+pub fn part1(map: &TopoMap) -> Answer {
+    arith::sum(map.trailheads().map(|trailhead| map.score(trailhead))).into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(map: &TopoMap) -> Answer {
+    arith::sum(map.trailheads().map(|trailhead| map.rating(trailhead))).into()
+}
+
+pub const EXAMPLE: &str = r#"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732"#;
+
+aoc_day! {
+    day: 10,
+    parsed: TopoMap,
+    example_part_1: EXAMPLE => 36,
+    example_part_2: EXAMPLE => 81,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_trailheads() {
+        let map = parse(EXAMPLE).unwrap();
+
+        let scores = map
+            .trailheads()
+            .map(|trailhead| map.score(trailhead))
+            .collect::<Vec<_>>();
+        assert_eq!(scores, [5, 6, 5, 3, 1, 3, 5, 3, 5]);
+
+        let ratings = map
+            .trailheads()
+            .map(|trailhead| map.rating(trailhead))
+            .collect::<Vec<_>>();
+        assert_eq!(ratings, [20, 24, 10, 4, 1, 4, 5, 8, 5]);
+    }
+
+    #[test]
+    fn single_trailhead() {
+        let map = parse("0123\n1234\n8765\n9876").unwrap();
+
+        assert_eq!(map.trailheads().collect::<Vec<_>>(), [(0, 0)]);
+        assert_eq!(map.score((0, 0)), 1);
+        assert_eq!(map.rating((0, 0)), 16);
+    }
+}
@@ -0,0 +1,169 @@
+use core::str::FromStr;
+
+use crate::arith;
+use crate::error::AocError;
+use crate::hash::HashMap;
+use crate::memo::Memo;
+use crate::parse;
+use crate::Answer;
+
+/// The stones in a line, stored as a map from engravings to the number of
+/// stones with that engraving.
+///
+/// The order of the stones never affects how they evolve, so we only need to
+/// track how many copies of each engraving exist. This keeps the map small
+/// (a few thousand entries) even after the counts have grown astronomically.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stones {
+    counts: HashMap<u64, u128>,
+}
+
+impl Stones {
+    /// Returns the total number of stones.
+    pub fn len(&self) -> u128 {
+        self.counts.values().sum()
+    }
+
+    /// Returns `true` if there are no stones at all.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the number of distinct engravings among the stones.
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns an iterator over the number of stones after each successive
+    /// blink, starting with the count after the first blink.
+    ///
+    /// Every count shares one cache, so each is cheap after the one before it.
+    pub fn growth(self) -> impl Iterator<Item = u128> {
+        let mut memo = Memo::default();
+        (1..).map(move |n| self.count_after(&mut memo, n))
+    }
+
+    /// Counts the stones after `n` blinks, caching counts for single stones
+    /// in `memo`.
+    fn count_after(&self, memo: &mut Memo<(u64, usize), u128>, n: usize) -> u128 {
+        self.counts
+            .iter()
+            .map(|(&stone, &count)| count * count_after(memo, stone, n))
+            .sum()
+    }
+}
+
+impl FromStr for Stones {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut counts = HashMap::default();
+
+        for raw in s.split_whitespace() {
+            *counts.entry(parse::number::<u64>(s, raw)?).or_default() += 1;
+        }
+
+        Ok(Self { counts })
+    }
+}
+
+/// A single engraved stone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stone(u64);
+
+impl From<u64> for Stone {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl Stone {
+    /// Applies the rules to a single stone, returning one or two new engravings.
+    fn blink(self) -> (u64, Option<u64>) {
+        if self.0 == 0 {
+            return (1, None);
+        }
+
+        let digits = self.0.ilog10() + 1;
+
+        if digits.is_multiple_of(2) {
+            let split = 10u64.pow(digits / 2);
+            (self.0 / split, Some(self.0 % split))
+        } else {
+            (arith::mul(self.0, 2024), None)
+        }
+    }
+}
+
+/// Counts the stones that a stone engraved with `stone` becomes after `n`
+/// blinks.
+fn count_after(memo: &mut Memo<(u64, usize), u128>, stone: u64, n: usize) -> u128 {
+    if n == 0 {
+        return 1;
+    }
+
+    memo.get_or_insert_with((stone, n), |memo| match Stone::from(stone).blink() {
+        (left, None) => count_after(memo, left, n - 1),
+        (left, Some(right)) => count_after(memo, left, n - 1) + count_after(memo, right, n - 1),
+    })
+}
+
+/// Computes the number of stones after blinking `n` times at `stones`.
+pub fn stones_after(stones: &Stones, n: usize) -> u128 {
+    stones.count_after(&mut Memo::default(), n)
+}
+
+pub fn parse(input: &str) -> Result<Stones, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(stones: &Stones) -> Answer {
+    Answer::int(stones_after(stones, 25))
+}
+
+/// Computes the solution to part 2.
+pub fn part2(stones: &Stones) -> Answer {
+    Answer::int(stones_after(stones, 75))
+}
+
+pub const EXAMPLE: &str = "125 17";
+
+aoc_day! {
+    day: 11,
+    parsed: Stones,
+    example_part_1: EXAMPLE => 55312,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_blink() {
+        assert_eq!(stones_after(&parse("0 1 10 99 999").unwrap(), 1), 7);
+    }
+
+    #[test]
+    fn example_growth() {
+        let stones = parse(EXAMPLE).unwrap();
+        let growth = stones.growth().take(6).collect::<Vec<_>>();
+        assert_eq!(growth, [3, 4, 5, 9, 13, 22]);
+    }
+
+    #[test]
+    fn deep_blinks_do_not_overflow() {
+        // the count grows by a factor of roughly 1.5 per blink, so this is
+        // well past anything a u64 accumulator could hold
+        assert!(stones_after(&parse(EXAMPLE).unwrap(), 150) > u128::from(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(any(debug_assertions, feature = "checked-arithmetic"))]
+    fn oversized_engravings_overflow() {
+        // 19 digits is odd, and the product doesn't fit in a u64
+        stones_after(&parse("1000000000000000000").unwrap(), 1);
+    }
+}
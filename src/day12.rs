@@ -0,0 +1,280 @@
+use alloc::{vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::Answer;
+
+/// A `(row, col)` position in a grid.
+pub type Position = (usize, usize);
+
+/// A map of garden plots, each labelled with the plant growing there.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Garden {
+    plots: Grid<u8>,
+}
+
+impl FromStr for Garden {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let plots = Grid::parse(s, |c| c.is_ascii_graphic().then_some(c as u8))?;
+
+        if plots.rows() == 0 {
+            return Err(AocError::at_end(s, "the garden is empty"));
+        }
+
+        Ok(Self { plots })
+    }
+}
+
+impl Garden {
+    /// Returns an iterator over the regions of the garden, in row-major order
+    /// of their top-left-most cell.
+    pub fn regions(&self) -> Regions<'_> {
+        Regions {
+            garden: self,
+            visited: Grid::from_element(self.plots.rows(), self.plots.cols(), false),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the neighbour of `position` in `direction` if it grows the same
+    /// plant, or `None` if it doesn't or lies outside the garden.
+    fn same_plant(&self, position: Position, direction: Direction) -> Option<Position> {
+        self.plots
+            .offset(position, direction)
+            .filter(|&next| self.plots[next] == self.plots[position])
+    }
+
+    /// Counts the number of corners of the region at `position` that occur on
+    /// that cell. The number of corners of a polygon is equal to its number of
+    /// sides, so summing this over a region gives the number of sides.
+    fn corners_at(&self, position: Position) -> usize {
+        let same = |direction| self.same_plant(position, direction).is_some();
+
+        Direction::CARDINAL
+            .into_iter()
+            .filter(|&a| {
+                let b = a.turn_right();
+
+                match (same(a), same(b)) {
+                    // convex corner
+                    (false, false) => true,
+                    // concave corner
+                    (true, true) => !same(a.rotate(1)),
+                    _ => false,
+                }
+            })
+            .count()
+    }
+}
+
+/// A contiguous region of plots growing the same plant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub plant: char,
+    /// The `(row, col)` positions of the plots in the region, in the order
+    /// they were discovered by the flood fill.
+    pub cells: Vec<Position>,
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+}
+
+impl Region {
+    /// The price of fencing this region, as used in part 1.
+    pub fn price(&self) -> usize {
+        self.area * self.perimeter
+    }
+
+    /// The discounted price of fencing this region, as used in part 2.
+    pub fn bulk_price(&self) -> usize {
+        self.area * self.sides
+    }
+}
+
+/// An iterator over the [`Region`]s of a [`Garden`].
+#[derive(Debug, Clone)]
+pub struct Regions<'a> {
+    garden: &'a Garden,
+    visited: Grid<bool>,
+    /// The row-major index of the next cell to check.
+    cursor: usize,
+}
+
+impl Iterator for Regions<'_> {
+    type Item = Region;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let plots = &self.garden.plots;
+        let ncols = plots.cols();
+
+        // find the next unvisited cell
+        let start = loop {
+            if self.cursor >= plots.len() {
+                return None;
+            }
+
+            let position = (self.cursor / ncols, self.cursor % ncols);
+            self.cursor += 1;
+
+            if !self.visited[position] {
+                break position;
+            }
+        };
+
+        let plant = plots[start];
+        self.visited[start] = true;
+
+        let mut cells = vec![start];
+        let mut perimeter = 0;
+        let mut sides = 0;
+        let mut i = 0;
+
+        // flood fill, using `cells` as the queue
+        while let Some(&position) = cells.get(i) {
+            i += 1;
+            sides += self.garden.corners_at(position);
+
+            for direction in Direction::CARDINAL {
+                let Some(neighbour) = self.garden.same_plant(position, direction) else {
+                    perimeter += 1;
+                    continue;
+                };
+
+                if !self.visited[neighbour] {
+                    self.visited[neighbour] = true;
+                    cells.push(neighbour);
+                }
+            }
+        }
+
+        Some(Region {
+            plant: plant as char,
+            area: cells.len(),
+            cells,
+            perimeter,
+            sides,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(garden: &Garden) -> Answer {
+    garden
+        .regions()
+        .map(|region| region.price())
+        .sum::<usize>()
+        .into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(garden: &Garden) -> Answer {
+    garden
+        .regions()
+        .map(|region| region.bulk_price())
+        .sum::<usize>()
+        .into()
+}
+
+pub const EXAMPLE_SMALL: &str = r#"AAAA
+BBCD
+BBCC
+EEEC"#;
+
+pub const EXAMPLE_NESTED: &str = r#"OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO"#;
+
+pub const EXAMPLE_E: &str = r#"EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE"#;
+
+pub const EXAMPLE_DIAGONAL: &str = r#"AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA"#;
+
+pub const EXAMPLE_LARGE: &str = r#"RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE"#;
+
+aoc_day! {
+    day: 12,
+    parsed: Garden,
+    example_part_1: EXAMPLE_LARGE => 1930,
+    example_part_2: EXAMPLE_LARGE => 1206,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_regions() {
+        let garden = EXAMPLE_SMALL.parse::<Garden>().unwrap();
+        let regions = garden
+            .regions()
+            .map(|r| (r.plant, r.area, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            regions,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn example_nested_regions() {
+        let garden = EXAMPLE_NESTED.parse::<Garden>().unwrap();
+        let (outer, inner): (Vec<_>, Vec<_>) = garden.regions().partition(|r| r.plant == 'O');
+
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer[0].area, 21);
+        assert_eq!(outer[0].perimeter, 36);
+        assert_eq!(inner.len(), 4);
+        assert!(inner.iter().all(|r| r.area == 1 && r.perimeter == 4));
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(part1(&parse(EXAMPLE_SMALL).unwrap()), 140);
+        assert_eq!(part1(&parse(EXAMPLE_NESTED).unwrap()), 772);
+        assert_eq!(part1(&parse(EXAMPLE_LARGE).unwrap()), 1930);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(part2(&parse(EXAMPLE_SMALL).unwrap()), 80);
+        assert_eq!(part2(&parse(EXAMPLE_NESTED).unwrap()), 436);
+        assert_eq!(part2(&parse(EXAMPLE_E).unwrap()), 236);
+        assert_eq!(part2(&parse(EXAMPLE_DIAGONAL).unwrap()), 368);
+        assert_eq!(part2(&parse(EXAMPLE_LARGE).unwrap()), 1206);
+    }
+}
@@ -0,0 +1,383 @@
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::grid::Vector2;
+use crate::numthe::extended_gcd;
+use crate::parse;
+use crate::Answer;
+
+/// The offset applied to every prize coordinate in part 2.
+pub const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+/// The token cost of pressing the A button.
+const A_COST: i64 = 3;
+
+/// The token cost of pressing the B button.
+const B_COST: i64 = 1;
+
+/// A single claw machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Machine {
+    a: Vector2<i64>,
+    b: Vector2<i64>,
+    prize: Vector2<i64>,
+}
+
+impl Machine {
+    pub fn new(a: [i64; 2], b: [i64; 2], prize: [i64; 2]) -> Self {
+        Self {
+            a: a.into(),
+            b: b.into(),
+            prize: prize.into(),
+        }
+    }
+
+    /// Returns a copy of `self` with `offset` added to both prize coordinates,
+    /// or `None` if either would overflow.
+    pub fn with_offset(self, offset: i64) -> Option<Self> {
+        let prize = Vector2::new(
+            self.prize.x.checked_add(offset)?,
+            self.prize.y.checked_add(offset)?,
+        );
+
+        Some(Self { prize, ..self })
+    }
+
+    /// Returns the cheapest number of `(a, b)` presses that reach the prize, if
+    /// the prize is reachable at all (in presses that fit in an `i64`).
+    pub fn cheapest_presses(&self) -> Option<(i64, i64)> {
+        let det = cross(self.a, self.b);
+
+        if det != 0 {
+            self.unique_presses(det)
+        } else {
+            self.colinear_presses()
+        }
+    }
+
+    /// Returns the fewest tokens needed to win the prize, if it can be won (in
+    /// a number of tokens that fits in an `i64`).
+    pub fn fewest_tokens(&self) -> Option<i64> {
+        let (a, b) = self.cheapest_presses()?;
+        A_COST.checked_mul(a)?.checked_add(B_COST.checked_mul(b)?)
+    }
+
+    /// Solves the system by Cramer's rule when the button vectors are
+    /// linearly independent, in which case there is at most one solution.
+    fn unique_presses(&self, det: i128) -> Option<(i64, i64)> {
+        let a_num = cross(self.prize, self.b);
+        let b_num = cross(self.a, self.prize);
+
+        if a_num % det != 0 || b_num % det != 0 {
+            return None;
+        }
+
+        let a = i64::try_from(a_num / det).ok()?;
+        let b = i64::try_from(b_num / det).ok()?;
+        Some((a, b)).filter(|&(a, b)| a >= 0 && b >= 0)
+    }
+
+    /// Solves the system when the button vectors are parallel (or zero). The
+    /// prize must then lie on the same line, and the problem reduces to the
+    /// one-dimensional minimization of `3a + b` subject to `a * u + b * v = w`.
+    fn colinear_presses(&self) -> Option<(i64, i64)> {
+        let direction = [self.a, self.b]
+            .into_iter()
+            .find(|v| *v != Vector2::zeros());
+
+        let Some(direction) = direction else {
+            // neither button moves the claw
+            return Some((0, 0)).filter(|_| self.prize == Vector2::zeros());
+        };
+
+        if cross(direction, self.prize) != 0 {
+            return None;
+        }
+
+        // project onto an axis along which the line is not constant
+        let axis = if direction.x != 0 { 0 } else { 1 };
+        solve_linear_minimum(self.a[axis], self.b[axis], self.prize[axis])
+    }
+}
+
+impl FromStr for Machine {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        let mut next = |prefix: &str, sep: char| {
+            let line = lines.next().ok_or_else(|| {
+                AocError::at_end(s, format_args!("expected a `{}` line", prefix.trim_end()))
+            })?;
+
+            parse_pair(s, line, prefix, sep)
+        };
+
+        let a = next("Button A: ", '+')?;
+        let b = next("Button B: ", '+')?;
+        let prize = next("Prize: ", '=')?;
+
+        Ok(Self::new(a, b, prize))
+    }
+}
+
+/// Parses a line (of `input`) of the form `{prefix}X{sep}123, Y{sep}456`.
+fn parse_pair(input: &str, line: &str, prefix: &str, sep: char) -> Result<[i64; 2], AocError> {
+    let malformed = || {
+        AocError::at(
+            input,
+            line,
+            format_args!("expected `{prefix}X{sep}.., Y{sep}..`"),
+        )
+    };
+
+    let (x, y) = line
+        .strip_prefix(prefix)
+        .and_then(|tail| tail.split_once(", "))
+        .ok_or_else(malformed)?;
+
+    let x = x
+        .strip_prefix('X')
+        .and_then(|x| x.strip_prefix(sep))
+        .ok_or_else(malformed)?;
+    let y = y
+        .strip_prefix('Y')
+        .and_then(|y| y.strip_prefix(sep))
+        .ok_or_else(malformed)?;
+
+    Ok([parse::number(input, x)?, parse::number(input, y)?])
+}
+
+/// The 2D cross product (i.e. the determinant of `[lhs rhs]`), which is
+/// taken in `i128` so that it can't overflow.
+#[inline(always)]
+fn cross(lhs: Vector2<i64>, rhs: Vector2<i64>) -> i128 {
+    let (lx, ly) = (i128::from(lhs.x), i128::from(lhs.y));
+    let (rx, ry) = (i128::from(rhs.x), i128::from(rhs.y));
+    lx * ry - ly * rx
+}
+
+/// Finds non-negative integers `(a, b)` with `a * u + b * v = w` that
+/// minimize `3a + b`, or `None` if no such pair exists (or it doesn't fit in
+/// an `i64`). The intermediate values are taken in `i128`.
+fn solve_linear_minimum(u: i64, v: i64, w: i64) -> Option<(i64, i64)> {
+    let (g, x, y) = extended_gcd(u, v);
+    let (u, v, w, g) = (i128::from(u), i128::from(v), i128::from(w), i128::from(g));
+    let narrow = |a: i128, b: i128| Some((a.try_into().ok()?, b.try_into().ok()?));
+
+    match (u, v) {
+        (0, 0) => return Some((0, 0)).filter(|_| w == 0),
+        (0, v) if w % v == 0 && w / v >= 0 => return narrow(0, w / v),
+        (u, 0) if w % u == 0 && w / u >= 0 => return narrow(w / u, 0),
+        (0, _) | (_, 0) => return None,
+        _ => {}
+    }
+
+    if w % g != 0 {
+        return None;
+    }
+
+    // every solution has the form (a0 + k * step_a, b0 - k * step_b), where
+    // negating k if need be makes step_a positive
+    let (a0, b0) = (i128::from(x) * (w / g), i128::from(y) * (w / g));
+    let (step_a, step_b) = match v / g {
+        step if step > 0 => (step, u / g),
+        step => (-step, -u / g),
+    };
+
+    // a >= 0 bounds k below, and b >= 0 bounds it above if step_b is positive
+    // and below otherwise
+    let lower = -a0.div_euclid(step_a);
+
+    let k = if step_b > 0 {
+        let upper = b0.div_euclid(step_b);
+        if lower > upper {
+            return None;
+        }
+
+        // the cost is linear in k, so the minimum lies at one of the bounds
+        let slope = i128::from(A_COST) * step_a - i128::from(B_COST) * step_b;
+        if slope >= 0 {
+            lower
+        } else {
+            upper
+        }
+    } else {
+        // the cost grows with k, so the minimum lies at the larger lower bound
+        lower.max(-b0.div_euclid(-step_b))
+    };
+
+    let a = a0.checked_add(k.checked_mul(step_a)?)?;
+    let b = b0.checked_sub(k.checked_mul(step_b)?)?;
+    narrow(a, b)
+}
+
+/// Parses the blank-line-separated machine descriptions in `input`.
+pub fn parse(input: &str) -> Result<Vec<Machine>, AocError> {
+    parse::blocks(input)
+        .map(|block| {
+            block
+                .text
+                .parse()
+                .map_err(|error: AocError| error.within(input, block.text))
+        })
+        .collect()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(machines: &[Machine]) -> Answer {
+    let tokens = machines
+        .iter()
+        .filter_map(Machine::fewest_tokens)
+        .sum::<i64>();
+
+    Answer::int(tokens)
+}
+
+/// Computes the solution to part 2.
+pub fn part2(machines: &[Machine]) -> Answer {
+    let tokens = machines
+        .iter()
+        .filter_map(|machine| machine.with_offset(PRIZE_OFFSET)?.fewest_tokens())
+        .sum::<i64>();
+
+    Answer::int(tokens)
+}
+
+pub const EXAMPLE: &str = r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279"#;
+
+aoc_day! {
+    day: 13,
+    parsed: Vec<Machine>,
+    example_part_1: EXAMPLE => 480,
+    example_part_2: EXAMPLE => 875318608908_u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_machines() {
+        let machines = parse(EXAMPLE).unwrap();
+        let presses = machines
+            .iter()
+            .map(Machine::cheapest_presses)
+            .collect::<Vec<_>>();
+
+        assert_eq!(presses, [Some((80, 40)), None, Some((38, 86)), None]);
+    }
+
+    #[test]
+    fn example_winnable_machines() {
+        let winnable = parse(EXAMPLE)
+            .unwrap()
+            .into_iter()
+            .map(|m| {
+                m.with_offset(PRIZE_OFFSET)
+                    .unwrap()
+                    .fewest_tokens()
+                    .is_some()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(winnable, [false, true, false, true]);
+    }
+
+    #[test]
+    fn malformed_machine_is_located() {
+        let input = EXAMPLE.replace("\n", "\r\n").replace("Y=6450", "Y=64x50");
+        let error = parse(&input).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 11, column 18: invalid number \"64x50\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn parallel_buttons_prefer_cheaper_distance() {
+        // A moves 4 units for 3 tokens, B moves 1 unit for 1 token
+        let machine = Machine::new([4, 4], [1, 1], [10, 10]);
+        assert_eq!(machine.cheapest_presses(), Some((2, 2)));
+        assert_eq!(machine.fewest_tokens(), Some(8));
+
+        // A moves 2 units for 3 tokens, so B alone is always cheaper
+        let machine = Machine::new([2, 2], [1, 1], [10, 10]);
+        assert_eq!(machine.cheapest_presses(), Some((0, 10)));
+    }
+
+    #[test]
+    fn parallel_buttons_with_gcd_constraint() {
+        // 6a + 4b = 22 only has solutions with a odd
+        let machine = Machine::new([6, 3], [4, 2], [22, 11]);
+        assert_eq!(machine.cheapest_presses(), Some((1, 4)));
+
+        let machine = Machine::new([6, 3], [4, 2], [23, 11]);
+        assert_eq!(machine.cheapest_presses(), None);
+
+        let machine = Machine::new([2, 2], [4, 4], [5, 5]);
+        assert_eq!(machine.cheapest_presses(), None);
+    }
+
+    #[test]
+    fn parallel_buttons_off_line_prize() {
+        let machine = Machine::new([1, 1], [2, 2], [3, 4]);
+        assert_eq!(machine.cheapest_presses(), None);
+    }
+
+    #[test]
+    fn degenerate_zero_buttons() {
+        let machine = Machine::new([0, 0], [1, 2], [3, 6]);
+        assert_eq!(machine.cheapest_presses(), Some((0, 3)));
+
+        let machine = Machine::new([0, 0], [0, 0], [0, 0]);
+        assert_eq!(machine.cheapest_presses(), Some((0, 0)));
+
+        let machine = Machine::new([0, 0], [0, 0], [1, 0]);
+        assert_eq!(machine.cheapest_presses(), None);
+    }
+
+    #[test]
+    fn vertical_parallel_buttons() {
+        let machine = Machine::new([0, 4], [0, 1], [0, 7]);
+        assert_eq!(machine.cheapest_presses(), Some((1, 3)));
+
+        let machine = Machine::new([0, 5], [0, 1], [0, 11]);
+        assert_eq!(machine.cheapest_presses(), Some((2, 1)));
+    }
+
+    #[test]
+    fn huge_coordinates() {
+        // the determinant is far larger than an i64
+        let max = i64::MAX;
+        let machine = Machine::new([max, 0], [0, max], [max, max]);
+        assert_eq!(machine.cheapest_presses(), Some((1, 1)));
+        assert_eq!(machine.fewest_tokens(), Some(4));
+
+        // and so is b0 = -(max - 1) / 2 * max in the parallel case
+        let machine = Machine::new([max, max], [2, 2], [max, max]);
+        assert_eq!(machine.cheapest_presses(), Some((1, 0)));
+
+        // the presses fit, but the tokens don't
+        let machine = Machine::new([1, 0], [0, 1], [max, max]);
+        assert_eq!(machine.fewest_tokens(), None);
+        assert_eq!(machine.with_offset(1), None);
+    }
+}
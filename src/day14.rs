@@ -0,0 +1,303 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::str::FromStr;
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::{numthe, parse, Answer};
+
+/// The width of the real lobby.
+pub const WIDTH: i64 = 101;
+
+/// The height of the real lobby.
+pub const HEIGHT: i64 = 103;
+
+/// A single robot, with its initial position and velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Robot {
+    position: (i64, i64),
+    velocity: (i64, i64),
+}
+
+impl FromStr for Robot {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || AocError::at(s, s, "expected a robot like `p=0,4 v=3,-3`");
+
+        let (position, velocity) = s
+            .trim()
+            .strip_prefix("p=")
+            .and_then(|s| s.split_once(" v="))
+            .ok_or_else(malformed)?;
+
+        let pair = |pair: &str| -> Result<(i64, i64), AocError> {
+            let (x, y) = pair.split_once(',').ok_or_else(malformed)?;
+            Ok((parse::number(s, x)?, parse::number(s, y)?))
+        };
+
+        Ok(Self {
+            position: pair(position)?,
+            velocity: pair(velocity)?,
+        })
+    }
+}
+
+/// The lobby, containing all the robots.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lobby {
+    width: i64,
+    height: i64,
+    robots: Vec<Robot>,
+}
+
+/// Why a lobby can't be searched for the picture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidLobby {
+    /// The dimensions aren't positive and coprime, which
+    /// [`Lobby::most_clustered_time`] needs.
+    #[error("a {width}x{height} lobby's width and height must be positive and coprime")]
+    Dimensions { width: i64, height: i64 },
+}
+
+impl DayError for InvalidLobby {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Lobby {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+impl Lobby {
+    /// Parses the robots in `input` into a lobby with the given dimensions,
+    /// which must be positive and coprime.
+    pub fn parse(input: &str, width: i64, height: i64) -> Result<Self, AocError> {
+        if width <= 0 || height <= 0 || numthe::gcd(width, height) != 1 {
+            return Err(AocError::invalid(
+                input,
+                input,
+                InvalidLobby::Dimensions { width, height },
+            ));
+        }
+
+        // the robots wrap around the lobby, so bringing every position and
+        // velocity into it changes nothing, and keeps the arithmetic small
+        let robots = parse::parse_lines::<Robot>(input)?
+            .into_iter()
+            .map(|Robot { position, velocity }| Robot {
+                position: (position.0.rem_euclid(width), position.1.rem_euclid(height)),
+                velocity: (velocity.0.rem_euclid(width), velocity.1.rem_euclid(height)),
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            robots,
+        })
+    }
+
+    /// Returns the positions of the robots after `seconds` have passed.
+    pub fn positions_at(&self, seconds: i64) -> impl Iterator<Item = (i64, i64)> + use<'_> {
+        self.robots
+            .iter()
+            .map(move |robot| (self.x_at(robot, seconds), self.y_at(robot, seconds)))
+    }
+
+    /// Computes the product of the robot counts in each quadrant after
+    /// `seconds` have passed.
+    pub fn safety_factor(&self, seconds: i64) -> usize {
+        let (mid_x, mid_y) = (self.width / 2, self.height / 2);
+        let mut quadrants = [0; 4];
+
+        for (x, y) in self.positions_at(seconds) {
+            if x == mid_x || y == mid_y {
+                continue;
+            }
+
+            let index = usize::from(x > mid_x) + 2 * usize::from(y > mid_y);
+            quadrants[index] += 1;
+        }
+
+        quadrants.into_iter().product()
+    }
+
+    /// Renders the lobby after `seconds` have passed, with each tile showing
+    /// the number of robots on it (or `.` if there are none).
+    pub fn render(&self, seconds: i64) -> String {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut counts = vec![0u32; width * height];
+
+        for (x, y) in self.positions_at(seconds) {
+            counts[y as usize * width + x as usize] += 1;
+        }
+
+        let mut output = String::with_capacity((width + 1) * height);
+        for row in counts.chunks(width) {
+            for &count in row {
+                output.push(match count {
+                    0 => '.',
+                    n => char::from_digit(n.min(9), 10).unwrap(),
+                });
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Finds the time at which the robots are most tightly clustered.
+    ///
+    /// The x-coordinates repeat with period `width` and the y-coordinates
+    /// with period `height`, and the two axes evolve independently. So we find
+    /// the offset within each period that minimizes the variance along that
+    /// axis, and then combine the two offsets with the Chinese remainder
+    /// theorem. This requires `width` and `height` to be coprime.
+    pub fn most_clustered_time(&self) -> Option<i64> {
+        let best_x = (0..self.width)
+            .min_by_key(|&t| variance(self.robots.iter().map(|robot| self.x_at(robot, t))))?;
+
+        let best_y = (0..self.height)
+            .min_by_key(|&t| variance(self.robots.iter().map(|robot| self.y_at(robot, t))))?;
+
+        numthe::crt([(best_x, self.width), (best_y, self.height)]).map(|(time, _)| time)
+    }
+
+    fn x_at(&self, robot: &Robot, seconds: i64) -> i64 {
+        (robot.position.0 + robot.velocity.0 * seconds).rem_euclid(self.width)
+    }
+
+    fn y_at(&self, robot: &Robot, seconds: i64) -> i64 {
+        (robot.position.1 + robot.velocity.1 * seconds).rem_euclid(self.height)
+    }
+}
+
+/// Computes `n` times the variance of the given values, which is enough to
+/// compare spreads across samples of the same size without any division.
+fn variance(values: impl Iterator<Item = i64>) -> i64 {
+    let (n, sum, sum_sq) = values.fold((0, 0, 0), |(n, sum, sum_sq), x| {
+        (n + 1, sum + x, sum_sq + x * x)
+    });
+
+    if n == 0 {
+        0
+    } else {
+        sum_sq - sum * sum / n
+    }
+}
+
+pub fn parse(input: &str) -> Result<Lobby, AocError> {
+    Lobby::parse(input, WIDTH, HEIGHT)
+}
+
+/// Computes the solution to part 1.
+pub fn part1(lobby: &Lobby) -> Answer {
+    lobby.safety_factor(100).into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(lobby: &Lobby) -> Answer {
+    // parsing checked that the lobby's dimensions are coprime
+    Answer::int(lobby.most_clustered_time().unwrap_or_default())
+}
+
+pub const EXAMPLE: &str = r#"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3"#;
+
+aoc_day! {
+    day: 14,
+    parsed: Lobby,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_1() {
+        let lobby = Lobby::parse(EXAMPLE, 11, 7).unwrap();
+        assert_eq!(part1(&lobby), 12);
+    }
+
+    #[test]
+    fn example_render() {
+        let lobby = Lobby::parse(EXAMPLE, 11, 7).unwrap();
+        let expected = "\
+......2..1.
+...........
+1..........
+.11........
+.....1.....
+...12......
+.1....1....
+";
+
+        assert_eq!(lobby.render(100), expected);
+    }
+
+    #[test]
+    fn single_robot_wraps() {
+        let lobby = Lobby::parse("p=2,4 v=2,-3", 11, 7).unwrap();
+        let positions = (0..=5)
+            .map(|t| lobby.positions_at(t).next().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, [(2, 4), (4, 1), (6, 5), (8, 2), (10, 6), (1, 3)]);
+    }
+
+    #[test]
+    fn finds_planted_cluster() {
+        const TARGET: i64 = 6789;
+
+        // scatter robots with pseudorandom velocities so that they all land in
+        // a small square at exactly `TARGET` seconds
+        let mut state = 0x2024u64;
+        let mut next = move |bound: i64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % bound as u64) as i64
+        };
+
+        let robots = (0..300)
+            .map(|_| {
+                let target = (40 + next(20), 40 + next(20));
+                let velocity = (next(201) - 100, next(207) - 103);
+                let position = (
+                    (target.0 - velocity.0 * TARGET).rem_euclid(WIDTH),
+                    (target.1 - velocity.1 * TARGET).rem_euclid(HEIGHT),
+                );
+
+                format!(
+                    "p={},{} v={},{}\n",
+                    position.0, position.1, velocity.0, velocity.1
+                )
+            })
+            .collect::<String>();
+
+        assert_eq!(part2(&parse(&robots).unwrap()), Answer::int(TARGET));
+    }
+
+    #[test]
+    fn invalid_dimensions() {
+        assert_eq!(
+            Lobby::parse(EXAMPLE, 4, 6).unwrap_err().to_string(),
+            "line 1, column 1: a 4x6 lobby's width and height must be positive and coprime"
+        );
+        assert!(Lobby::parse(EXAMPLE, 0, 7).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::{parse, Answer};
+
+/// A `(row, col)` position in a grid.
+pub type Position = (usize, usize);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    #[default]
+    Empty,
+    Wall,
+    Robot,
+    Box,
+    /// The left half of a wide box.
+    BoxLeft,
+    /// The right half of a wide box.
+    BoxRight,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Wall),
+            '@' => Ok(Self::Robot),
+            'O' => Ok(Self::Box),
+            '[' => Ok(Self::BoxLeft),
+            ']' => Ok(Self::BoxRight),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        match value {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+            Tile::Robot => '@',
+            Tile::Box => 'O',
+            Tile::BoxLeft => '[',
+            Tile::BoxRight => ']',
+        }
+    }
+}
+
+impl Tile {
+    /// Returns the direction of the other half of a wide box, if any. Both
+    /// halves of a wide box always move together.
+    fn partner(self) -> Option<Direction> {
+        match self {
+            Self::BoxLeft => Some(Direction::E),
+            Self::BoxRight => Some(Direction::W),
+            _ => None,
+        }
+    }
+}
+
+/// Computes the full set of cells that would move if the object at `from` is
+/// pushed one step in `direction`, including `from` itself. Returns `None` if
+/// the push is blocked by a wall (or the edge of the grid).
+///
+/// The chain is discovered breadth-first, so vertical pushes of wide boxes
+/// fan out into a tree of cells rather than a single line.
+pub fn push_chain(
+    grid: &Grid<Tile>,
+    from: Position,
+    direction: Direction,
+) -> Option<Vec<Position>> {
+    let mut chain = vec![from];
+    let mut i = 0;
+
+    while let Some(&position) = chain.get(i) {
+        i += 1;
+
+        let next = grid.offset(position, direction)?;
+        let tile = grid[next];
+
+        match tile {
+            Tile::Empty => continue,
+            Tile::Wall => return None,
+            _ if chain.contains(&next) => continue,
+            _ => chain.push(next),
+        }
+
+        if let Some(side) = tile.partner() {
+            let partner = grid.offset(next, side)?;
+
+            if !chain.contains(&partner) {
+                chain.push(partner);
+            }
+        }
+    }
+
+    Some(chain)
+}
+
+/// Pushes the object at `from` one step in `direction`, moving every cell in
+/// its push chain at once. Returns the moved cells (at their original
+/// positions), or `None` if the push was blocked and the grid is unchanged.
+pub fn push(grid: &mut Grid<Tile>, from: Position, direction: Direction) -> Option<Vec<Position>> {
+    let chain = push_chain(grid, from, direction)?;
+    let tiles = chain
+        .iter()
+        .map(|&position| core::mem::take(&mut grid[position]))
+        .collect::<Vec<_>>();
+
+    for (&position, tile) in chain.iter().zip(tiles) {
+        // every cell in the chain was checked to have a successor
+        let next = grid.offset(position, direction).unwrap();
+        grid[next] = tile;
+    }
+
+    Some(chain)
+}
+
+/// The warehouse, including the robot and all the boxes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warehouse {
+    grid: Grid<Tile>,
+    robot: Position,
+}
+
+impl FromStr for Warehouse {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::parse(s, |c| Tile::try_from(c).ok())?;
+        let robot = grid
+            .positions(|&tile| tile == Tile::Robot)
+            .next()
+            .ok_or_else(|| AocError::at_end(s, "there is no robot"))?;
+
+        Ok(Self { grid, robot })
+    }
+}
+
+impl Warehouse {
+    /// Returns the doubled-width version of this warehouse used in part 2.
+    pub fn widen(&self) -> Self {
+        let (nrows, ncols) = self.grid.shape();
+        let grid = Grid::from_fn(nrows, 2 * ncols, |(row, col)| {
+            match (self.grid[(row, col / 2)], col % 2) {
+                (Tile::Box, 0) => Tile::BoxLeft,
+                (Tile::Box, _) => Tile::BoxRight,
+                (Tile::Robot, 1) => Tile::Empty,
+                (tile, _) => tile,
+            }
+        });
+
+        Self {
+            grid,
+            robot: (self.robot.0, self.robot.1 * 2),
+        }
+    }
+
+    /// Returns the position of the robot.
+    pub fn robot(&self) -> Position {
+        self.robot
+    }
+
+    /// Attempts to move the robot in `direction`, returning `true` if it moved.
+    pub fn step(&mut self, direction: Direction) -> bool {
+        match push(&mut self.grid, self.robot, direction) {
+            Some(_) => {
+                self.robot = self.grid.offset(self.robot, direction).unwrap();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attempts each move in `moves` in turn, returning how many of them
+    /// actually moved the robot.
+    pub fn run(&mut self, moves: &[Direction]) -> usize {
+        moves
+            .iter()
+            .filter(|&&direction| self.step(direction))
+            .count()
+    }
+
+    /// Returns the GPS coordinates of every box, in row-major order.
+    pub fn gps_coordinates(&self) -> impl Iterator<Item = usize> + use<'_> {
+        self.grid
+            .positions(|tile| matches!(tile, Tile::Box | Tile::BoxLeft))
+            .map(|(row, col)| 100 * row + col)
+    }
+
+    /// Returns the sum of the GPS coordinates of every box.
+    pub fn gps_sum(&self) -> usize {
+        self.gps_coordinates().sum()
+    }
+
+    /// Renders the warehouse in the same format as the input.
+    pub fn render(&self) -> String {
+        let mut output = String::with_capacity((self.grid.cols() + 1) * self.grid.rows());
+
+        for row in self.grid.row_iter() {
+            output.extend(row.iter().map(|&tile| char::from(tile)));
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+/// Parses the warehouse map and the sequence of moves from `input`.
+pub fn parse(input: &str) -> Result<(Warehouse, Vec<Direction>), AocError> {
+    let mut blocks = parse::blocks(input);
+    let missing = |what| AocError::at_end(input, format_args!("missing the {what}"));
+
+    let map = blocks.next().ok_or_else(|| missing("map"))?.text;
+    let warehouse = map
+        .parse::<Warehouse>()
+        .map_err(|error| error.within(input, map))?;
+
+    let moves = blocks.next().ok_or_else(|| missing("moves"))?.text;
+    let moves = moves
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(index, c)| {
+            Direction::from_arrow(c).ok_or_else(|| {
+                AocError::at(
+                    input,
+                    &moves[index..],
+                    format_args!("unexpected move {c:?}"),
+                )
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((warehouse, moves))
+}
+
+/// Computes the solution to part 1.
+pub fn part1((warehouse, moves): &(Warehouse, Vec<Direction>)) -> Answer {
+    let mut warehouse = warehouse.clone();
+    warehouse.run(moves);
+    warehouse.gps_sum().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2((warehouse, moves): &(Warehouse, Vec<Direction>)) -> Answer {
+    let mut warehouse = warehouse.widen();
+    warehouse.run(moves);
+    warehouse.gps_sum().into()
+}
+
+pub const EXAMPLE_SMALL: &str = r#"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<"#;
+
+pub const EXAMPLE_WIDE: &str = r#"#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^"#;
+
+pub const EXAMPLE_LARGE: &str = r#"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
+
+aoc_day! {
+    day: 15,
+    parsed: (Warehouse, Vec<Direction>),
+    example_part_1: EXAMPLE_LARGE => 10092,
+    example_part_2: EXAMPLE_LARGE => 9021,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(part1(&parse(EXAMPLE_SMALL).unwrap()), 2028);
+        assert_eq!(part1(&parse(EXAMPLE_LARGE).unwrap()), 10092);
+    }
+
+    #[test]
+    fn example_wide_render() {
+        let (warehouse, moves) = parse(EXAMPLE_WIDE).unwrap();
+        let mut warehouse = warehouse.widen();
+
+        // exactly one of the moves is blocked
+        assert_eq!(warehouse.run(&moves), moves.len() - 1);
+
+        let expected = "\
+##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############
+";
+
+        assert_eq!(warehouse.render(), expected);
+    }
+
+    #[test]
+    fn vertical_push_fans_out() {
+        let warehouse = "\
+########
+#......#
+#.[][].#
+#..[]..#
+#..@...#
+########"
+            .parse::<Warehouse>()
+            .unwrap();
+
+        let mut grid = warehouse.grid.clone();
+        let chain = push(&mut grid, warehouse.robot(), Direction::N).unwrap();
+
+        assert_eq!(chain.len(), 7);
+        assert_eq!(grid[(1, 2)], Tile::BoxLeft);
+        assert_eq!(grid[(1, 5)], Tile::BoxRight);
+        assert_eq!(grid[(2, 3)], Tile::BoxLeft);
+        assert_eq!(grid[(3, 3)], Tile::Robot);
+    }
+
+    #[test]
+    fn blocked_push_is_atomic() {
+        let warehouse = "\
+########
+#...#..#
+#.[][].#
+#..[]..#
+#..@...#
+########"
+            .parse::<Warehouse>()
+            .unwrap();
+
+        let mut pushed = warehouse.clone();
+        assert!(!pushed.step(Direction::N));
+        assert_eq!(pushed, warehouse);
+    }
+}
@@ -0,0 +1,418 @@
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::hash::HashSet;
+use crate::search::{self, Reached};
+use crate::Answer;
+
+/// The cost of stepping forward one tile.
+const STEP_COST: u32 = 1;
+
+/// The cost of rotating 90 degrees in place.
+const TURN_COST: u32 = 1000;
+
+/// A `(row, col)` position in the maze.
+pub type Position = (usize, usize);
+
+/// Where the reindeer is, and which way it's facing.
+pub type State = (Position, Direction);
+
+/// The reindeer maze, which is surrounded by walls, and whose end can be
+/// reached from its start.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Maze {
+    walls: Grid<bool>,
+    start: Position,
+    end: Position,
+}
+
+/// Why a well-formed [`Maze`] can't be solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidMaze {
+    /// A tile on the edge of the maze isn't a wall.
+    #[error("expected the maze to be surrounded by walls")]
+    Gap,
+    /// There is no path from the start to the end.
+    #[error("the end can't be reached from the start")]
+    Unreachable,
+}
+
+impl DayError for InvalidMaze {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Maze {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+impl FromStr for Maze {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::parse(s, |c| matches!(c, '#' | '.' | 'S' | 'E').then_some(c))?;
+        let find = |tile| {
+            tiles
+                .positions(|&c| c == tile)
+                .next()
+                .ok_or_else(|| AocError::at_end(s, format_args!("there is no {tile:?} tile")))
+        };
+
+        // the tiles are all ASCII, so a tile's column is its offset in its line
+        let error_at = |(row, col): Position, error: InvalidMaze| {
+            let line = s
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .nth(row)
+                .unwrap_or_default();
+
+            AocError::invalid(s, &line[col..], error)
+        };
+
+        let (nrows, ncols) = tiles.shape();
+        let edge =
+            |(row, col): Position| row == 0 || col == 0 || row + 1 == nrows || col + 1 == ncols;
+
+        if let Some(gap) = tiles
+            .positions(|&c| c != '#')
+            .find(|&position| edge(position))
+        {
+            return Err(error_at(gap, InvalidMaze::Gap));
+        }
+
+        let maze = Self {
+            walls: tiles.map(|&c| c == '#'),
+            start: find('S')?,
+            end: find('E')?,
+        };
+
+        let reached = search::bfs(maze.start, |&position| maze.open_neighbours(position));
+        if !reached.contains(&maze.end) {
+            return Err(error_at(maze.end, InvalidMaze::Unreachable));
+        }
+
+        Ok(maze)
+    }
+}
+
+impl Maze {
+    /// Returns an iterator over the open tiles next to `position`.
+    fn open_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.walls.offset(position, direction))
+            .filter(|&next| !self.walls[next])
+    }
+
+    /// Returns the states reachable from `(position, direction)` in one move,
+    /// with the cost of each move.
+    fn moves(&self, (position, direction): State) -> impl Iterator<Item = (State, u32)> {
+        let forward = self
+            .walls
+            .offset(position, direction)
+            .filter(|&forward| !self.walls[forward]);
+
+        [
+            forward.map(|forward| ((forward, direction), STEP_COST)),
+            Some(((position, direction.turn_left()), TURN_COST)),
+            Some(((position, direction.turn_right()), TURN_COST)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Runs Dijkstra's algorithm from the start tile (facing east), recording
+    /// every optimal predecessor of each state.
+    pub fn solve(&self) -> Solution {
+        let reached = search::dijkstra((self.start, Direction::E), |&state| self.moves(state));
+
+        let best_score = Direction::CARDINAL
+            .iter()
+            .filter_map(|&d| reached.distance(&(self.end, d)))
+            .min();
+
+        let ends = Direction::CARDINAL
+            .iter()
+            .map(|&d| (self.end, d))
+            .filter(|end| best_score.is_some() && reached.distance(end) == best_score)
+            .collect();
+
+        Solution {
+            best_score,
+            reached,
+            ends,
+        }
+    }
+}
+
+/// The result of searching a [`Maze`], from which the optimal paths can be
+/// recovered.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    best_score: Option<u32>,
+    reached: Reached<State, u32>,
+    /// The end states that are reached with the best score.
+    ends: Vec<State>,
+}
+
+impl Solution {
+    /// Returns the lowest possible score, or `None` if the end is unreachable.
+    pub fn best_score(&self) -> Option<u32> {
+        self.best_score
+    }
+
+    /// Counts the tiles that lie on at least one optimal path.
+    pub fn best_path_tile_count(&self) -> usize {
+        self.reached
+            .on_shortest_paths(self.ends.iter().copied())
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Returns an iterator over every distinct optimal path from the start to
+    /// the end. The number of such paths can grow exponentially with the size
+    /// of the maze, so the paths are reconstructed lazily.
+    pub fn optimal_paths(&self) -> OptimalPaths<'_> {
+        OptimalPaths {
+            solution: self,
+            roots: self.ends.clone(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+/// A single path through the maze, as the sequence of `(position, direction)`
+/// states visited from the start to the end. Turns appear as consecutive states
+/// with the same position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    pub states: Vec<State>,
+}
+
+impl Path {
+    /// Returns the tiles visited by the path, in order.
+    pub fn cells(&self) -> Vec<Position> {
+        let mut cells = self
+            .states
+            .iter()
+            .map(|&(position, _)| position)
+            .collect::<Vec<_>>();
+
+        cells.dedup();
+        cells
+    }
+
+    /// Returns the number of turns made along the path.
+    pub fn turns(&self) -> usize {
+        self.states
+            .windows(2)
+            .filter(|pair| pair[0].0 == pair[1].0)
+            .count()
+    }
+
+    /// Computes the score of the path.
+    pub fn score(&self) -> u32 {
+        let turns = self.turns() as u32;
+        let steps = (self.states.len() as u32).saturating_sub(1) - turns;
+        turns * TURN_COST + steps * STEP_COST
+    }
+}
+
+/// An iterator over the optimal [`Path`]s of a [`Solution`].
+///
+/// This is a depth-first traversal of the predecessor graph from each optimal
+/// end state back to the start state.
+#[derive(Debug, Clone)]
+pub struct OptimalPaths<'a> {
+    solution: &'a Solution,
+    roots: Vec<State>,
+    /// The current partial path from an end state, with the index of the next
+    /// predecessor to visit from each state.
+    stack: Vec<(State, usize)>,
+}
+
+impl OptimalPaths<'_> {
+    /// Pushes `state` onto the stack, returning the completed path if `state`
+    /// is the start state.
+    fn push(&mut self, state: State) -> Option<Path> {
+        self.stack.push((state, 0));
+
+        if state != *self.solution.reached.start() {
+            return None;
+        }
+
+        let states = self.stack.iter().rev().map(|&(state, _)| state).collect();
+
+        self.stack.pop();
+        Some(Path { states })
+    }
+}
+
+impl Iterator for OptimalPaths<'_> {
+    type Item = Path;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let path = match self.stack.last_mut() {
+                None => {
+                    let root = self.roots.pop()?;
+                    self.push(root)
+                }
+                Some((state, cursor)) => {
+                    match self.solution.reached.predecessors(state).get(*cursor) {
+                        Some(&predecessor) => {
+                            *cursor += 1;
+                            self.push(predecessor)
+                        }
+                        None => {
+                            self.stack.pop();
+                            None
+                        }
+                    }
+                }
+            };
+
+            if path.is_some() {
+                return path;
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maze, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(maze: &Maze) -> Answer {
+    // parsing checked that the end is reachable, so there is always a score
+    maze.solve().best_score().unwrap_or_default().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(maze: &Maze) -> Answer {
+    maze.solve().best_path_tile_count().into()
+}
+
+pub const EXAMPLE_1: &str = r#"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############"#;
+
+pub const EXAMPLE_2: &str = r#"#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################"#;
+
+aoc_day! {
+    day: 16,
+    parsed: Maze,
+    example_part_1: EXAMPLE_1 => 7036,
+    example_part_2: EXAMPLE_1 => 45,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(part1(&parse(EXAMPLE_1).unwrap()), 7036);
+        assert_eq!(part1(&parse(EXAMPLE_2).unwrap()), 11048);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(part2(&parse(EXAMPLE_1).unwrap()), 45);
+        assert_eq!(part2(&parse(EXAMPLE_2).unwrap()), 64);
+    }
+
+    #[test]
+    fn example_optimal_paths() {
+        for example in [EXAMPLE_1, EXAMPLE_2] {
+            let maze = example.parse::<Maze>().unwrap();
+            let solution = maze.solve();
+            let paths = solution.optimal_paths().collect::<Vec<_>>();
+
+            assert!(!paths.is_empty());
+
+            // every path is distinct, optimal, and runs from start to end
+            let distinct = paths.iter().collect::<HashSet<_>>();
+            assert_eq!(distinct.len(), paths.len());
+
+            for path in &paths {
+                assert_eq!(Some(path.score()), solution.best_score());
+                assert_eq!(path.states.first(), Some(&(maze.start, Direction::E)));
+                assert_eq!(path.cells().last(), Some(&maze.end));
+            }
+
+            // the union of the paths is exactly the part 2 tile set
+            let tiles = paths.iter().flat_map(Path::cells).collect::<HashSet<_>>();
+            assert_eq!(tiles.len(), solution.best_path_tile_count());
+        }
+    }
+
+    #[test]
+    fn invalid_mazes() {
+        let error = |maze: &str| maze.parse::<Maze>().unwrap_err().to_string();
+
+        assert_eq!(
+            error("#S.E#"),
+            "line 1, column 2: expected the maze to be surrounded by walls"
+        );
+        assert_eq!(
+            error("####\n#S.#\n#..E\n####"),
+            "line 3, column 4: expected the maze to be surrounded by walls"
+        );
+        assert_eq!(
+            error("#####\n#S#E#\n#####"),
+            "line 2, column 4: the end can't be reached from the start"
+        );
+        assert_eq!(
+            error("###\n#S#\n###"),
+            "line 3, column 4: there is no 'E' tile"
+        );
+    }
+
+    #[test]
+    fn example_1_has_three_optimal_paths() {
+        let maze = EXAMPLE_1.parse::<Maze>().unwrap();
+        assert_eq!(maze.solve().optimal_paths().count(), 3);
+    }
+}
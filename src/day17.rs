@@ -0,0 +1,472 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::{cancel, parse, Answer};
+
+/// The most instructions [`Computer::run`] executes before it gives up on a
+/// program that doesn't halt. Real programs run for about a hundred.
+pub const FUEL: usize = 1 << 20;
+
+/// The three registers of the computer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Registers {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Opcode {
+    Adv = 0,
+    Bxl = 1,
+    Bst = 2,
+    Jnz = 3,
+    Bxc = 4,
+    Out = 5,
+    Bdv = 6,
+    Cdv = 7,
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Adv),
+            1 => Ok(Self::Bxl),
+            2 => Ok(Self::Bst),
+            3 => Ok(Self::Jnz),
+            4 => Ok(Self::Bxc),
+            5 => Ok(Self::Out),
+            6 => Ok(Self::Bdv),
+            7 => Ok(Self::Cdv),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Opcode {
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Adv => "adv",
+            Self::Bxl => "bxl",
+            Self::Bst => "bst",
+            Self::Jnz => "jnz",
+            Self::Bxc => "bxc",
+            Self::Out => "out",
+            Self::Bdv => "bdv",
+            Self::Cdv => "cdv",
+        }
+    }
+
+    /// Returns `true` if this opcode interprets its operand as a combo operand.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Self::Adv | Self::Bst | Self::Out | Self::Bdv | Self::Cdv
+        )
+    }
+}
+
+/// A single decoded instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operand: u8,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mnemonic = self.opcode.mnemonic();
+
+        match self.opcode {
+            // the operand of bxc is read but ignored
+            Opcode::Bxc => write!(f, "{mnemonic}"),
+            op if op.takes_combo() => match self.operand {
+                0..=3 => write!(f, "{mnemonic} {}", self.operand),
+                4 => write!(f, "{mnemonic} a"),
+                5 => write!(f, "{mnemonic} b"),
+                6 => write!(f, "{mnemonic} c"),
+                _ => write!(f, "{mnemonic} <invalid>"),
+            },
+            _ => write!(f, "{mnemonic} {}", self.operand),
+        }
+    }
+}
+
+/// The chronospatial computer: a 3-bit machine with three registers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Computer {
+    pub registers: Registers,
+    program: Vec<u8>,
+    ip: usize,
+    output: Vec<u8>,
+}
+
+/// Why a well-formed program can't be run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidProgram {
+    /// An instruction takes the combo operand 7, which has no meaning.
+    #[error("combo operand 7 is reserved")]
+    ReservedOperand,
+    /// A jump lands between an instruction and its operand.
+    #[error("expected an even jump target")]
+    OddJump,
+    /// The program runs for longer than [`FUEL`] instructions.
+    #[error("program doesn't halt within {FUEL} instructions")]
+    NoHalt,
+}
+
+impl DayError for InvalidProgram {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Program {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+impl FromStr for Computer {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        let mut next = |prefix: &str| -> Result<&str, AocError> {
+            let line = lines.next().ok_or_else(|| {
+                AocError::at_end(s, format_args!("expected a `{}` line", prefix.trim_end()))
+            })?;
+
+            line.strip_prefix(prefix)
+                .ok_or_else(|| AocError::at(s, line, format_args!("expected `{prefix}..`")))
+        };
+
+        let registers = Registers {
+            a: parse::number(s, next("Register A: ")?)?,
+            b: parse::number(s, next("Register B: ")?)?,
+            c: parse::number(s, next("Register C: ")?)?,
+        };
+
+        let line = next("Program: ")?;
+        let raws = line.split(',').collect::<Vec<_>>();
+        let program = raws
+            .iter()
+            .map(|&raw| match parse::number(s, raw)? {
+                n @ 0..8 => Ok(n),
+                _ => Err(AocError::at(s, raw, "expected a 3-bit number")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // every instruction the computer can reach starts at an even address
+        // if no jump leads to an odd one, so these are all it can decode
+        for (i, pair) in program.chunks_exact(2).enumerate() {
+            let operand = raws[2 * i + 1];
+
+            match (Opcode::try_from(pair[0]), pair[1]) {
+                (Ok(opcode), 7) if opcode.takes_combo() => {
+                    return Err(AocError::invalid(
+                        s,
+                        operand,
+                        InvalidProgram::ReservedOperand,
+                    ))
+                }
+                (Ok(Opcode::Jnz), target) if target % 2 == 1 => {
+                    return Err(AocError::invalid(s, operand, InvalidProgram::OddJump))
+                }
+                _ => {}
+            }
+        }
+
+        let computer = Self::new(registers, program);
+        if computer.clone().run().is_none() {
+            return Err(AocError::invalid(s, line, InvalidProgram::NoHalt));
+        }
+
+        Ok(computer)
+    }
+}
+
+impl Computer {
+    pub fn new(registers: Registers, program: Vec<u8>) -> Self {
+        Self {
+            registers,
+            program,
+            ip: 0,
+            output: Vec::new(),
+        }
+    }
+
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.ip
+    }
+
+    /// Returns `true` if the instruction pointer has run off the program.
+    pub fn is_halted(&self) -> bool {
+        self.ip + 1 >= self.program.len()
+    }
+
+    /// Resets the computer to run the program again from the start with the
+    /// given registers.
+    pub fn reset(&mut self, registers: Registers) {
+        self.registers = registers;
+        self.ip = 0;
+        self.output.clear();
+    }
+
+    /// Decodes the instruction at `ip`, if any.
+    pub fn instruction_at(&self, ip: usize) -> Option<Instruction> {
+        let opcode = Opcode::try_from(*self.program.get(ip)?).ok()?;
+        let operand = *self.program.get(ip + 1)?;
+        Some(Instruction { opcode, operand })
+    }
+
+    /// Executes a single instruction, returning it (or `None` if halted).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instruction has the reserved combo operand 7, which
+    /// parsing rejects.
+    pub fn step(&mut self) -> Option<Instruction> {
+        let instruction = self.instruction_at(self.ip)?;
+        let Registers { a, b, c } = self.registers;
+        let literal = instruction.operand as u64;
+        let combo = || match instruction.operand {
+            n @ 0..=3 => n as u64,
+            4 => a,
+            5 => b,
+            6 => c,
+            _ => panic!("invalid combo operand {}", instruction.operand),
+        };
+
+        self.ip += 2;
+
+        match instruction.opcode {
+            Opcode::Adv => self.registers.a = shr(a, combo()),
+            Opcode::Bxl => self.registers.b = b ^ literal,
+            Opcode::Bst => self.registers.b = combo() % 8,
+            Opcode::Jnz if a != 0 => self.ip = literal as usize,
+            Opcode::Jnz => {}
+            Opcode::Bxc => self.registers.b = b ^ c,
+            Opcode::Out => self.output.push((combo() % 8) as u8),
+            Opcode::Bdv => self.registers.b = shr(a, combo()),
+            Opcode::Cdv => self.registers.c = shr(a, combo()),
+        }
+
+        Some(instruction)
+    }
+
+    /// Runs the program until it halts, returning the collected output, or
+    /// `None` if it runs for more than [`FUEL`] instructions or is cancelled.
+    pub fn run(&mut self) -> Option<&[u8]> {
+        for _ in 0..FUEL {
+            if cancel::is_cancelled() {
+                return None;
+            }
+
+            if self.step().is_none() {
+                return Some(&self.output);
+            }
+        }
+
+        None
+    }
+
+    /// Returns a human-readable listing of the program, with one instruction
+    /// per line prefixed by its address.
+    pub fn disassemble(&self) -> String {
+        (0..self.program.len())
+            .step_by(2)
+            .map(|ip| match self.instruction_at(ip) {
+                Some(instruction) => format!("{ip:>2}: {instruction}\n"),
+                None => format!("{ip:>2}: <invalid>\n"),
+            })
+            .collect()
+    }
+
+    /// Finds the lowest initial value of register A for which the program
+    /// outputs a copy of itself.
+    ///
+    /// This assumes the program has the usual shape: a single loop that emits
+    /// one value per iteration and shifts A right by 3 bits before jumping back
+    /// to the start. Then each output depends only on the highest remaining
+    /// octal digits of A, so A can be reconstructed one digit at a time from
+    /// the last output backwards.
+    pub fn find_quine_register(&self) -> Option<u64> {
+        let mut computer = self.clone();
+        let mut candidates = vec![0u64];
+
+        for i in (0..self.program.len()).rev() {
+            let expected = &self.program[i..];
+            let mut next = Vec::new();
+
+            for prefix in candidates {
+                for digit in 0..8 {
+                    let a = (prefix << 3) | digit;
+                    computer.reset(Registers {
+                        a,
+                        ..self.registers
+                    });
+
+                    if computer.run() == Some(expected) {
+                        next.push(a);
+                    }
+                }
+            }
+
+            candidates = next;
+        }
+
+        candidates.into_iter().min()
+    }
+}
+
+/// Shifts `value` right by `shift` bits, saturating to zero for large shifts.
+#[inline(always)]
+fn shr(value: u64, shift: u64) -> u64 {
+    u32::try_from(shift)
+        .ok()
+        .and_then(|shift| value.checked_shr(shift))
+        .unwrap_or(0)
+}
+
+pub fn parse(input: &str) -> Result<Computer, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(computer: &Computer) -> Answer {
+    let mut computer = computer.clone();
+
+    // parsing checked that the program halts, so this only fails if the part
+    // was cancelled
+    computer
+        .run()
+        .unwrap_or_default()
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+        .into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(computer: &Computer) -> Answer {
+    // a program that never outputs a copy of itself has no answer, which is
+    // reported as 0
+    computer.find_quine_register().unwrap_or_default().into()
+}
+
+pub const EXAMPLE_PART1: &str = r#"Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0"#;
+
+pub const EXAMPLE_PART2: &str = r#"Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0"#;
+
+aoc_day! {
+    day: 17,
+    parsed: Computer,
+    example_part_1: EXAMPLE_PART1 => "4,6,3,5,6,3,5,2,1,0",
+    example_part_2: EXAMPLE_PART2 => 117440,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(a: u64, b: u64, c: u64, program: &[u8]) -> Computer {
+        let mut computer = Computer::new(Registers { a, b, c }, program.to_vec());
+        computer.run();
+        computer
+    }
+
+    #[test]
+    fn example_instructions() {
+        assert_eq!(run(0, 0, 9, &[2, 6]).registers.b, 1);
+        assert_eq!(run(10, 0, 0, &[5, 0, 5, 1, 5, 4]).output(), [0, 1, 2]);
+        assert_eq!(run(0, 29, 0, &[1, 7]).registers.b, 26);
+        assert_eq!(run(0, 2024, 43690, &[4, 0]).registers.b, 44354);
+
+        let computer = run(2024, 0, 0, &[0, 1, 5, 4, 3, 0]);
+        assert_eq!(computer.output(), [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(computer.registers.a, 0);
+    }
+
+    #[test]
+    fn single_stepping() {
+        let mut computer = EXAMPLE_PART1.parse::<Computer>().unwrap();
+
+        let first = computer.step().unwrap();
+        assert_eq!(first.opcode, Opcode::Adv);
+        assert_eq!(computer.registers.a, 364);
+        assert_eq!(computer.instruction_pointer(), 2);
+
+        assert_eq!(computer.step().unwrap().opcode, Opcode::Out);
+        assert_eq!(computer.output(), [4]);
+
+        assert_eq!(computer.step().unwrap().opcode, Opcode::Jnz);
+        assert_eq!(computer.instruction_pointer(), 0);
+    }
+
+    #[test]
+    fn example_disassembly() {
+        let computer = EXAMPLE_PART1.parse::<Computer>().unwrap();
+        assert_eq!(computer.disassemble(), " 0: adv 1\n 2: out a\n 4: jnz 0\n");
+
+        // the usual shape of a real input program
+        let program = vec![2, 4, 1, 5, 7, 5, 4, 3, 1, 6, 0, 3, 5, 5, 3, 0];
+        let computer = Computer::new(Registers::default(), program);
+        insta::assert_snapshot!(computer.disassemble());
+    }
+
+    #[test]
+    fn invalid_programs() {
+        let error = |program: &str| {
+            format!("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {program}")
+                .parse::<Computer>()
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("2,7"),
+            "line 5, column 12: combo operand 7 is reserved"
+        );
+        assert_eq!(
+            error("0,1,3,1"),
+            "line 5, column 16: expected an even jump target"
+        );
+        assert_eq!(
+            error("3,0"),
+            "line 5, column 10: program doesn't halt within 1048576 instructions"
+        );
+
+        // literal operands can be 7, and so can the ignored operand of bxc
+        assert!(run(0, 0, 0, &[1, 7, 4, 7]).run().is_some());
+        assert_eq!(run(1, 0, 0, &[3, 0]).run(), None);
+    }
+}
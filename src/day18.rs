@@ -0,0 +1,550 @@
+use alloc::{collections::BinaryHeap, format, vec, vec::Vec};
+use core::cmp::Reverse;
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::geom::Direction;
+use crate::grid::Grid;
+use crate::{parse, search, Answer};
+
+/// The largest coordinate in the real memory space.
+pub const SIZE: usize = 70;
+
+/// The number of bytes that have fallen in part 1.
+pub const KILOBYTE: usize = 1024;
+
+/// A `(x, y)` position in the memory space.
+pub type Position = (usize, usize);
+
+/// The memory space, with the time at which each cell is corrupted.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemorySpace {
+    /// The index of the byte that corrupts each cell, or `usize::MAX` if the
+    /// cell is never corrupted. This is indexed by `(x, y)`.
+    corrupted_at: Grid<usize>,
+    bytes: Vec<Position>,
+}
+
+impl MemorySpace {
+    /// Parses the falling bytes in `input` into a memory space whose
+    /// coordinates range from `0` to `size` inclusive.
+    pub fn parse(input: &str, size: usize) -> Result<Self, AocError> {
+        let bytes = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (x, y) = line
+                    .split_once(',')
+                    .ok_or_else(|| AocError::at(input, line, "expected a position like `5,4`"))?;
+                let position = (parse::number(input, x)?, parse::number(input, y)?);
+
+                match position {
+                    (x, y) if x <= size && y <= size => Ok(position),
+                    _ => Err(AocError::at(
+                        input,
+                        line,
+                        format_args!("position is outside of the {size}x{size} memory space"),
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut corrupted_at = Grid::from_element(size + 1, size + 1, usize::MAX);
+        for (i, &position) in bytes.iter().enumerate().rev() {
+            corrupted_at[position] = i;
+        }
+
+        Ok(Self {
+            corrupted_at,
+            bytes,
+        })
+    }
+
+    /// Returns the falling bytes, in order.
+    pub fn bytes(&self) -> &[Position] {
+        &self.bytes
+    }
+
+    /// Returns an iterator over the cells next to `position`.
+    fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.corrupted_at.offset(position, direction))
+    }
+
+    fn exit(&self) -> Position {
+        (self.corrupted_at.rows() - 1, self.corrupted_at.cols() - 1)
+    }
+
+    /// Returns `true` if `position` is corrupted once `fallen` bytes have fallen.
+    fn is_corrupted(&self, position: Position, fallen: usize) -> bool {
+        self.corrupted_at[position] < fallen
+    }
+
+    /// Finds a shortest path from the top-left corner to the exit after
+    /// `fallen` bytes have fallen, using A* with the Manhattan distance
+    /// heuristic. The returned path includes both endpoints.
+    pub fn shortest_path(&self, fallen: usize) -> Option<Vec<Position>> {
+        let exit = self.exit();
+        let heuristic = |&(x, y): &Position| exit.0.abs_diff(x) + exit.1.abs_diff(y);
+
+        if self.is_corrupted((0, 0), fallen) {
+            return None;
+        }
+
+        let successors = |&position: &Position| {
+            self.neighbours(position)
+                .filter(move |&next| !self.is_corrupted(next, fallen))
+                .map(|next| (next, 1))
+        };
+
+        search::astar((0, 0), successors, heuristic, |&position| position == exit)
+            .map(|(path, _)| path)
+    }
+
+    /// Finds the first byte that cuts off the exit by binary searching over
+    /// the number of fallen bytes.
+    pub fn first_blocking_byte(&self) -> Option<Position> {
+        self.first_blocking_index().map(|i| self.bytes[i])
+    }
+
+    /// Finds the index of the first byte that cuts off the exit, with the
+    /// binary search of [`MemorySpace::first_blocking_byte`].
+    fn first_blocking_index(&self) -> Option<usize> {
+        let (mut low, mut high) = (0, self.bytes.len());
+
+        // invariant: the exit is reachable after `low` bytes but not `high`
+        if self.shortest_path(high).is_some() {
+            return None;
+        }
+
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+
+            match self.shortest_path(mid) {
+                Some(_) => low = mid,
+                None => high = mid,
+            }
+        }
+
+        Some(high - 1)
+    }
+
+    /// Finds the first byte that cuts off the exit by dropping the bytes one
+    /// at a time, and repairing the shortest path after each one with
+    /// Lifelong Planning A* instead of searching again from scratch.
+    pub fn first_blocking_byte_replanning(&self) -> Option<Position> {
+        let mut planner = Planner::new(self);
+        planner.repair()?;
+
+        for &byte in &self.bytes {
+            planner.drop_byte(byte);
+
+            if planner.repair().is_none() {
+                return Some(byte);
+            }
+        }
+
+        None
+    }
+
+    /// Finds the first byte that cuts off the exit by running time backwards:
+    /// starting with every byte fallen, the bytes are lifted in reverse order
+    /// while a union-find tracks which open cells are connected. The first
+    /// byte whose removal joins the start to the exit is the blocking byte.
+    pub fn first_blocking_byte_union_find(&self) -> Option<Position> {
+        let (nrows, ncols) = self.corrupted_at.shape();
+        let fallen = self.bytes.len();
+        let index = |(x, y): Position| x * ncols + y;
+        let mut sets = DisjointSets::new(nrows * ncols);
+
+        let open = |sets: &mut DisjointSets, position: Position, fallen: usize| {
+            for next in self.neighbours(position) {
+                if !self.is_corrupted(next, fallen) {
+                    sets.union(index(position), index(next));
+                }
+            }
+        };
+
+        let connected = |sets: &mut DisjointSets, fallen: usize| {
+            !self.is_corrupted((0, 0), fallen)
+                && !self.is_corrupted(self.exit(), fallen)
+                && sets.find(index((0, 0))) == sets.find(index(self.exit()))
+        };
+
+        for x in 0..nrows {
+            for y in 0..ncols {
+                if !self.is_corrupted((x, y), fallen) {
+                    open(&mut sets, (x, y), fallen);
+                }
+            }
+        }
+
+        if connected(&mut sets, fallen) {
+            return None;
+        }
+
+        for (i, &byte) in self.bytes.iter().enumerate().rev() {
+            // a repeated byte only uncorrupts its cell at its first occurrence
+            if self.corrupted_at[byte] != i {
+                continue;
+            }
+
+            open(&mut sets, byte, i);
+
+            if connected(&mut sets, i) {
+                return Some(byte);
+            }
+        }
+
+        None
+    }
+}
+
+/// An incremental search for the shortest path from the top-left corner to
+/// the exit as bytes fall, with Lifelong Planning A* (LPA*).
+///
+/// Every cell keeps its distance from the start as of the last search (`g`),
+/// and the distance that its open neighbours' `g` values imply (`rhs`). The
+/// two only differ for cells a falling byte has invalidated: the byte's own
+/// cell and its neighbours at first, and then whichever cells the repair
+/// finds depended on them. Repairing only expands those cells, and only while
+/// they could still change the distance to the exit, so the search from
+/// earlier bytes is reused rather than redone.
+struct Planner<'a> {
+    space: &'a MemorySpace,
+    fallen: usize,
+    g: Grid<usize>,
+    rhs: Grid<usize>,
+    /// The cells whose `g` and `rhs` differ, by their key (see
+    /// [`Planner::key`]). Entries go stale when a cell's key changes or it
+    /// becomes consistent again, and are skipped when popped.
+    queue: BinaryHeap<Reverse<(Key, Position)>>,
+}
+
+/// The order in which LPA* expands cells: by the A* priority of their
+/// smaller distance, and then by that distance.
+type Key = (usize, usize);
+
+impl<'a> Planner<'a> {
+    /// Searches for the exit before any bytes have fallen.
+    fn new(space: &'a MemorySpace) -> Self {
+        let mut planner = Self {
+            space,
+            fallen: 0,
+            g: space.corrupted_at.map(|_| usize::MAX),
+            rhs: space.corrupted_at.map(|_| usize::MAX),
+            queue: BinaryHeap::new(),
+        };
+
+        planner.update((0, 0));
+        planner
+    }
+
+    fn key(&self, position: Position) -> Key {
+        let exit = self.space.exit();
+        let distance = self.g[position].min(self.rhs[position]);
+        let heuristic = exit.0.abs_diff(position.0) + exit.1.abs_diff(position.1);
+
+        (distance.saturating_add(heuristic), distance)
+    }
+
+    /// Recomputes the `rhs` of `position`, and queues it if that makes it
+    /// inconsistent.
+    fn update(&mut self, position: Position) {
+        self.rhs[position] = if self.space.is_corrupted(position, self.fallen) {
+            usize::MAX
+        } else if position == (0, 0) {
+            0
+        } else {
+            self.space
+                .neighbours(position)
+                .filter(|&next| !self.space.is_corrupted(next, self.fallen))
+                .map(|next| self.g[next].saturating_add(1))
+                .min()
+                .unwrap_or(usize::MAX)
+        };
+
+        if self.g[position] != self.rhs[position] {
+            self.queue.push(Reverse((self.key(position), position)));
+        }
+    }
+
+    /// Drops the next byte, which lands on `byte`.
+    fn drop_byte(&mut self, byte: Position) {
+        self.fallen += 1;
+        self.update(byte);
+
+        for next in self.space.neighbours(byte) {
+            self.update(next);
+        }
+    }
+
+    /// Expands invalidated cells until the distance to the exit is settled,
+    /// and returns it, or `None` if the exit is cut off.
+    fn repair(&mut self) -> Option<usize> {
+        let exit = self.space.exit();
+
+        while let Some(&Reverse((key, position))) = self.queue.peek() {
+            let current = self.key(position);
+
+            if self.g[position] == self.rhs[position] || key != current {
+                self.queue.pop();
+                continue;
+            }
+
+            if key >= self.key(exit) && self.g[exit] == self.rhs[exit] {
+                break;
+            }
+
+            self.queue.pop();
+
+            if self.g[position] > self.rhs[position] {
+                self.g[position] = self.rhs[position];
+            } else {
+                self.g[position] = usize::MAX;
+                self.update(position);
+            }
+
+            for next in self.space.neighbours(position) {
+                self.update(next);
+            }
+        }
+
+        Some(self.g[exit]).filter(|&distance| distance != usize::MAX)
+    }
+}
+
+/// A union-find over the cells of the memory space, by flattened index.
+struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            // path halving
+            self.parents[element] = self.parents[self.parents[element]];
+            element = self.parents[element];
+        }
+
+        element
+    }
+
+    fn union(&mut self, lhs: usize, rhs: usize) {
+        let (mut lhs, mut rhs) = (self.find(lhs), self.find(rhs));
+        if lhs == rhs {
+            return;
+        }
+
+        if self.sizes[lhs] < self.sizes[rhs] {
+            core::mem::swap(&mut lhs, &mut rhs);
+        }
+
+        self.parents[rhs] = lhs;
+        self.sizes[lhs] += self.sizes[rhs];
+    }
+}
+
+/// Computes the minimum number of steps to the exit of `space` after `fallen`
+/// bytes have fallen.
+pub fn min_steps(space: &MemorySpace, fallen: usize) -> Option<usize> {
+    space.shortest_path(fallen).map(|path| path.len() - 1)
+}
+
+/// Why the falling bytes don't fit the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidMemory {
+    /// A byte cuts off the exit before a kilobyte has fallen, so there's no
+    /// path for part 1.
+    #[error("this byte cuts off the exit before a kilobyte has fallen")]
+    EarlyCutoff,
+    /// No byte cuts off the exit, so there's no answer to part 2.
+    #[error("no byte cuts off the exit")]
+    NeverCutOff,
+}
+
+impl DayError for InvalidMemory {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Memory {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+/// Parses the falling bytes in the real memory space, and checks that both
+/// parts have an answer: the exit is cut off, but not by the first kilobyte.
+pub fn parse(input: &str) -> Result<MemorySpace, AocError> {
+    let space = MemorySpace::parse(input, SIZE)?;
+
+    let Some(byte) = space.first_blocking_index() else {
+        return Err(AocError::invalid(
+            input,
+            &input[input.len()..],
+            InvalidMemory::NeverCutOff,
+        ));
+    };
+
+    if byte < KILOBYTE {
+        let line = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .nth(byte)
+            .unwrap_or_default();
+
+        return Err(AocError::invalid(input, line, InvalidMemory::EarlyCutoff));
+    }
+
+    Ok(space)
+}
+
+/// Computes the solution to part 1.
+pub fn part1(space: &MemorySpace) -> Answer {
+    // parsing checked that the exit is still reachable after a kilobyte
+    min_steps(space, KILOBYTE).unwrap_or_default().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(space: &MemorySpace) -> Answer {
+    // parsing checked that some byte cuts off the exit
+    let (x, y) = space.first_blocking_byte().unwrap_or_default();
+    format!("{x},{y}").into()
+}
+
+pub const EXAMPLE: &str = r#"5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0"#;
+
+aoc_day! {
+    day: 18,
+    parsed: MemorySpace,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_part_1() {
+        let space = MemorySpace::parse(EXAMPLE, 6).unwrap();
+        assert_eq!(min_steps(&space, 12), Some(22));
+    }
+
+    #[test]
+    fn example_part_2() {
+        let space = MemorySpace::parse(EXAMPLE, 6).unwrap();
+        assert_eq!(part2(&space), "6,1");
+    }
+
+    #[test]
+    fn example_replanning_agrees() {
+        let space = MemorySpace::parse(EXAMPLE, 6).unwrap();
+        assert_eq!(space.first_blocking_byte_replanning(), Some((6, 1)));
+        assert_eq!(
+            space.first_blocking_byte_replanning(),
+            space.first_blocking_byte()
+        );
+    }
+
+    #[test]
+    fn repairs_match_fresh_searches() {
+        let mut rng = crate::rng::Rng::new(18);
+
+        for _ in 0..20 {
+            let input = (0..40)
+                .map(|_| format!("{},{}\n", rng.below(8), rng.below(8)))
+                .collect::<String>();
+            let space = MemorySpace::parse(&input, 7).unwrap();
+            let mut planner = Planner::new(&space);
+
+            for (i, &byte) in space.bytes().iter().enumerate() {
+                assert_eq!(planner.repair(), min_steps(&space, i), "{input}");
+                planner.drop_byte(byte);
+            }
+
+            assert_eq!(
+                space.first_blocking_byte_replanning(),
+                space.first_blocking_byte()
+            );
+        }
+    }
+
+    #[test]
+    fn example_union_find_agrees() {
+        let space = MemorySpace::parse(EXAMPLE, 6).unwrap();
+        assert_eq!(space.first_blocking_byte_union_find(), Some((6, 1)));
+
+        // the second 1,0 lands on a cell that is already corrupted
+        let space = MemorySpace::parse("1,0\n0,1\n1,0", 2).unwrap();
+        assert_eq!(space.first_blocking_byte_union_find(), Some((0, 1)));
+        assert_eq!(space.first_blocking_byte(), Some((0, 1)));
+    }
+
+    #[test]
+    fn never_blocked() {
+        let space = MemorySpace::parse("1,1\n2,2", 3).unwrap();
+        assert_eq!(space.first_blocking_byte(), None);
+        assert_eq!(space.first_blocking_byte_replanning(), None);
+        assert_eq!(space.first_blocking_byte_union_find(), None);
+    }
+
+    #[test]
+    fn shortest_path_is_connected() {
+        let space = MemorySpace::parse(EXAMPLE, 6).unwrap();
+        let path = space.shortest_path(12).unwrap();
+
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(6, 6)));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn invalid_memory() {
+        assert_eq!(
+            parse("1,1\n0,1\n1,0").unwrap_err().to_string(),
+            "line 3, column 1: this byte cuts off the exit before a kilobyte has fallen"
+        );
+        assert_eq!(
+            parse("1,1\n").unwrap_err().to_string(),
+            "line 2, column 1: no byte cuts off the exit"
+        );
+    }
+}
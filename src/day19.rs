@@ -0,0 +1,241 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::memo::DenseMemo;
+use crate::par::*;
+use crate::{parse, Answer};
+
+/// The number of distinct stripe colours.
+const COLORS: usize = 5;
+
+/// Maps a stripe colour to its index in a trie node, or `None` if it isn't a
+/// valid colour.
+#[inline(always)]
+fn color_index(stripe: u8) -> Option<usize> {
+    match stripe {
+        b'w' => Some(0),
+        b'u' => Some(1),
+        b'b' => Some(2),
+        b'r' => Some(3),
+        b'g' => Some(4),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Node {
+    /// The child index for each colour, where `0` means "no child" (since the
+    /// root can never be a child).
+    children: [u32; COLORS],
+    terminal: bool,
+}
+
+/// A trie over the available towel patterns.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TowelTrie {
+    nodes: Vec<Node>,
+}
+
+impl Default for TowelTrie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+}
+
+impl TowelTrie {
+    /// Inserts a towel pattern, returning `false` if it contains an invalid
+    /// colour (in which case the trie is unchanged).
+    pub fn insert(&mut self, pattern: &[u8]) -> bool {
+        let Some(indices) = pattern
+            .iter()
+            .map(|&stripe| color_index(stripe))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        let mut node = 0;
+        for color in indices {
+            node = match self.nodes[node].children[color] {
+                0 => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children[color] = child as u32;
+                    child
+                }
+                child => child as usize,
+            };
+        }
+
+        self.nodes[node].terminal = true;
+        true
+    }
+
+    /// Returns an iterator over the lengths of every towel that is a prefix
+    /// of `design`.
+    fn prefix_lengths<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        design
+            .iter()
+            .scan(0usize, |node, &stripe| {
+                let child = self.nodes[*node].children[color_index(stripe)?];
+                *node = child as usize;
+                Some(child).filter(|&child| child != 0)
+            })
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node as usize].terminal)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Counts the number of ways that `design` can be made from the towels.
+    ///
+    /// This is the counting core for both parts.
+    pub fn arrangements(&self, design: &[u8]) -> u64 {
+        self.suffix_arrangements(&mut DenseMemo::new(design.len()), design, 0)
+    }
+
+    /// Counts the arrangements of `design[start..]`, caching them by `start`.
+    fn suffix_arrangements(&self, memo: &mut DenseMemo<u64>, design: &[u8], start: usize) -> u64 {
+        if start == design.len() {
+            return 1;
+        }
+
+        memo.get_or_insert_with(start, |memo| {
+            self.prefix_lengths(&design[start..])
+                .map(|len| self.suffix_arrangements(memo, design, start + len))
+                .sum()
+        })
+    }
+}
+
+/// The available towels and the desired designs.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Onsen {
+    towels: TowelTrie,
+    designs: Vec<Box<[u8]>>,
+}
+
+impl FromStr for Onsen {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut blocks = parse::blocks(s);
+        let missing = |what| AocError::at_end(s, format_args!("missing the {what}"));
+
+        let towels = blocks.next().ok_or_else(|| missing("towels"))?.text;
+        let designs = blocks.next().ok_or_else(|| missing("designs"))?.text;
+
+        let mut trie = TowelTrie::default();
+        for towel in towels.split(',').map(str::trim) {
+            if !trie.insert(towel.as_bytes()) {
+                return Err(AocError::at(
+                    s,
+                    towel,
+                    "towels can only be `w`, `u`, `b`, `r` or `g`",
+                ));
+            }
+        }
+
+        let designs = designs
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.as_bytes().into())
+            .collect();
+
+        Ok(Self {
+            towels: trie,
+            designs,
+        })
+    }
+}
+
+impl Onsen {
+    /// Counts the arrangements of each design, in parallel (unless the days
+    /// are running sequentially). The designs are independent, and the trie is
+    /// only ever read, so it is shared freely between threads.
+    pub fn arrangement_counts(&self) -> Vec<u64> {
+        if is_sequential() {
+            return self.arrangement_counts_sequential();
+        }
+
+        self.designs
+            .par_iter()
+            .map(|design| self.towels.arrangements(design))
+            .collect()
+    }
+
+    /// Counts the arrangements of each design on the current thread.
+    pub fn arrangement_counts_sequential(&self) -> Vec<u64> {
+        self.designs
+            .iter()
+            .map(|design| self.towels.arrangements(design))
+            .collect()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Onsen, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(onsen: &Onsen) -> Answer {
+    onsen
+        .arrangement_counts()
+        .into_iter()
+        .filter(|&ways| ways > 0)
+        .count()
+        .into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(onsen: &Onsen) -> Answer {
+    onsen.arrangement_counts().into_iter().sum::<u64>().into()
+}
+
+pub const EXAMPLE: &str = r#"r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb"#;
+
+aoc_day! {
+    day: 19,
+    parsed: Onsen,
+    example_part_1: EXAMPLE => 6,
+    example_part_2: EXAMPLE => 16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_arrangements() {
+        let onsen = EXAMPLE.parse::<Onsen>().unwrap();
+        assert_eq!(onsen.arrangement_counts(), [2, 1, 4, 6, 0, 1, 2, 0]);
+        assert_eq!(
+            onsen.arrangement_counts(),
+            onsen.arrangement_counts_sequential()
+        );
+    }
+
+    #[test]
+    fn invalid_colors() {
+        let mut trie = TowelTrie::default();
+        assert!(!trie.insert(b"rx"));
+        assert!(trie.insert(b"r"));
+        assert_eq!(trie.arrangements(b"rxr"), 0);
+        assert_eq!(trie.arrangements(b"rrr"), 1);
+    }
+}
@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::geom::{Direction, Vec2};
+use crate::grid::Grid;
+use crate::{search, Answer};
+
+/// The minimum saving counted by both parts of the puzzle.
+pub const MIN_SAVING: usize = 100;
+
+/// A `(row, col)` position on the track.
+pub type Position = (usize, usize);
+
+/// The racetrack, which is assumed to be surrounded by walls.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RaceTrack {
+    walls: Grid<bool>,
+    start: Position,
+    end: Position,
+}
+
+impl FromStr for RaceTrack {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = Grid::parse(s, |c| matches!(c, '#' | '.' | 'S' | 'E').then_some(c))?;
+        let find = |tile| {
+            tiles
+                .positions(|&c| c == tile)
+                .next()
+                .ok_or_else(|| AocError::at_end(s, format_args!("there is no {tile:?} tile")))
+        };
+
+        Ok(Self {
+            walls: tiles.map(|&c| c == '#'),
+            start: find('S')?,
+            end: find('E')?,
+        })
+    }
+}
+
+impl RaceTrack {
+    /// Computes the distance from the start to every reachable track
+    /// position, with `usize::MAX` marking walls and unreachable positions.
+    pub fn distances(&self) -> Grid<usize> {
+        let mut distances = self.walls.map(|_| usize::MAX);
+
+        let reached = search::bfs(self.start, |&position: &Position| {
+            Direction::CARDINAL
+                .into_iter()
+                .filter_map(move |direction| self.walls.offset(position, direction))
+                .filter(|&next| !self.walls[next])
+        });
+
+        for (&position, distance) in reached.iter() {
+            distances[position] = distance;
+        }
+
+        distances
+    }
+
+    /// Returns the length of the honest race from start to end, if possible.
+    pub fn race_length(&self) -> Option<usize> {
+        Some(self.distances()[self.end]).filter(|&d| d != usize::MAX)
+    }
+
+    /// Returns the time saved by every distinct cheat lasting at most
+    /// `max_cheat_len` picoseconds that saves any time at all.
+    ///
+    /// A cheat is identified by its start and end positions, and saves the
+    /// difference between their distances along the track minus the length of
+    /// the cheat itself.
+    pub fn cheat_savings(&self, max_cheat_len: usize) -> Vec<usize> {
+        let distances = self.distances();
+        let (nrows, ncols) = self.walls.shape();
+        let radius = max_cheat_len as isize;
+        let mut savings = Vec::new();
+
+        for row in 0..nrows {
+            for col in 0..ncols {
+                let from = distances[(row, col)];
+                if from == usize::MAX {
+                    continue;
+                }
+
+                // check every position within the diamond of the given radius
+                for dr in -radius..=radius {
+                    let span = radius - dr.abs();
+
+                    for dc in -span..=span {
+                        let Some(target) = distances.offset((row, col), Vec2::new(dr, dc)) else {
+                            continue;
+                        };
+
+                        let Some(&to) = distances.get(target).filter(|&&to| to != usize::MAX)
+                        else {
+                            continue;
+                        };
+
+                        let cheat_len = dr.unsigned_abs() + dc.unsigned_abs();
+                        match to.checked_sub(from + cheat_len) {
+                            Some(saving) if saving > 0 => savings.push(saving),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        savings
+    }
+
+    /// Counts the cheats lasting at most `max_cheat_len` picoseconds that save
+    /// at least `min_saving` picoseconds.
+    pub fn count_cheats(&self, max_cheat_len: usize, min_saving: usize) -> usize {
+        self.cheat_savings(max_cheat_len)
+            .into_iter()
+            .filter(|&saving| saving >= min_saving)
+            .count()
+    }
+}
+
+pub fn parse(input: &str) -> Result<RaceTrack, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(track: &RaceTrack) -> Answer {
+    track.count_cheats(2, MIN_SAVING).into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(track: &RaceTrack) -> Answer {
+    track.count_cheats(20, MIN_SAVING).into()
+}
+
+pub const EXAMPLE: &str = r#"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############"#;
+
+aoc_day! {
+    day: 20,
+    parsed: RaceTrack,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn histogram(max_cheat_len: usize, min_saving: usize) -> BTreeMap<usize, usize> {
+        let track = EXAMPLE.parse::<RaceTrack>().unwrap();
+        let mut histogram = BTreeMap::new();
+
+        for saving in track.cheat_savings(max_cheat_len) {
+            if saving >= min_saving {
+                *histogram.entry(saving).or_default() += 1;
+            }
+        }
+
+        histogram
+    }
+
+    #[test]
+    fn example_race_length() {
+        let track = EXAMPLE.parse::<RaceTrack>().unwrap();
+        assert_eq!(track.race_length(), Some(84));
+    }
+
+    #[test]
+    fn example_short_cheats() {
+        let expected = BTreeMap::from([
+            (2, 14),
+            (4, 14),
+            (6, 2),
+            (8, 4),
+            (10, 2),
+            (12, 3),
+            (20, 1),
+            (36, 1),
+            (38, 1),
+            (40, 1),
+            (64, 1),
+        ]);
+
+        assert_eq!(histogram(2, 1), expected);
+        assert_eq!(parse(EXAMPLE).unwrap().count_cheats(2, 20), 5);
+        assert_eq!(parse(EXAMPLE).unwrap().count_cheats(2, 64), 1);
+    }
+
+    #[test]
+    fn example_long_cheats() {
+        let expected = BTreeMap::from([
+            (50, 32),
+            (52, 31),
+            (54, 29),
+            (56, 39),
+            (58, 25),
+            (60, 23),
+            (62, 20),
+            (64, 19),
+            (66, 12),
+            (68, 14),
+            (70, 12),
+            (72, 22),
+            (74, 4),
+            (76, 3),
+        ]);
+
+        assert_eq!(histogram(20, 50), expected);
+        assert_eq!(parse(EXAMPLE).unwrap().count_cheats(20, 50), 285);
+        assert_eq!(parse(EXAMPLE).unwrap().count_cheats(20, 74), 7);
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::{memo::DenseMemo, Answer};
+
+/// The number of directional keypad robots in part 1.
+pub const SHORT_CHAIN: usize = 2;
+
+/// The number of directional keypad robots in part 2.
+pub const LONG_CHAIN: usize = 25;
+
+/// A `(row, col)` position on a keypad.
+type Position = (usize, usize);
+
+/// The numeric keypad on the door.
+const NUMERIC: Keypad = Keypad {
+    keys: &[
+        ('7', (0, 0)),
+        ('8', (0, 1)),
+        ('9', (0, 2)),
+        ('4', (1, 0)),
+        ('5', (1, 1)),
+        ('6', (1, 2)),
+        ('1', (2, 0)),
+        ('2', (2, 1)),
+        ('3', (2, 2)),
+        ('0', (3, 1)),
+        ('A', (3, 2)),
+    ],
+    gap: (3, 0),
+};
+
+/// The directional keypad used to control a robot.
+const DIRECTIONAL: Keypad = Keypad {
+    keys: &[
+        ('^', (0, 1)),
+        ('A', (0, 2)),
+        ('<', (1, 0)),
+        ('v', (1, 1)),
+        ('>', (1, 2)),
+    ],
+    gap: (0, 0),
+};
+
+/// The index of each key in [`DIRECTIONAL`], for use in cost caches.
+fn directional_index(key: char) -> usize {
+    match key {
+        '^' => 0,
+        'A' => 1,
+        '<' => 2,
+        'v' => 3,
+        '>' => 4,
+        _ => unreachable!("invalid directional key {key:?}"),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Keypad {
+    keys: &'static [(char, Position)],
+    gap: Position,
+}
+
+impl Keypad {
+    fn position(&self, key: char) -> Option<Position> {
+        self.keys
+            .iter()
+            .find_map(|&(k, position)| Some(position).filter(|_| k == key))
+    }
+
+    /// Returns the candidate sequences of directional presses (ending in `A`)
+    /// that move a robot arm from `from` to `to` and then press the key.
+    ///
+    /// Zig-zagging is never optimal, since each direction change costs extra
+    /// presses further up the chain, so we only need to consider moving
+    /// horizontally first or vertically first (whichever avoid the gap).
+    fn candidate_moves(&self, from: Position, to: Position) -> Vec<Vec<char>> {
+        let vertical = match to.0 >= from.0 {
+            true => vec!['v'; to.0 - from.0],
+            false => vec!['^'; from.0 - to.0],
+        };
+
+        let horizontal = match to.1 >= from.1 {
+            true => vec!['>'; to.1 - from.1],
+            false => vec!['<'; from.1 - to.1],
+        };
+
+        let mut candidates = Vec::with_capacity(2);
+
+        // horizontal first passes through the corner (from.0, to.1)
+        if (from.0, to.1) != self.gap {
+            candidates.push([horizontal.as_slice(), &vertical, &['A']].concat());
+        }
+
+        // vertical first passes through the corner (to.0, from.1)
+        if (to.0, from.1) != self.gap {
+            candidates.push([vertical.as_slice(), &horizontal, &['A']].concat());
+        }
+
+        candidates
+    }
+}
+
+/// A cache of [`press_cost`] for each robot in a chain, or `None` where it
+/// overflows a `u128`.
+type Costs = DenseMemo<Option<u128>>;
+
+/// Creates an empty cache for a chain of `depth` directional keypad robots.
+fn chain_costs(depth: usize) -> Costs {
+    DenseMemo::new(depth * 25)
+}
+
+/// Computes the number of human button presses needed to make the robot at
+/// `depth` in the chain move its arm from the directional key `from` to `to`
+/// and press it.
+///
+/// At depth zero the human presses each key directly, which always costs
+/// exactly one press.
+fn press_cost(costs: &mut Costs, from: char, to: char, depth: usize) -> Option<u128> {
+    let Some(below) = depth.checked_sub(1) else {
+        return Some(1);
+    };
+
+    let key = (below * 5 + directional_index(from)) * 5 + directional_index(to);
+    costs.get_or_insert_with(key, |costs| {
+        let candidates =
+            DIRECTIONAL.candidate_moves(DIRECTIONAL.position(from)?, DIRECTIONAL.position(to)?);
+        cheapest(costs, &candidates, below)
+    })
+}
+
+/// Computes the cost of pressing every key in `sequence` (starting from `A`)
+/// on the keypad that the robot at `depth` operates.
+fn sequence_cost(costs: &mut Costs, sequence: &[char], depth: usize) -> Option<u128> {
+    let mut previous = 'A';
+    let mut total = 0u128;
+
+    for &key in sequence {
+        total = total.checked_add(press_cost(costs, previous, key, depth)?)?;
+        previous = key;
+    }
+
+    Some(total)
+}
+
+/// Returns the cheapest cost among `candidates` at `depth`, or `None` if every
+/// candidate overflows.
+fn cheapest(costs: &mut Costs, candidates: &[Vec<char>], depth: usize) -> Option<u128> {
+    candidates
+        .iter()
+        .filter_map(|candidate| sequence_cost(costs, candidate, depth))
+        .min()
+}
+
+/// Computes the fewest human button presses needed to type `code` on the
+/// numeric keypad through a chain of `depth` robots.
+fn code_presses(code: &str, costs: &mut Costs, depth: usize) -> Option<u128> {
+    let mut previous = NUMERIC.position('A')?;
+    let mut total = 0u128;
+
+    for key in code.chars() {
+        let next = NUMERIC.position(key)?;
+        let candidates = NUMERIC.candidate_moves(previous, next);
+        total = total.checked_add(cheapest(costs, &candidates, depth)?)?;
+        previous = next;
+    }
+
+    Some(total)
+}
+
+/// Computes the fewest human button presses needed to type `code` through a
+/// chain of `chain_depth` directional keypad robots.
+pub fn shortest_sequence_length(code: &str, chain_depth: usize) -> Option<u128> {
+    code_presses(code, &mut chain_costs(chain_depth), chain_depth)
+}
+
+/// Computes the sum of the complexities of `codes`, when typed through a chain
+/// of `chain_depth` directional keypad robots. Returns `None` if the result (or
+/// any intermediate cost) would overflow a `u128`.
+pub fn complexity_sum(codes: &[String], chain_depth: usize) -> Option<u128> {
+    let mut costs = chain_costs(chain_depth);
+
+    codes.iter().try_fold(0u128, |total, code| {
+        total.checked_add(complexity(code, &mut costs, chain_depth)?)
+    })
+}
+
+/// Computes the complexity of `code` when typed through a chain of `depth`
+/// directional keypad robots, or `None` if it would overflow a `u128`.
+fn complexity(code: &str, costs: &mut Costs, depth: usize) -> Option<u128> {
+    let numeric = code.trim_end_matches('A').parse::<u128>().ok()?;
+    code_presses(code, costs, depth)?.checked_mul(numeric)
+}
+
+/// Why a list of well-formed codes can't be solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidCodes {
+    /// The complexities up to this code add up to more than an answer can
+    /// hold.
+    #[error("the complexities add up to more than 64 bits")]
+    TooComplex,
+}
+
+impl DayError for InvalidCodes {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Codes {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+/// Parses the codes, and checks that both parts' answers fit in a `u64`.
+pub fn parse(input: &str) -> Result<Vec<String>, AocError> {
+    let lines = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    let codes = lines
+        .iter()
+        .map(|&code| {
+            let digits = code.strip_suffix('A').unwrap_or_default();
+
+            match !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                true => Ok(code.to_string()),
+                false => Err(AocError::at(input, code, "expected a code like `029A`")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // more robots only ever need more presses, so if part 2's answer fits
+    // then so does part 1's
+    let mut costs = chain_costs(LONG_CHAIN);
+    let mut total = 0u64;
+
+    for (code, line) in codes.iter().zip(lines) {
+        total = complexity(code, &mut costs, LONG_CHAIN)
+            .and_then(|complexity| u64::try_from(complexity).ok())
+            .and_then(|complexity| total.checked_add(complexity))
+            .ok_or_else(|| AocError::invalid(input, line, InvalidCodes::TooComplex))?;
+    }
+
+    Ok(codes)
+}
+
+/// Computes the solution to part 1.
+pub fn part1(codes: &[String]) -> Answer {
+    // parsing checked that the sum fits
+    Answer::int(complexity_sum(codes, SHORT_CHAIN).unwrap_or_default())
+}
+
+/// Computes the solution to part 2.
+pub fn part2(codes: &[String]) -> Answer {
+    Answer::int(complexity_sum(codes, LONG_CHAIN).unwrap_or_default())
+}
+
+pub const EXAMPLE: &str = r#"029A
+980A
+179A
+456A
+379A"#;
+
+aoc_day! {
+    day: 21,
+    parsed: Vec<String>,
+    example_part_1: EXAMPLE => 126384,
+    example_part_2: EXAMPLE => 154115708116294_u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_sequence_lengths() {
+        let lengths = EXAMPLE
+            .lines()
+            .map(|code| shortest_sequence_length(code, SHORT_CHAIN).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(lengths, [68, 60, 68, 64, 64]);
+    }
+
+    #[test]
+    fn zero_and_one_directional_robots() {
+        // at depth 0 the human types on a directional keypad that drives the
+        // numeric keypad robot, so each digit costs its moves plus an `A`
+        // press, and "029A" takes `<A^A>^^AvvvA`. depth 1 puts one more
+        // directional keypad robot between them
+        assert_eq!(shortest_sequence_length("029A", 0), Some(12));
+        assert_eq!(shortest_sequence_length("029A", 1), Some(28));
+    }
+
+    #[test]
+    fn deep_chains_overflow_cleanly() {
+        assert!(complexity_sum(&parse(EXAMPLE).unwrap(), 60).is_some());
+        assert_eq!(complexity_sum(&parse(EXAMPLE).unwrap(), 200), None);
+    }
+
+    #[test]
+    fn oversized_answers() {
+        assert!(parse("999999A").is_ok());
+        assert_eq!(
+            parse("029A\n99999999999999999999A")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: the complexities add up to more than 64 bits"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use alloc::{vec, vec::Vec};
+
+use crate::cancel;
+use crate::error::AocError;
+use crate::{parse, Answer};
+
+/// The number of new secrets each buyer generates in a day.
+pub const SECRETS_PER_DAY: usize = 2000;
+
+/// The number of secrets evolved in lockstep by the batched implementation.
+#[cfg(feature = "simd")]
+pub const LANES: usize = 16;
+
+/// Secrets are always pruned to 24 bits.
+const PRUNE_MASK: u32 = (1 << 24) - 1;
+
+/// The number of distinct price changes (from -9 to 9 inclusive).
+const CHANGES: usize = 19;
+
+/// The number of distinct sequences of four price changes.
+const SEQUENCES: usize = CHANGES.pow(4);
+
+/// Computes the next secret number in the sequence.
+///
+/// Each step of the puzzle's description is a multiplication or division by a
+/// power of two followed by a mix (XOR) and prune (modulo 2^24), so the whole
+/// thing reduces to shifts, XORs, and masks.
+#[inline(always)]
+pub fn next_secret(mut secret: u32) -> u32 {
+    secret = ((secret << 6) ^ secret) & PRUNE_MASK;
+    secret = (secret >> 5) ^ secret;
+    ((secret << 11) ^ secret) & PRUNE_MASK
+}
+
+/// Computes the `n`th secret number after `secret`.
+pub fn nth_secret(secret: u32, n: usize) -> u32 {
+    (0..n).fold(secret, |secret, _| next_secret(secret))
+}
+
+/// Evolves every lane of `secrets` by `n` steps in lockstep.
+///
+/// The body is written as independent per-lane operations on a fixed-size
+/// array, which LLVM reliably lowers to vector instructions without needing
+/// the nightly-only `core::simd`.
+#[cfg(feature = "simd")]
+#[inline]
+pub fn evolve_lanes(secrets: &mut [u32; LANES], n: usize) {
+    for _ in 0..n {
+        for secret in secrets.iter_mut() {
+            *secret = ((*secret << 6) ^ *secret) & PRUNE_MASK;
+        }
+
+        for secret in secrets.iter_mut() {
+            *secret ^= *secret >> 5;
+        }
+
+        for secret in secrets.iter_mut() {
+            *secret = ((*secret << 11) ^ *secret) & PRUNE_MASK;
+        }
+    }
+}
+
+/// Sums the `n`th secret of each buyer, one buyer at a time.
+pub fn sum_of_nth_secrets_scalar(secrets: &[u32], n: usize) -> u64 {
+    secrets
+        .iter()
+        .map(|&secret| nth_secret(secret, n) as u64)
+        .sum()
+}
+
+/// Sums the `n`th secret of each buyer, evolving [`LANES`] buyers at a time.
+#[cfg(feature = "simd")]
+pub fn sum_of_nth_secrets_batched(secrets: &[u32], n: usize) -> u64 {
+    let chunks = secrets.chunks_exact(LANES);
+    let remainder = sum_of_nth_secrets_scalar(chunks.remainder(), n);
+
+    chunks
+        .map(|chunk| {
+            let mut lanes: [u32; LANES] = chunk.try_into().unwrap();
+            evolve_lanes(&mut lanes, n);
+            lanes.iter().map(|&secret| secret as u64).sum::<u64>()
+        })
+        .sum::<u64>()
+        + remainder
+}
+
+/// Returns the index of a sequence of four price changes in a flat table of
+/// size [`SEQUENCES`], treating the changes as digits in base 19.
+#[inline(always)]
+fn sequence_index(changes: [i8; 4]) -> usize {
+    changes
+        .iter()
+        .fold(0, |index, &change| index * CHANGES + (change + 9) as usize)
+}
+
+/// Computes the total number of bananas each sequence of four price changes
+/// would earn across all buyers, indexed by [`sequence_index`].
+///
+/// The totals live in one flat array rather than a map keyed by tuples, and
+/// each buyer marks the sequences it has already sold on with its own index so
+/// that only the first occurrence per buyer counts.
+fn banana_totals(secrets: &[u32]) -> Vec<u32> {
+    let mut totals = vec![0u32; SEQUENCES];
+    let mut last_seen = vec![usize::MAX; SEQUENCES];
+
+    for (buyer, &secret) in secrets.iter().enumerate() {
+        if cancel::is_cancelled() {
+            break;
+        }
+
+        let mut secret = secret;
+        let mut price = (secret % 10) as i8;
+        let mut changes = [0i8; 4];
+
+        for i in 0..SECRETS_PER_DAY {
+            secret = next_secret(secret);
+            let next_price = (secret % 10) as i8;
+
+            changes.rotate_left(1);
+            changes[3] = next_price - price;
+            price = next_price;
+
+            if i >= 3 {
+                let index = sequence_index(changes);
+
+                if last_seen[index] != buyer {
+                    last_seen[index] = buyer;
+                    totals[index] += price as u32;
+                }
+            }
+        }
+    }
+
+    totals
+}
+
+/// Parses the initial secret number of each buyer.
+pub fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+    input
+        .split_whitespace()
+        .map(|secret| parse::number(input, secret))
+        .collect()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(secrets: &[u32]) -> Answer {
+    #[cfg(feature = "simd")]
+    return sum_of_nth_secrets_batched(secrets, SECRETS_PER_DAY).into();
+
+    #[cfg(not(feature = "simd"))]
+    return sum_of_nth_secrets_scalar(secrets, SECRETS_PER_DAY).into();
+}
+
+/// Computes the solution to part 2.
+pub fn part2(secrets: &[u32]) -> Answer {
+    banana_totals(secrets).into_iter().max().unwrap_or(0).into()
+}
+
+pub const EXAMPLE_PART1: &str = r#"1
+10
+100
+2024"#;
+
+pub const EXAMPLE_PART2: &str = r#"1
+2
+3
+2024"#;
+
+aoc_day! {
+    day: 22,
+    parsed: Vec<u32>,
+    example_part_1: EXAMPLE_PART1 => 37327623,
+    example_part_2: EXAMPLE_PART2 => 23,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_secret_sequence() {
+        let secrets = core::iter::successors(Some(123), |&s| Some(next_secret(s)))
+            .skip(1)
+            .take(10)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            secrets,
+            [
+                15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+                5908254
+            ]
+        );
+    }
+
+    #[test]
+    fn example_banana_totals() {
+        assert_eq!(
+            banana_totals(&[1, 2, 3, 2024])[sequence_index([-2, 1, -1, 3])],
+            23
+        );
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn batched_matches_scalar() {
+        // enough buyers to fill a few batches and leave a remainder
+        let secrets = (0..(3 * LANES as u32 + 5))
+            .map(|i| i * 7919 + 1)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sum_of_nth_secrets_batched(&secrets, SECRETS_PER_DAY),
+            sum_of_nth_secrets_scalar(&secrets, SECRETS_PER_DAY)
+        );
+    }
+}
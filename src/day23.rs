@@ -0,0 +1,153 @@
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+
+use crate::error::AocError;
+use crate::graph::{self, Graph};
+use crate::Answer;
+
+/// The number of possible two-letter computer names.
+const NAMES: usize = 26 * 26;
+
+/// The network of computers at the LAN party.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Network {
+    graph: Graph,
+    /// The name of each vertex in the graph.
+    names: Vec<[u8; 2]>,
+}
+
+impl FromStr for Network {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ids = [usize::MAX; NAMES];
+        let mut names = Vec::new();
+        let mut edges = Vec::new();
+
+        let mut intern = |name: &str| -> Result<usize, AocError> {
+            let &[a @ b'a'..=b'z', b @ b'a'..=b'z'] = name.as_bytes() else {
+                return Err(AocError::at(
+                    s,
+                    name,
+                    "expected a name of two lowercase letters",
+                ));
+            };
+
+            let key = (a - b'a') as usize * 26 + (b - b'a') as usize;
+            if ids[key] == usize::MAX {
+                ids[key] = names.len();
+                names.push([a, b]);
+            }
+
+            Ok(ids[key])
+        };
+
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| AocError::at(s, line, "expected a connection like `kh-tc`"))?;
+            edges.push((intern(a)?, intern(b)?));
+        }
+
+        let mut graph = Graph::new(names.len());
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+
+        Ok(Self { graph, names })
+    }
+}
+
+impl Network {
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns the name of the computer with the given vertex index.
+    pub fn name(&self, vertex: usize) -> &str {
+        // names are always validated as lowercase ASCII during parsing
+        core::str::from_utf8(&self.names[vertex]).unwrap()
+    }
+
+    /// Returns the names of the computers in the largest fully-connected set,
+    /// sorted alphabetically and joined with commas.
+    pub fn password(&self) -> String {
+        let mut names = graph::max_clique(&self.graph)
+            .into_iter()
+            .map(|vertex| self.name(vertex))
+            .collect::<Vec<_>>();
+
+        names.sort_unstable();
+        names.join(",")
+    }
+}
+
+pub fn parse(input: &str) -> Result<Network, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(network: &Network) -> Answer {
+    graph::triangles(network.graph())
+        .into_iter()
+        .filter(|triangle| triangle.iter().any(|&v| network.name(v).starts_with('t')))
+        .count()
+        .into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(network: &Network) -> Answer {
+    network.password().into()
+}
+
+pub const EXAMPLE: &str = r#"kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn"#;
+
+aoc_day! {
+    day: 23,
+    parsed: Network,
+    example_part_1: EXAMPLE => 7,
+    example_part_2: EXAMPLE => "co,de,ka,ta",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_triangles() {
+        let network = EXAMPLE.parse::<Network>().unwrap();
+        assert_eq!(graph::triangles(network.graph()).len(), 12);
+    }
+}
@@ -0,0 +1,548 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Write, str::FromStr};
+
+use thiserror::Error;
+
+use crate::error::{AocError, DayError};
+use crate::hash::{HashMap, HashSet};
+use crate::{parse, Answer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+}
+
+impl FromStr for GateKind {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(Self::And),
+            "OR" => Ok(Self::Or),
+            "XOR" => Ok(Self::Xor),
+            _ => Err(AocError::at(s, s, format_args!("unknown gate {s:?}"))),
+        }
+    }
+}
+
+impl GateKind {
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            GateKind::And => a & b,
+            GateKind::Or => a | b,
+            GateKind::Xor => a ^ b,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GateKind::And => "AND",
+            GateKind::Or => "OR",
+            GateKind::Xor => "XOR",
+        }
+    }
+
+    /// The Graphviz node shape and fill color used for this kind of gate.
+    fn dot_style(self) -> (&'static str, &'static str) {
+        match self {
+            GateKind::And => ("box", "lightblue"),
+            GateKind::Or => ("ellipse", "palegreen"),
+            GateKind::Xor => ("diamond", "gold"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: [String; 2],
+    pub output: String,
+}
+
+impl Gate {
+    /// Returns `true` if this gate reads directly from an `x` and a `y` wire.
+    fn reads_inputs(&self) -> bool {
+        self.inputs
+            .iter()
+            .all(|wire| wire.starts_with('x') || wire.starts_with('y'))
+    }
+
+    /// Returns `true` if this gate reads the least significant input bits.
+    fn reads_first_bits(&self) -> bool {
+        self.inputs
+            .iter()
+            .all(|wire| wire == "x00" || wire == "y00")
+    }
+}
+
+/// Why a well-formed circuit can't be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidCircuit {
+    /// A wire has both an initial value and a gate driving it, or more than
+    /// one gate driving it.
+    #[error("wire `{0}` is driven more than once")]
+    DrivenTwice(String),
+    /// A gate reads a wire that nothing drives.
+    #[error("wire `{0}` is never driven")]
+    Undriven(String),
+    /// A gate depends on its own output.
+    #[error("this gate is part of a cycle")]
+    Cycle,
+    /// There are more `z` wires than bits in the answer.
+    #[error("there are more than 64 `z` wires")]
+    TooManyOutputs,
+}
+
+impl DayError for InvalidCircuit {
+    fn at(self, line: usize, column: usize) -> AocError {
+        AocError::Circuit {
+            line,
+            column,
+            error: self,
+        }
+    }
+}
+
+/// A circuit of boolean gates, together with the initial values of its input
+/// wires, where every wire has a value.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circuit {
+    initial: BTreeMap<String, bool>,
+    gates: Vec<Gate>,
+}
+
+impl FromStr for Circuit {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut blocks = parse::blocks(s);
+        let missing = |what| AocError::at_end(s, format_args!("missing the {what}"));
+
+        let initial = blocks.next().ok_or_else(|| missing("initial values"))?.text;
+        let gates = blocks.next().ok_or_else(|| missing("gates"))?.text;
+
+        let initial_lines = initial.lines().map(str::trim).collect::<Vec<_>>();
+        let gate_lines = gates
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        let initial = initial_lines
+            .iter()
+            .map(|&line| {
+                let malformed = || AocError::at(s, line, "expected a wire like `x00: 1`");
+                let (wire, value) = line.split_once(": ").ok_or_else(malformed)?;
+
+                match value {
+                    "0" => Ok((wire.to_string(), false)),
+                    "1" => Ok((wire.to_string(), true)),
+                    _ => Err(malformed()),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let gates = gate_lines
+            .iter()
+            .map(|&line| {
+                let malformed =
+                    || AocError::at(s, line, "expected a gate like `x00 AND y00 -> z00`");
+                let (expr, output) = line.split_once(" -> ").ok_or_else(malformed)?;
+
+                let mut parts = expr.split_whitespace();
+                let (Some(a), Some(kind), Some(b), None) =
+                    (parts.next(), parts.next(), parts.next(), parts.next())
+                else {
+                    return Err(malformed());
+                };
+
+                Ok(Gate {
+                    kind: kind
+                        .parse()
+                        .map_err(|error: AocError| error.within(s, kind))?,
+                    inputs: [a.to_string(), b.to_string()],
+                    output: output.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // every wire needs exactly one value for the circuit to have an output,
+        // and the answer has room for 64 `z` wires
+        {
+            let mut driven = HashSet::default();
+            let mut z_wires = 0;
+            let outputs = initial.iter().map(|(wire, _)| wire).zip(&initial_lines);
+            let outputs = outputs.chain(gates.iter().map(|gate| &gate.output).zip(&gate_lines));
+
+            for (wire, &line) in outputs {
+                if !driven.insert(wire.as_str()) {
+                    let error = InvalidCircuit::DrivenTwice(wire.clone());
+                    return Err(AocError::invalid(s, line, error));
+                }
+
+                z_wires += usize::from(wire.starts_with('z'));
+                if z_wires > 64 {
+                    return Err(AocError::invalid(s, line, InvalidCircuit::TooManyOutputs));
+                }
+            }
+
+            for (gate, &line) in gates.iter().zip(&gate_lines) {
+                let undriven = gate
+                    .inputs
+                    .iter()
+                    .find(|wire| !driven.contains(wire.as_str()));
+
+                if let Some(wire) = undriven {
+                    let error = InvalidCircuit::Undriven(wire.clone());
+                    return Err(AocError::invalid(s, line, error));
+                }
+            }
+        }
+
+        let circuit = Self {
+            initial: initial.into_iter().collect(),
+            gates,
+        };
+
+        if let Some(gate) = circuit.gate_in_cycle() {
+            return Err(AocError::invalid(
+                s,
+                gate_lines[gate],
+                InvalidCircuit::Cycle,
+            ));
+        }
+
+        Ok(circuit)
+    }
+}
+
+impl Circuit {
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Computes the value of every wire in the circuit, or returns `None` if
+    /// some gate can never be evaluated (because of a cycle or an undriven
+    /// input wire).
+    pub fn evaluate(&self) -> Option<HashMap<&str, bool>> {
+        let (values, pending) = self.settle();
+        pending.is_empty().then_some(values)
+    }
+
+    /// Evaluates every gate that can be, returning the values of the wires
+    /// and the indices of the gates that are left.
+    fn settle(&self) -> (HashMap<&str, bool>, Vec<usize>) {
+        let mut values = self
+            .initial
+            .iter()
+            .map(|(wire, &value)| (wire.as_str(), value))
+            .collect::<HashMap<_, _>>();
+
+        let mut pending = (0..self.gates.len()).collect::<Vec<_>>();
+
+        loop {
+            let before = pending.len();
+
+            pending.retain(|&index| {
+                let gate = &self.gates[index];
+                let [a, b] = &gate.inputs;
+                match (values.get(a.as_str()), values.get(b.as_str())) {
+                    (Some(&a), Some(&b)) => {
+                        values.insert(&gate.output, gate.kind.apply(a, b));
+                        false
+                    }
+                    _ => true,
+                }
+            });
+
+            if pending.is_empty() || pending.len() == before {
+                return (values, pending);
+            }
+        }
+    }
+
+    /// Returns the index of a gate on a cycle, if any gate can't be evaluated
+    /// even though every wire is driven.
+    fn gate_in_cycle(&self) -> Option<usize> {
+        let (values, pending) = self.settle();
+        let drivers = pending
+            .iter()
+            .map(|&index| (self.gates[index].output.as_str(), index))
+            .collect::<HashMap<_, _>>();
+
+        // every gate left has an input that another gate left drives, so
+        // following those back for as many steps as there are gates left must
+        // end up on a cycle
+        let mut gate = *pending.first()?;
+        for _ in 0..pending.len() {
+            gate = self.gates[gate]
+                .inputs
+                .iter()
+                .filter(|wire| !values.contains_key(wire.as_str()))
+                .find_map(|wire| drivers.get(wire.as_str()).copied())?;
+        }
+
+        Some(gate)
+    }
+
+    /// Returns the number formed by the `z` wires, with `z00` as the least
+    /// significant bit.
+    pub fn output(&self) -> Option<u64> {
+        let values = self.evaluate()?;
+
+        let mut z_wires = values
+            .iter()
+            .filter(|(wire, _)| wire.starts_with('z'))
+            .collect::<Vec<_>>();
+
+        z_wires.sort_unstable_by_key(|(wire, _)| core::cmp::Reverse(*wire));
+
+        Some(
+            z_wires
+                .into_iter()
+                .fold(0, |acc, (_, &bit)| (acc << 1) | bit as u64),
+        )
+    }
+
+    /// Returns the name of the most significant output wire.
+    fn last_output(&self) -> Option<&str> {
+        self.gates
+            .iter()
+            .map(|gate| gate.output.as_str())
+            .filter(|wire| wire.starts_with('z'))
+            .max()
+    }
+
+    /// Returns the wires that violate the structure of a ripple-carry adder,
+    /// in sorted order.
+    ///
+    /// In a correct adder every `z` wire (except the final carry) is driven by
+    /// an XOR gate, the only XOR gates not reading from the inputs drive `z`
+    /// wires, the XOR of each pair of input bits feeds another XOR gate, and
+    /// every AND gate feeds an OR gate (the half adder on the first bit being
+    /// the exception to the last two rules). Any gate breaking one of these
+    /// rules must have had its output swapped.
+    pub fn suspicious_wires(&self) -> Vec<&str> {
+        let last_output = self.last_output();
+
+        let feeds = |wire: &str, kind: GateKind| {
+            self.gates
+                .iter()
+                .any(|gate| gate.kind == kind && gate.inputs.iter().any(|input| input == wire))
+        };
+
+        self.gates
+            .iter()
+            .filter(|gate| {
+                let output = gate.output.as_str();
+                let drives_z = output.starts_with('z');
+
+                match gate.kind {
+                    _ if drives_z && Some(output) == last_output => gate.kind != GateKind::Or,
+                    GateKind::And | GateKind::Or if drives_z => true,
+                    GateKind::Xor if !gate.reads_inputs() => !drives_z,
+                    GateKind::Xor if !gate.reads_first_bits() => {
+                        drives_z || !feeds(output, GateKind::Xor)
+                    }
+                    GateKind::And if !gate.reads_first_bits() => !feeds(output, GateKind::Or),
+                    _ => false,
+                }
+            })
+            .map(|gate| gate.output.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Renders the circuit in the Graphviz DOT language.
+    ///
+    /// Each gate is drawn as its own node (shaped and colored by its kind),
+    /// with edges running from its input wires and to its output wire. Wires
+    /// reported by [`Circuit::suspicious_wires`] are highlighted in red.
+    pub fn to_dot(&self) -> String {
+        let suspicious = self.suspicious_wires();
+
+        let mut wires = BTreeSet::new();
+        for gate in &self.gates {
+            wires.extend(gate.inputs.iter().map(String::as_str));
+            wires.insert(gate.output.as_str());
+        }
+        wires.extend(self.initial.keys().map(String::as_str));
+
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+
+        for wire in wires {
+            let style = match wire.chars().next() {
+                _ if suspicious.contains(&wire) => ", style=filled, fillcolor=red",
+                Some('x' | 'y') => ", style=filled, fillcolor=lightgrey",
+                Some('z') => ", style=filled, fillcolor=lightpink",
+                _ => "",
+            };
+
+            writeln!(dot, "    {wire} [shape=plaintext{style}];").unwrap();
+        }
+
+        for (index, gate) in self.gates.iter().enumerate() {
+            let (shape, color) = gate.kind.dot_style();
+            let [a, b] = &gate.inputs;
+            let output = &gate.output;
+
+            writeln!(
+                dot,
+                "    gate{index} [label={}, shape={shape}, style=filled, fillcolor={color}];",
+                gate.kind.name()
+            )
+            .unwrap();
+            writeln!(dot, "    {a} -> gate{index};").unwrap();
+            writeln!(dot, "    {b} -> gate{index};").unwrap();
+
+            match suspicious.contains(&output.as_str()) {
+                true => writeln!(dot, "    gate{index} -> {output} [color=red, penwidth=2];"),
+                false => writeln!(dot, "    gate{index} -> {output};"),
+            }
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub fn parse(input: &str) -> Result<Circuit, AocError> {
+    input.parse()
+}
+
+/// Computes the solution to part 1.
+pub fn part1(circuit: &Circuit) -> Answer {
+    // parsing checked that every wire has a value
+    circuit.output().unwrap_or_default().into()
+}
+
+/// Computes the solution to part 2.
+pub fn part2(circuit: &Circuit) -> Answer {
+    circuit.suspicious_wires().join(",").into()
+}
+
+pub const EXAMPLE: &str = r#"x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02"#;
+
+aoc_day! {
+    day: 24,
+    parsed: Circuit,
+    example_part_1: EXAMPLE => 4,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a correct ripple-carry adder over `bits`-bit inputs, then swaps
+    /// the outputs of each given pair of wires.
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines = Vec::new();
+
+        for wire in ['x', 'y'] {
+            for bit in 0..bits {
+                lines.push(format!("{wire}{bit:02}: {}", bit % 2));
+            }
+        }
+
+        lines.push(String::new());
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("x00 AND y00 -> c00".to_string());
+
+        for bit in 1..bits {
+            let carry = format!("c{:02}", bit - 1);
+            let sum = match bit == bits - 1 {
+                true => format!("z{bits:02}"),
+                false => format!("c{bit:02}"),
+            };
+
+            lines.push(format!("x{bit:02} XOR y{bit:02} -> h{bit:02}"));
+            lines.push(format!("x{bit:02} AND y{bit:02} -> a{bit:02}"));
+            lines.push(format!("h{bit:02} XOR {carry} -> z{bit:02}"));
+            lines.push(format!("h{bit:02} AND {carry} -> t{bit:02}"));
+            lines.push(format!("a{bit:02} OR t{bit:02} -> {sum}"));
+        }
+
+        let mut text = lines.join("\n");
+        for (a, b) in swaps {
+            text = text
+                .replace(&format!("-> {a}"), "-> ~")
+                .replace(&format!("-> {b}"), &format!("-> {a}"))
+                .replace("-> ~", &format!("-> {b}"));
+        }
+
+        text
+    }
+
+    #[test]
+    fn correct_adder_adds() {
+        let circuit = adder(8, &[]).parse::<Circuit>().unwrap();
+
+        // x = y = 0b10101010
+        assert_eq!(circuit.output(), Some(2 * 0b10101010));
+        assert!(circuit.suspicious_wires().is_empty());
+    }
+
+    #[test]
+    fn swapped_adder_wires() {
+        let input = adder(16, &[("z05", "c05"), ("h10", "a10")]);
+        assert_eq!(part2(&parse(&input).unwrap()), "a10,c05,h10,z05");
+    }
+
+    #[test]
+    fn dot_highlights_swapped_wires() {
+        let circuit = adder(4, &[("h02", "a02")]).parse::<Circuit>().unwrap();
+        let dot = circuit.to_dot();
+
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.contains("h02 [shape=plaintext, style=filled, fillcolor=red];"));
+        assert!(dot.contains("c01 [shape=plaintext];"));
+        assert_eq!(dot.matches("shape=diamond").count(), 7);
+        assert_eq!(dot.matches("color=red, penwidth=2").count(), 2);
+    }
+
+    #[test]
+    fn invalid_circuits() {
+        let error = |gates: &str| {
+            format!("x00: 1\ny00: 0\n\n{gates}")
+                .parse::<Circuit>()
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("x00 AND y00 -> x00"),
+            "line 4, column 1: wire `x00` is driven more than once"
+        );
+        assert_eq!(
+            error("x00 AND y00 -> z00\nx00 OR w00 -> z01"),
+            "line 5, column 1: wire `w00` is never driven"
+        );
+        assert_eq!(
+            error("x00 AND b -> a\na OR y00 -> b\na XOR y00 -> z00"),
+            "line 5, column 1: this gate is part of a cycle"
+        );
+
+        let too_wide = adder(64, &[]).parse::<Circuit>().unwrap_err().to_string();
+        assert!(too_wide.ends_with("there are more than 64 `z` wires"));
+    }
+}
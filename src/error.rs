@@ -0,0 +1,226 @@
+//! The crate's error type, for inputs that can't be solved.
+//!
+//! Parsers report where an input went wrong with [`AocError::at`], relative to
+//! the text they were given. A parser that hands a slice of its input to
+//! another one moves any error back into its own coordinates with
+//! [`AocError::within`], and the registry finally tags it with the day whose
+//! input it was.
+//!
+//! Malformed text is always a [`AocError::Parse`] error. Some days also check
+//! that well-formed input describes a puzzle they can solve, so that their
+//! parts can't fail, and those report what's wrong with their own error type
+//! (see [`DayError`]), which has its own variant here.
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::fmt::Display;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AocError {
+    /// Some part of an input couldn't be parsed. Lines and columns count from
+    /// 1, and columns count bytes.
+    #[error("line {line}, column {column}: {reason}")]
+    Parse {
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+    /// Day 14's lobby can't be searched for the picture.
+    #[cfg(feature = "day14")]
+    #[error("line {line}, column {column}: {error}")]
+    Lobby {
+        line: usize,
+        column: usize,
+        error: crate::day14::InvalidLobby,
+    },
+    /// Day 16's maze can't be solved.
+    #[cfg(feature = "day16")]
+    #[error("line {line}, column {column}: {error}")]
+    Maze {
+        line: usize,
+        column: usize,
+        error: crate::day16::InvalidMaze,
+    },
+    /// Day 17's program can't be run.
+    #[cfg(feature = "day17")]
+    #[error("line {line}, column {column}: {error}")]
+    Program {
+        line: usize,
+        column: usize,
+        error: crate::day17::InvalidProgram,
+    },
+    /// Day 18's bytes don't cut off the exit when the puzzle says they do.
+    #[cfg(feature = "day18")]
+    #[error("line {line}, column {column}: {error}")]
+    Memory {
+        line: usize,
+        column: usize,
+        error: crate::day18::InvalidMemory,
+    },
+    /// Day 21's codes have an answer too large to give.
+    #[cfg(feature = "day21")]
+    #[error("line {line}, column {column}: {error}")]
+    Codes {
+        line: usize,
+        column: usize,
+        error: crate::day21::InvalidCodes,
+    },
+    /// Day 24's circuit can't be evaluated.
+    #[cfg(feature = "day24")]
+    #[error("line {line}, column {column}: {error}")]
+    Circuit {
+        line: usize,
+        column: usize,
+        error: crate::day24::InvalidCircuit,
+    },
+    /// The input of a particular day couldn't be parsed.
+    #[error("invalid input for {year} day {day}: {error}")]
+    Day {
+        year: u16,
+        day: u8,
+        error: Box<AocError>,
+    },
+}
+
+/// An error that a day's own checks find in input that parses, but doesn't
+/// describe a puzzle the day can solve.
+pub trait DayError {
+    /// Wraps the error in its day's variant of [`AocError`], at `line` and
+    /// `column`.
+    fn at(self, line: usize, column: usize) -> AocError;
+}
+
+/// Returns the offset of `part` in `input`, which it must be a slice of.
+fn offset_of(input: &[u8], part: &[u8]) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    assert!(offset <= input.len(), "part is not a slice of input");
+    offset
+}
+
+/// Returns the line and column of byte `offset` in `input`.
+fn location(input: &[u8], offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+
+    let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+    (line, offset - line_start + 1)
+}
+
+impl AocError {
+    /// Creates a parse error at the start of `part`, which must be a slice of
+    /// `input`.
+    pub fn at(input: &str, part: &str, reason: impl Display) -> Self {
+        Self::at_bytes(input.as_bytes(), part.as_bytes(), reason)
+    }
+
+    /// Like [`AocError::at`], but for parsers that work on bytes.
+    pub fn at_bytes(input: &[u8], part: &[u8], reason: impl Display) -> Self {
+        let (line, column) = location(input, offset_of(input, part));
+
+        Self::Parse {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates one of a day's own errors at the start of `part`, which must be
+    /// a slice of `input`.
+    pub fn invalid(input: &str, part: &str, error: impl DayError) -> Self {
+        let (line, column) = location(
+            input.as_bytes(),
+            offset_of(input.as_bytes(), part.as_bytes()),
+        );
+
+        error.at(line, column)
+    }
+
+    /// Returns the line and column of a located error, which is any error
+    /// but one tagged with its day.
+    pub(crate) fn location_mut(&mut self) -> Option<(&mut usize, &mut usize)> {
+        match self {
+            Self::Parse { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day14")]
+            Self::Lobby { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day16")]
+            Self::Maze { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day17")]
+            Self::Program { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day18")]
+            Self::Memory { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day21")]
+            Self::Codes { line, column, .. } => Some((line, column)),
+            #[cfg(feature = "day24")]
+            Self::Circuit { line, column, .. } => Some((line, column)),
+            Self::Day { .. } => None,
+        }
+    }
+
+    /// Creates a parse error at the end of `input`, for when it stops short.
+    pub fn at_end(input: &str, reason: impl Display) -> Self {
+        Self::at(input, &input[input.len()..], reason)
+    }
+
+    /// Moves a parse error in `part`, which must be a slice of `input`, to
+    /// where it is in `input`.
+    pub fn within(mut self, input: &str, part: &str) -> Self {
+        if let Some((line, column)) = self.location_mut() {
+            let (part_line, part_column) = location(
+                input.as_bytes(),
+                offset_of(input.as_bytes(), part.as_bytes()),
+            );
+
+            if *line == 1 {
+                *column += part_column - 1;
+            }
+
+            *line += part_line - 1;
+        }
+
+        self
+    }
+
+    /// Tags the error with the day whose input it was found in.
+    pub fn in_day(self, year: u16, day: u8) -> Self {
+        Self::Day {
+            year,
+            day,
+            error: Box::new(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let input = "12 34\n56 7x\n";
+        let line = &input[6..11];
+        let error = AocError::at(line, &line[4..], "not a digit");
+
+        assert_eq!(error.to_string(), "line 1, column 5: not a digit");
+        assert_eq!(
+            error.within(input, line).to_string(),
+            "line 2, column 5: not a digit"
+        );
+        assert_eq!(
+            AocError::at_end(input, "missing a line").to_string(),
+            "line 3, column 1: missing a line"
+        );
+        assert_eq!(
+            AocError::at(input, &input[3..], "no")
+                .in_day(2024, 1)
+                .to_string(),
+            "invalid input for 2024 day 1: line 1, column 4: no"
+        );
+    }
+}
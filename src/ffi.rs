@@ -0,0 +1,182 @@
+//! A C API, for embedding the solutions in programs written in other languages.
+//!
+//! Build the library as a C library, e.g. with `cargo rustc --lib --release
+//! --features ffi --crate-type staticlib`, and include `include/aoc2024.h`.
+//! The header is generated by `build.rs` from this module's public items, so
+//! everything here has to stick to the handful of types that it can translate.
+
+use std::{ffi::c_char, panic::AssertUnwindSafe};
+
+/// The header generated from this module.
+pub const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/aoc2024.h"));
+
+/// The outcome of a call to `aoc2024_solve`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The answer was written to the output buffer.
+    Ok = 0,
+    /// The day isn't compiled into the library.
+    NoSuchDay = 1,
+    /// The day has no such part.
+    NoSuchPart = 2,
+    /// The input isn't valid UTF-8.
+    InvalidUtf8 = 3,
+    /// The input couldn't be parsed, and the output buffer holds the reason.
+    InvalidInput = 4,
+    /// The output buffer is too small for the answer.
+    BufferTooSmall = 5,
+    /// The solver panicked, which it should only do on an input it can't solve.
+    Panicked = 6,
+}
+
+/// Solves `part` of `day` (of 2024) for the `len` bytes of input at `input`,
+/// and writes the answer to the `out_len` bytes at `out_buf` as a
+/// nul-terminated string.
+///
+/// If the input can't be parsed, the reason is written instead, truncated to
+/// fit. After any other failure the buffer holds an empty string.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, and `out_buf` to `out_len`
+/// writable bytes. Either may be null if its length is zero.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2024_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> AocStatus {
+    let input = match len {
+        0 => &[],
+        len => unsafe { std::slice::from_raw_parts(input, len) },
+    };
+
+    let out = match out_len {
+        0 => &mut [],
+        len => unsafe { std::slice::from_raw_parts_mut(out_buf.cast::<u8>(), len) },
+    };
+
+    let result = match std::str::from_utf8(input) {
+        Ok(input) => std::panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, input)))
+            .unwrap_or(Err((AocStatus::Panicked, String::new()))),
+        Err(_) => Err((AocStatus::InvalidUtf8, String::new())),
+    };
+
+    match result {
+        Ok(answer) if write(out, &answer) => AocStatus::Ok,
+        Ok(_) => {
+            write(out, "");
+            AocStatus::BufferTooSmall
+        }
+        Err((status, message)) => {
+            write(out, &message);
+            status
+        }
+    }
+}
+
+/// Solves `part` of `day` for `input`, or returns the status to fail with and
+/// the message to write.
+fn solve(day: u8, part: u8, input: &str) -> Result<String, (AocStatus, String)> {
+    let day = crate::find(2024, day).ok_or((AocStatus::NoSuchDay, String::new()))?;
+    let no_such_part = || (AocStatus::NoSuchPart, String::new());
+
+    let answer = match part {
+        1 => day.solve_part_1(input).map(Some),
+        2 => day.solve_part_2(input),
+        _ => return Err(no_such_part()),
+    };
+
+    answer
+        .map_err(|err| (AocStatus::InvalidInput, err.to_string()))?
+        .map(|answer| answer.to_string())
+        .ok_or_else(no_such_part)
+}
+
+/// Writes as much of `text` as fits into `out` as a nul-terminated string,
+/// returning whether all of it did.
+fn write(out: &mut [u8], text: &str) -> bool {
+    let Some(capacity) = out.len().checked_sub(1) else {
+        return false;
+    };
+
+    let len = text.len().min(capacity);
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out[len] = 0;
+    len == text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn call(day: u8, part: u8, input: &str, out: &mut [u8]) -> (AocStatus, String) {
+        let status = unsafe {
+            aoc2024_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().cast(),
+                out.len(),
+            )
+        };
+
+        let text = CStr::from_bytes_until_nul(out)
+            .map_or(String::new(), |text| text.to_string_lossy().into_owned());
+        (status, text)
+    }
+
+    #[test]
+    fn statuses() {
+        let mut out = [0xff; 64];
+        assert_eq!(
+            call(26, 1, "", &mut out),
+            (AocStatus::NoSuchDay, String::new())
+        );
+
+        if crate::find(2024, 1).is_none() {
+            return;
+        }
+
+        assert_eq!(
+            call(1, 1, "1 3\n4 2\n", &mut out),
+            (AocStatus::Ok, "2".into())
+        );
+        assert_eq!(
+            call(1, 3, "", &mut out),
+            (AocStatus::NoSuchPart, String::new())
+        );
+        assert_eq!(
+            call(1, 1, "1 3\n4 2\n", &mut out[..1]),
+            (AocStatus::BufferTooSmall, String::new())
+        );
+
+        let (status, message) = call(1, 1, "1 x\n", &mut out[..16]);
+        assert_eq!(status, AocStatus::InvalidInput);
+        assert_eq!(message, "invalid input f");
+    }
+
+    /// The checked-in header has to match the generated one, and is rewritten
+    /// by running the tests with `AOC_UPDATE_HEADER` set.
+    #[test]
+    fn header_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc2024.h");
+
+        if std::env::var_os("AOC_UPDATE_HEADER").is_some() {
+            std::fs::write(path, HEADER).unwrap();
+        }
+
+        let checked_in = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(
+            checked_in == HEADER,
+            "include/aoc2024.h is out of date, so rerun this test with AOC_UPDATE_HEADER=1"
+        );
+    }
+}
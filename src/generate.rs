@@ -0,0 +1,462 @@
+//! Random puzzle inputs with known answers, for testing the solvers.
+//!
+//! Each generator builds its input so that the answers fall out of the
+//! construction itself (e.g. a report is generated as safe, rather than being
+//! checked for safety afterwards), which gives an oracle that shares no code
+//! with the optimized solutions.
+
+use std::collections::BTreeMap;
+
+use crate::{arith, rng::Rng};
+
+/// A generated input together with its expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part_1: u64,
+    pub part_2: u64,
+}
+
+/// Generates a pair of location lists for day 1.
+///
+/// Both lists are built in sorted order, so the total difference can be summed
+/// pairwise as they're constructed, and the right list is built from a table
+/// of counts, which directly gives the similarity score. The lists are then
+/// shuffled independently.
+pub fn day01(rng: &mut Rng, len: usize) -> Generated {
+    let ids = (0..8)
+        .map(|_| rng.between(10000, 99999))
+        .collect::<Vec<_>>();
+
+    let mut counts = BTreeMap::<usize, usize>::new();
+    for _ in 0..len {
+        *counts.entry(ids[rng.below(ids.len())]).or_default() += 1;
+    }
+
+    let mut right = counts
+        .iter()
+        .flat_map(|(&id, &count)| std::iter::repeat_n(id, count))
+        .collect::<Vec<_>>();
+
+    // ascending random ids, which sometimes reuse one from the right list
+    let mut left = Vec::with_capacity(len);
+    let mut previous = 10000;
+    for _ in 0..len {
+        let id = match rng.coin() {
+            true => ids[rng.below(ids.len())].max(previous),
+            false => rng.between(previous, previous + 5000),
+        };
+
+        left.push(id);
+        previous = id;
+    }
+
+    let part_1 = left
+        .iter()
+        .zip(&right)
+        .map(|(&l, &r)| l.abs_diff(r))
+        .sum::<usize>();
+    let part_2 = left
+        .iter()
+        .map(|id| id * counts.get(id).copied().unwrap_or(0))
+        .sum::<usize>();
+
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+
+    let input = left
+        .iter()
+        .zip(&right)
+        .map(|(l, r)| format!("{l}   {r}\n"))
+        .collect();
+
+    Generated {
+        input,
+        part_1: part_1 as u64,
+        part_2: part_2 as u64,
+    }
+}
+
+/// Generates a strictly monotonic report whose adjacent levels differ by one
+/// to three.
+fn safe_report(rng: &mut Rng) -> Vec<usize> {
+    let len = rng.between(5, 8);
+    let increasing = rng.coin();
+    let mut level = match increasing {
+        true => rng.between(1, 20),
+        false => rng.between(40, 60),
+    };
+
+    let mut report = vec![level];
+    for _ in 1..len {
+        let step = rng.between(1, 3);
+        level = if increasing {
+            level + step
+        } else {
+            level - step
+        };
+        report.push(level);
+    }
+
+    report
+}
+
+/// Generates a list of reports for day 2.
+///
+/// Each report is one of three kinds: safe as generated; safe except for one
+/// duplicated level (so the dampener fixes it); or a safe report with two
+/// duplicated levels, which no single removal can fix.
+pub fn day02(rng: &mut Rng, len: usize) -> Generated {
+    let (mut safe, mut dampened) = (0, 0);
+    let mut input = String::new();
+
+    for _ in 0..len {
+        let mut report = safe_report(rng);
+
+        match rng.below(3) {
+            0 => safe += 1,
+            1 => {
+                let index = rng.below(report.len());
+                report.insert(index, report[index]);
+                dampened += 1;
+            }
+            _ => {
+                let first = rng.below(report.len() / 2);
+                let second = rng.between(report.len() / 2 + 1, report.len() - 1);
+                report.insert(second, report[second]);
+                report.insert(first, report[first]);
+            }
+        }
+
+        let levels = report.iter().map(usize::to_string).collect::<Vec<_>>();
+        input.push_str(&levels.join(" "));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        part_1: safe,
+        part_2: safe + dampened,
+    }
+}
+
+/// Generates page ordering rules and updates for day 5.
+///
+/// The rules come from a random total order on a set of pages (with a rule
+/// for every pair), so each update's correct order is known up front: correct
+/// updates are subsequences of the order, and incorrect ones are shuffled
+/// subsequences whose middle page is read off the sorted subsequence.
+pub fn day05(rng: &mut Rng, len: usize) -> Generated {
+    let mut order = (10..100).collect::<Vec<usize>>();
+    rng.shuffle(&mut order);
+    order.truncate(rng.between(5, 20));
+
+    let mut rules = Vec::new();
+    for (i, first) in order.iter().enumerate() {
+        for second in &order[i + 1..] {
+            rules.push(format!("{first}|{second}"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let (mut part_1, mut part_2) = (0, 0);
+    let mut updates = Vec::new();
+
+    for _ in 0..len {
+        // an odd-length subsequence of the order
+        let size = 2 * rng.between(1, (order.len() - 1) / 2) + 1;
+        let mut picks = rng.permutation(order.len());
+        picks.truncate(size);
+        picks.sort_unstable();
+
+        let mut update = picks.iter().map(|&i| order[i]).collect::<Vec<_>>();
+        let middle = update[size / 2] as u64;
+
+        if rng.coin() {
+            part_1 += middle;
+        } else {
+            // shuffle until the update is out of order
+            let sorted = update.clone();
+            while update == sorted {
+                rng.shuffle(&mut update);
+            }
+            part_2 += middle;
+        }
+
+        let pages = update.iter().map(usize::to_string).collect::<Vec<_>>();
+        updates.push(pages.join(","));
+    }
+
+    Generated {
+        input: format!("{}\n\n{}\n", rules.join("\n"), updates.join("\n")),
+        part_1,
+        part_2,
+    }
+}
+
+/// The operators available to the day 7 equations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Mul,
+    Concat,
+}
+
+impl Operator {
+    fn apply(self, lhs: u64, rhs: u64) -> u64 {
+        match self {
+            Operator::Add => arith::add(lhs, rhs),
+            Operator::Mul => arith::mul(lhs, rhs),
+            Operator::Concat => arith::add(arith::mul(lhs, 10u64.pow(rhs.ilog10() + 1)), rhs),
+        }
+    }
+}
+
+/// Returns whether any left-to-right assignment of `operators` to the gaps
+/// between `operands` evaluates to `value`, by trying every one in turn.
+fn brute_force(value: u64, operands: &[u64], operators: &[Operator]) -> bool {
+    let gaps = operands.len() as u32 - 1;
+
+    (0..operators.len().pow(gaps)).any(|mut choice| {
+        let mut total = operands[0];
+        for &operand in &operands[1..] {
+            total = operators[choice % operators.len()].apply(total, operand);
+            choice /= operators.len();
+        }
+        total == value
+    })
+}
+
+/// Generates calibration equations for day 7.
+///
+/// Each equation's value is built by applying random operators to random
+/// operands (sometimes offset so it is likely unsolvable). Because a value can
+/// coincidentally be reachable in other ways, each equation is classified by
+/// exhaustively trying every operator assignment, rather than by the pruning
+/// search the solution uses.
+pub fn day07(rng: &mut Rng, len: usize) -> Generated {
+    const ALL: [Operator; 3] = [Operator::Add, Operator::Mul, Operator::Concat];
+
+    let (mut part_1, mut part_2) = (0, 0);
+    let mut input = String::new();
+
+    for _ in 0..len {
+        let operands = (0..rng.between(2, 6))
+            .map(|_| rng.between(1, 99) as u64)
+            .collect::<Vec<_>>();
+
+        let mut value = operands[1..].iter().fold(operands[0], |total, &operand| {
+            ALL[rng.below(ALL.len())].apply(total, operand)
+        });
+
+        if rng.below(4) == 0 {
+            value += 1;
+        }
+
+        if brute_force(value, &operands, &ALL[..2]) {
+            part_1 = arith::add(part_1, value);
+        }
+
+        if brute_force(value, &operands, &ALL) {
+            part_2 = arith::add(part_2, value);
+        }
+
+        let operands = operands.iter().map(u64::to_string).collect::<Vec<_>>();
+        input.push_str(&format!("{value}: {}\n", operands.join(" ")));
+    }
+
+    Generated {
+        input,
+        part_1,
+        part_2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    /// Checks the registered solution for `day` against many generated inputs.
+    fn check(day: u8, generate: fn(&mut Rng, usize) -> Generated) {
+        let Some(solution) = crate::find(2024, day) else {
+            return;
+        };
+
+        for seed in 0..100 {
+            let mut rng = Rng::new(seed);
+            let len = rng.between(1, 50);
+            let generated = generate(&mut rng, len);
+
+            assert_eq!(
+                solution.solve_part_1(&generated.input),
+                Ok(Answer::from(generated.part_1)),
+                "part 1 of day {day} with seed {seed}:\n{}",
+                generated.input
+            );
+
+            assert_eq!(
+                solution.solve_part_2(&generated.input),
+                Ok(Some(Answer::from(generated.part_2))),
+                "part 2 of day {day} with seed {seed}:\n{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn day01_generated() {
+        check(1, day01);
+    }
+
+    #[test]
+    fn day02_generated() {
+        check(2, day02);
+    }
+
+    #[test]
+    fn day05_generated() {
+        check(5, day05);
+    }
+
+    #[test]
+    fn day07_generated() {
+        check(7, day07);
+    }
+
+    /// Returns the answers to both parts of `day` for `input`, or `None` if the
+    /// day isn't compiled in or `input` isn't valid.
+    fn answers(day: u8, input: &str) -> Option<(u64, u64)> {
+        let solution = crate::find(2024, day)?;
+
+        match (
+            solution.solve_part_1(input).ok()?,
+            solution.solve_part_2(input).ok()??,
+        ) {
+            (Answer::Int(part_1), Answer::Int(part_2)) => Some((part_1, part_2)),
+            _ => None,
+        }
+    }
+
+    /// Shuffles the lines within each blank-line-separated block of `input`.
+    fn shuffle_lines(rng: &mut Rng, input: &str) -> String {
+        let blocks = input
+            .split("\n\n")
+            .map(|block| {
+                let mut lines = block.lines().collect::<Vec<_>>();
+                rng.shuffle(&mut lines);
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>();
+
+        blocks.join("\n\n") + "\n"
+    }
+
+    /// Repeats every line in the last block of `input`, which for days whose
+    /// answers are sums over those lines doubles both answers.
+    fn repeat_last_block(input: &str) -> String {
+        let (rest, last) = match input.trim_end().rsplit_once("\n\n") {
+            Some((rest, last)) => (format!("{rest}\n\n"), last),
+            None => (String::new(), input.trim_end()),
+        };
+
+        format!("{rest}{last}\n{last}\n")
+    }
+
+    /// Removes lines from `input` one at a time for as long as it still
+    /// `fails`, so that a failing property is reported with a minimal input.
+    fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+        let mut lines = input.lines().collect::<Vec<_>>();
+        let mut index = 0;
+
+        while index < lines.len() {
+            let mut candidate = lines.clone();
+            candidate.remove(index);
+
+            if fails(&(candidate.join("\n") + "\n")) {
+                lines = candidate;
+            } else {
+                index += 1;
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Checks that `property` holds for many generated inputs of a day.
+    ///
+    /// A property is given the input and a generator seeded the same way on
+    /// every call, and should hold vacuously of inputs that aren't valid, which
+    /// lets [`shrink`] drop lines freely.
+    fn check_property(
+        name: &str,
+        generate: fn(&mut Rng, usize) -> Generated,
+        property: impl Fn(&mut Rng, &str) -> bool,
+    ) {
+        for seed in 0..100 {
+            let mut rng = Rng::new(seed);
+            let len = rng.between(1, 50);
+            let input = generate(&mut rng, len).input;
+            let fails = |input: &str| !property(&mut Rng::new(seed), input);
+
+            if fails(&input) {
+                panic!(
+                    "{name} fails with seed {seed}, for example on:\n{}",
+                    shrink(&input, fails)
+                );
+            }
+        }
+    }
+
+    /// A property that the answers of `day` don't depend on the order of the
+    /// lines in each block of its input.
+    fn order_independent(day: u8) -> impl Fn(&mut Rng, &str) -> bool {
+        move |rng, input| answers(day, input) == answers(day, &shuffle_lines(rng, input))
+    }
+
+    /// A property that repeating the last block of the input of `day` doubles
+    /// both of its answers.
+    fn additive(day: u8) -> impl Fn(&mut Rng, &str) -> bool {
+        move |_, input| {
+            answers(day, input).is_none_or(|(part_1, part_2)| {
+                answers(day, &repeat_last_block(input)) == Some((2 * part_1, 2 * part_2))
+            })
+        }
+    }
+
+    #[test]
+    fn day01_properties() {
+        check_property("day 1 order independence", day01, order_independent(1));
+    }
+
+    #[test]
+    fn day02_properties() {
+        check_property("day 2 order independence", day02, order_independent(2));
+        check_property("day 2 additivity", day02, additive(2));
+
+        // the dampener can only make more reports safe, and never more than
+        // there are
+        check_property("day 2 answer bounds", day02, |_, input| {
+            answers(2, input).is_none_or(|(safe, dampened)| {
+                safe <= dampened && dampened <= input.lines().count() as u64
+            })
+        });
+    }
+
+    #[test]
+    fn day05_properties() {
+        check_property("day 5 order independence", day05, order_independent(5));
+        check_property("day 5 additivity", day05, additive(5));
+    }
+
+    #[test]
+    fn day07_properties() {
+        check_property("day 7 order independence", day07, order_independent(7));
+        check_property("day 7 additivity", day07, additive(7));
+
+        // concatenation only adds ways to make an equation true
+        check_property("day 7 answer order", day07, |_, input| {
+            answers(7, input).is_none_or(|(part_1, part_2)| part_1 <= part_2)
+        });
+    }
+}
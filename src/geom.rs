@@ -0,0 +1,250 @@
+//! Integer geometry on the plane of a grid.
+//!
+//! Coordinates follow [`Grid`](crate::grid::Grid): a [`Point`] is a `(row,
+//! col)` pair, rows grow downwards, and so [`Direction::N`] is a step to the
+//! previous row. Unlike grid positions, points can be negative, which lets a
+//! day step off the edge of a grid and check where it landed afterwards.
+
+use core::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A point on the plane.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Point {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    /// Returns the point as a grid position, if neither coordinate is negative.
+    pub fn to_position(self) -> Option<(usize, usize)> {
+        Some((self.row.try_into().ok()?, self.col.try_into().ok()?))
+    }
+
+    /// Returns the Manhattan distance between `self` and `other`.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row as isize, col as isize)
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Self::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+/// The offset between two points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2 {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Vec2 {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((row, col): (isize, isize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.row * rhs, self.col * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.row, -self.col)
+    }
+}
+
+/// One of the eight compass directions, in clockwise order from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// Every direction, in clockwise order from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// The directions along the rows and columns of a grid.
+    pub const CARDINAL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    /// The directions between the cardinal ones.
+    pub const DIAGONAL: [Self; 4] = [Self::NE, Self::SE, Self::SW, Self::NW];
+
+    /// Returns the offset of a single step in this direction.
+    pub const fn offset(self) -> Vec2 {
+        match self {
+            Self::N => Vec2::new(-1, 0),
+            Self::NE => Vec2::new(-1, 1),
+            Self::E => Vec2::new(0, 1),
+            Self::SE => Vec2::new(1, 1),
+            Self::S => Vec2::new(1, 0),
+            Self::SW => Vec2::new(1, -1),
+            Self::W => Vec2::new(0, -1),
+            Self::NW => Vec2::new(-1, -1),
+        }
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        Self::CARDINAL.contains(&self)
+    }
+
+    /// Rotates this direction clockwise by `eighths` of a full turn, or
+    /// anticlockwise if `eighths` is negative.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    /// Turns a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns a quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Reflects this direction in a vertical mirror, swapping east and west.
+    pub fn flip_horizontal(self) -> Self {
+        Self::ALL[(8 - self as usize) % 8]
+    }
+
+    /// Reflects this direction in a horizontal mirror, swapping north and
+    /// south.
+    pub fn flip_vertical(self) -> Self {
+        self.flip_horizontal().reverse()
+    }
+
+    /// Parses one of the arrows `^`, `>`, `v` and `<` that puzzles use for
+    /// the cardinal directions.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::N),
+            '>' => Some(Self::E),
+            'v' | 'V' => Some(Self::S),
+            '<' => Some(Self::W),
+            _ => None,
+        }
+    }
+
+    /// Returns the arrow for a cardinal direction (see [`Self::from_arrow`]).
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Self::N => Some('^'),
+            Self::E => Some('>'),
+            Self::S => Some('v'),
+            Self::W => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_and_reflection() {
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::NW.rotate(1), Direction::N);
+        assert_eq!(Direction::SE.reverse(), Direction::NW);
+        assert_eq!(Direction::NE.flip_horizontal(), Direction::NW);
+        assert_eq!(Direction::NE.flip_vertical(), Direction::SE);
+        assert_eq!(Direction::E.flip_vertical(), Direction::E);
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+            assert_eq!(direction.flip_horizontal().flip_horizontal(), direction);
+            assert_eq!(direction.is_cardinal(), direction.arrow().is_some());
+        }
+    }
+
+    #[test]
+    fn points_and_offsets() {
+        let start = Point::from((2, 3));
+        let end = start + Direction::NW.offset() * 3;
+
+        assert_eq!(end, Point::new(-1, 0));
+        assert_eq!(end.to_position(), None);
+        assert_eq!(end - start, Vec2::new(-3, -3));
+        assert_eq!(start.manhattan(end), 6);
+    }
+}
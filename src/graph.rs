@@ -0,0 +1,335 @@
+//! Undirected graphs over dense vertex indices, with bitset adjacency.
+
+use alloc::{vec, vec::Vec};
+
+/// A fixed-capacity set of small integers, stored as a bit vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// Creates an empty set that can hold the integers `0..capacity`.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+        }
+    }
+
+    /// Creates the set containing every integer in `0..capacity`.
+    pub fn full(capacity: usize) -> Self {
+        let mut set = Self::new(capacity);
+        for i in 0..capacity {
+            set.insert(i);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, value: usize) {
+        self.words[value / 64] |= 1 << (value % 64);
+    }
+
+    pub fn remove(&mut self, value: usize) {
+        self.words[value / 64] &= !(1 << (value % 64));
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / 64)
+            .is_some_and(|word| word & (1 << (value % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Returns the intersection of `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    /// Returns the elements of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & !b)
+                .collect(),
+        }
+    }
+
+    /// Returns the number of elements in the intersection of `self` and
+    /// `other`, without allocating it.
+    pub fn intersection_len(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns an iterator over the elements of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            core::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+/// An undirected simple graph on the vertices `0..len`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Graph {
+    adjacency: Vec<BitSet>,
+}
+
+impl Graph {
+    /// Creates a graph with `len` vertices and no edges.
+    pub fn new(len: usize) -> Self {
+        Self {
+            adjacency: vec![BitSet::new(len); len],
+        }
+    }
+
+    /// Returns the number of vertices.
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// Adds an undirected edge between `a` and `b`. Self-loops are ignored.
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        if a != b {
+            self.adjacency[a].insert(b);
+            self.adjacency[b].insert(a);
+        }
+    }
+
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].contains(b)
+    }
+
+    /// Returns the set of neighbours of `vertex`.
+    pub fn neighbours(&self, vertex: usize) -> &BitSet {
+        &self.adjacency[vertex]
+    }
+
+    /// Returns the set of neighbours of `vertex` with a greater index.
+    fn forward_neighbours(&self, vertex: usize) -> BitSet {
+        let mut set = self.adjacency[vertex].clone();
+        for lower in 0..=vertex {
+            set.remove(lower);
+        }
+        set
+    }
+}
+
+/// Returns every triangle in `graph`, as vertex triples in increasing order.
+pub fn triangles(graph: &Graph) -> Vec<[usize; 3]> {
+    cliques_of_size(graph, 3)
+        .into_iter()
+        .map(|clique| [clique[0], clique[1], clique[2]])
+        .collect()
+}
+
+/// Returns every clique of exactly `k` vertices in `graph`, each as a list of
+/// vertices in increasing order. The cliques are listed in lexicographic order.
+///
+/// Each clique is found exactly once by only ever extending a partial clique
+/// with vertices greater than all of its current members.
+pub fn cliques_of_size(graph: &Graph, k: usize) -> Vec<Vec<usize>> {
+    fn extend(
+        graph: &Graph,
+        k: usize,
+        clique: &mut Vec<usize>,
+        candidates: BitSet,
+        output: &mut Vec<Vec<usize>>,
+    ) {
+        if clique.len() == k {
+            output.push(clique.clone());
+            return;
+        }
+
+        for vertex in candidates.iter() {
+            let next = candidates.intersection(&graph.forward_neighbours(vertex));
+
+            // prune branches that can't possibly reach k vertices
+            if clique.len() + 1 + next.len() < k {
+                continue;
+            }
+
+            clique.push(vertex);
+            extend(graph, k, clique, next, output);
+            clique.pop();
+        }
+    }
+
+    let mut output = Vec::new();
+
+    if k == 0 {
+        output.push(Vec::new());
+    } else {
+        extend(
+            graph,
+            k,
+            &mut Vec::new(),
+            BitSet::full(graph.len()),
+            &mut output,
+        );
+    }
+
+    output
+}
+
+/// Finds a maximum clique of `graph` using the Bron–Kerbosch algorithm with
+/// pivoting, returning its vertices in increasing order.
+pub fn max_clique(graph: &Graph) -> Vec<usize> {
+    fn bron_kerbosch(
+        graph: &Graph,
+        clique: &mut Vec<usize>,
+        mut candidates: BitSet,
+        mut excluded: BitSet,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            if clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+
+        // a branch can't beat the best clique if it can't grow large enough
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+
+        // pivot on the vertex with the most neighbours among the candidates,
+        // since any maximal clique must contain the pivot or a non-neighbour
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|&v| candidates.intersection_len(graph.neighbours(v)))
+            .unwrap();
+
+        for vertex in candidates.difference(graph.neighbours(pivot)).iter() {
+            let neighbours = graph.neighbours(vertex);
+
+            clique.push(vertex);
+            bron_kerbosch(
+                graph,
+                clique,
+                candidates.intersection(neighbours),
+                excluded.intersection(neighbours),
+                best,
+            );
+            clique.pop();
+
+            candidates.remove(vertex);
+            excluded.insert(vertex);
+        }
+    }
+
+    let mut best = Vec::new();
+    bron_kerbosch(
+        graph,
+        &mut Vec::new(),
+        BitSet::full(graph.len()),
+        BitSet::new(graph.len()),
+        &mut best,
+    );
+
+    best.sort_unstable();
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the complete graph on `n` vertices.
+    fn complete(n: usize) -> Graph {
+        let mut graph = Graph::new(n);
+        for a in 0..n {
+            for b in (a + 1)..n {
+                graph.add_edge(a, b);
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn bitset_operations() {
+        let mut set = BitSet::new(130);
+        set.insert(0);
+        set.insert(64);
+        set.insert(129);
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 64, 129]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+
+        set.remove(64);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 129]);
+    }
+
+    #[test]
+    fn complete_graph_cliques() {
+        let graph = complete(6);
+
+        // the number of k-cliques in K_n is n choose k
+        let counts = (0..=7)
+            .map(|k| cliques_of_size(&graph, k).len())
+            .collect::<Vec<_>>();
+
+        assert_eq!(counts, [1, 6, 15, 20, 15, 6, 1, 0]);
+        assert_eq!(max_clique(&graph), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn triangles_in_a_square_with_diagonal() {
+        let mut graph = Graph::new(4);
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)] {
+            graph.add_edge(a, b);
+        }
+
+        assert_eq!(triangles(&graph), [[0, 1, 2], [0, 2, 3]]);
+        assert_eq!(max_clique(&graph).len(), 3);
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = Graph::new(0);
+        assert!(triangles(&graph).is_empty());
+        assert!(max_clique(&graph).is_empty());
+    }
+}
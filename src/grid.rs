@@ -0,0 +1,295 @@
+//! The grid and vector types used for grid puzzles.
+//!
+//! [`Grid`] is a plain row-major grid indexed by `(row, col)`, which every day
+//! with a map stores its cells in.
+//!
+//! The [`Vector2`] type is for days that do linear algebra. With the
+//! `nalgebra` feature it's just a re-export, and otherwise it's a small
+//! stand-in that implements the subset of the `nalgebra` API used by the days
+//! (so a day imports it from here rather than from `nalgebra`).
+
+use alloc::{format, vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+use crate::{error::AocError, geom::Vec2};
+
+#[cfg(feature = "nalgebra")]
+pub use nalgebra::Vector2;
+
+#[cfg(not(feature = "nalgebra"))]
+pub use fallback::Vector2;
+
+/// A rectangular grid stored in row-major order, and indexed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with `rows` rows of `cols` cells, taken in row-major
+    /// order from `cells`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` doesn't have exactly `rows * cols` elements.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            rows * cols,
+            "grid has the wrong number of cells"
+        );
+        Self { cells, rows, cols }
+    }
+
+    /// Creates a grid with `rows` rows of `cols` cells, each a copy of
+    /// `value`.
+    pub fn from_element(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(rows, cols, vec![value; rows * cols])
+    }
+
+    /// Creates a grid with `rows` rows of `cols` cells, calling `f` with the
+    /// position of each cell (in row-major order) to get its value.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(&mut f)
+            .collect();
+
+        Self::from_vec(rows, cols, cells)
+    }
+
+    /// Parses a character map, with a row for each non-blank line of `s`
+    /// (ignoring surrounding whitespace), and a cell for each character.
+    ///
+    /// Fails if the rows aren't all the same length, or if `cell` rejects a
+    /// character.
+    pub fn parse(s: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        let mut cells = Vec::with_capacity(s.len());
+        let (mut rows, mut cols) = (0, None);
+
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            for (index, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    AocError::at(s, &line[index..], format!("unexpected character {c:?}"))
+                })?;
+
+                cells.push(value);
+            }
+
+            let len = line.chars().count();
+            match *cols.get_or_insert(len) {
+                expected if expected != len => {
+                    return Err(AocError::at(
+                        s,
+                        line,
+                        format!("expected a row of {expected} cells, not {len}"),
+                    ))
+                }
+                _ => rows += 1,
+            }
+        }
+
+        Ok(Self::from_vec(rows, cols.unwrap_or(0), cells))
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the number of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns whether `(row, col)` is in the grid.
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    /// Returns the cell at `(row, col)`, if it's in the grid.
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.cols + position.1])
+    }
+
+    /// Returns the cell at `(row, col)` mutably, if it's in the grid.
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.0 * self.cols + position.1])
+    }
+
+    /// Moves `(row, col)` by `offset`, returning `None` if that leaves the
+    /// grid.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        offset: impl Into<Vec2>,
+    ) -> Option<(usize, usize)> {
+        let offset = offset.into();
+        let position = (
+            row.checked_add_signed(offset.row)?,
+            col.checked_add_signed(offset.col)?,
+        );
+
+        self.contains(position).then_some(position)
+    }
+
+    /// Returns an iterator over the cells, in row-major order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Sets every cell to a copy of `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.cells.fill(value);
+    }
+
+    /// Returns a grid of the same shape, with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.rows, self.cols, self.cells.iter().map(f).collect())
+    }
+
+    /// Returns an iterator over the rows, each as a slice.
+    pub fn row_iter(&self) -> core::slice::Chunks<'_, T> {
+        // chunks panics on a chunk size of zero, which an empty grid would have
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// Returns an iterator over the cells and their `(row, col)` positions, in
+    /// row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index / cols, index % cols), cell))
+    }
+
+    /// Returns an iterator over the positions of the cells that satisfy
+    /// `predicate`, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.enumerate()
+            .filter_map(move |(position, cell)| predicate(cell).then_some(position))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        self.get(position).expect("grid index out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(position).expect("grid index out of bounds")
+    }
+}
+
+#[cfg(not(feature = "nalgebra"))]
+mod fallback {
+    use super::Index;
+
+    /// A two-dimensional column vector.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Vector2<T> {
+        pub x: T,
+        pub y: T,
+    }
+
+    impl<T: Copy + Default> Vector2<T> {
+        pub fn new(x: T, y: T) -> Self {
+            Self { x, y }
+        }
+
+        pub fn zeros() -> Self {
+            Self::default()
+        }
+    }
+
+    impl<T> From<[T; 2]> for Vector2<T> {
+        fn from([x, y]: [T; 2]) -> Self {
+            Self { x, y }
+        }
+    }
+
+    impl<T> Index<usize> for Vector2<T> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &Self::Output {
+            match index {
+                0 => &self.x,
+                1 => &self.y,
+                _ => panic!("vector index out of bounds"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_parsing_and_indexing() {
+        let grid = Grid::parse("ab\n  cd\n\nef\n", Some).unwrap();
+
+        assert_eq!(grid.shape(), (3, 2));
+        assert_eq!(grid[(1, 0)], 'c');
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.offset((1, 1), (1, -1)), Some((2, 0)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(
+            grid.positions(|&c| c > 'c').collect::<Vec<_>>(),
+            [(1, 1), (2, 0), (2, 1)]
+        );
+        assert_eq!(grid.row_iter().nth(2), Some(&['e', 'f'][..]));
+
+        let mut numbers = Grid::from_fn(2, 3, |(row, col)| row * 3 + col);
+        assert_eq!(numbers, Grid::from_vec(2, 3, (0..6).collect()));
+        assert!(numbers.map(|&n| n % 2 == 0)[(1, 1)]);
+
+        numbers.fill(7);
+        assert_eq!(numbers, Grid::from_element(2, 3, 7));
+
+        assert_eq!(
+            Grid::parse("ab\nc", Some).unwrap_err().to_string(),
+            "line 2, column 1: expected a row of 2 cells, not 1"
+        );
+        assert_eq!(
+            Grid::parse("ab", |c| (c == 'a').then_some(c))
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 2: unexpected character 'b'"
+        );
+    }
+}
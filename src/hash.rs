@@ -0,0 +1,22 @@
+//! The hash-based collections used by the days.
+//!
+//! With the `fast-hash` feature these use FxHash, which is much cheaper than
+//! std's SipHash for the small integer keys that most puzzles hash, and
+//! otherwise they fall back to std's default hasher. Either way, construct them
+//! with `default()`, `with_capacity_and_hasher`, or `collect()`, since the
+//! `new` and `with_capacity` constructors are specific to std's hasher.
+//!
+//! The collections themselves come from hashbrown (which std's are built on),
+//! so that they're available without std too.
+
+#[cfg(feature = "fast-hash")]
+pub type BuildHasher = rustc_hash::FxBuildHasher;
+
+#[cfg(all(not(feature = "fast-hash"), feature = "std"))]
+pub type BuildHasher = std::hash::RandomState;
+
+#[cfg(not(any(feature = "fast-hash", feature = "std")))]
+compile_error!("building without std needs the `fast-hash` feature");
+
+pub type HashMap<K, V> = hashbrown::HashMap<K, V, BuildHasher>;
+pub type HashSet<T> = hashbrown::HashSet<T, BuildHasher>;
@@ -0,0 +1,268 @@
+//! Locating the puzzle inputs on disk.
+//!
+//! Inputs are looked up as `<year>/dayNN.txt` in the configured input directory
+//! first (the crate's `input/` by default, see [`crate::config`]), and then in
+//! the cache kept by the `advent-of-code-data` (aocd) tool, which stores them as
+//! `<user id>/<year>_NN_input.txt` under `$AOCD_DIR` (or `~/.config/aocd`).
+//! Inputs for 2024 may also be kept directly in the input directory, which is
+//! where they lived before there were other years.
+//!
+//! Everything here works with the configured year, except for [`read_year`].
+//!
+//! If neither has a copy, [`fetch`] downloads the input into a cache of its own
+//! under `$XDG_CACHE_HOME/aoc-2024/inputs/<year>` (or `~/.cache/...`), next to a
+//! checksum that is verified whenever the cached copy is read, so that a
+//! truncated file is downloaded again rather than solved.
+
+use std::path::{Path, PathBuf};
+
+use crate::{client, config};
+
+/// Returns the path of the input for `day` in the configured input directory,
+/// whether or not it exists.
+pub fn local_path(day: u8) -> PathBuf {
+    local_path_for_year(config::get().year, day)
+}
+
+fn local_path_for_year(year: u16, day: u8) -> PathBuf {
+    let dir = &config::get().input_dir;
+    let file_name = format!("day{day:02}.txt");
+    let path = dir.join(year.to_string()).join(&file_name);
+
+    match dir.join(&file_name) {
+        flat if year == 2024 && !path.exists() && flat.exists() => flat,
+        _ => path,
+    }
+}
+
+/// Returns aocd's data directory, if it can be determined.
+fn aocd_dir() -> Option<PathBuf> {
+    match std::env::var_os("AOCD_DIR") {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => std::env::var_os("HOME").map(|home| Path::new(&home).join(".config").join("aocd")),
+    }
+}
+
+/// Returns the configured session token, or else the one in the `token` file
+/// in aocd's data directory (which is where aocd itself keeps it).
+///
+/// This selects the user whose cached inputs to use, and authenticates
+/// requests to adventofcode.com.
+pub fn session_token() -> Option<String> {
+    let token = match &config::get().session {
+        Some(token) => token.clone(),
+        None => std::fs::read_to_string(aocd_dir()?.join("token")).ok()?,
+    };
+
+    Some(token.trim().to_string())
+}
+
+/// Returns the user id that `token2id.json` maps `token` to.
+///
+/// The file is a flat object of strings, so this just takes the first string
+/// following the token rather than parsing it as JSON.
+fn user_id(dir: &Path, token: &str) -> Option<String> {
+    let table = std::fs::read_to_string(dir.join("token2id.json")).ok()?;
+    let (_, rest) = table.split_once(&format!("\"{token}\""))?;
+    let (_, rest) = rest.split_once('"')?;
+    let (id, _) = rest.split_once('"')?;
+    Some(id.to_string())
+}
+
+/// Finds the input for `day` in the aocd data directory `dir`.
+///
+/// If `token` belongs to a known user then only their inputs are considered,
+/// and otherwise the first user (by id) with a cached input is used.
+fn aocd_path_in(dir: &Path, token: Option<&str>, year: u16, day: u8) -> Option<PathBuf> {
+    let file_name = format!("{year}_{day:02}_input.txt");
+
+    if let Some(id) = token.and_then(|token| user_id(dir, token)) {
+        return Some(dir.join(id).join(file_name)).filter(|path| path.is_file());
+    }
+
+    let mut users = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+
+    users.sort();
+    users
+        .into_iter()
+        .map(|user| user.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Returns the path of the input for `day` in aocd's cache, if it has one.
+pub fn aocd_path(day: u8) -> Option<PathBuf> {
+    aocd_path_for_year(config::get().year, day)
+}
+
+fn aocd_path_for_year(year: u16, day: u8) -> Option<PathBuf> {
+    let dir = aocd_dir()?;
+    aocd_path_in(&dir, session_token().as_deref(), year, day)
+}
+
+/// Returns the path that the input for `day` is cached at once downloaded,
+/// whether or not it exists.
+pub fn cache_path(day: u8) -> Option<PathBuf> {
+    cache_path_for_year(config::get().year, day)
+}
+
+fn cache_path_for_year(year: u16, day: u8) -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&std::env::var_os("HOME")?).join(".cache"),
+    };
+
+    let dir = base.join("aoc-2024").join("inputs").join(year.to_string());
+    Some(dir.join(format!("day{day:02}.txt")))
+}
+
+/// Returns the path of the input for `day`, preferring a non-empty local copy
+/// to aocd's cache and then to a downloaded copy, and falling back to the
+/// local path if none of them exist.
+pub fn find(day: u8) -> PathBuf {
+    let local = local_path(day);
+
+    if non_empty(&local).is_some() {
+        return local;
+    }
+
+    aocd_path(day)
+        .or_else(|| cache_path(day).filter(|path| read_cached(path).is_some()))
+        .unwrap_or(local)
+}
+
+/// Reads the input for `day`, if it exists and isn't empty.
+pub fn read(day: u8) -> Option<String> {
+    read_year(config::get().year, day)
+}
+
+/// Reads the input for `day` of `year`, rather than of the configured year,
+/// if it exists and isn't empty.
+pub fn read_year(year: u16, day: u8) -> Option<String> {
+    non_empty(&local_path_for_year(year, day))
+        .or_else(|| non_empty(&aocd_path_for_year(year, day)?))
+        .or_else(|| read_cached(&cache_path_for_year(year, day)?))
+}
+
+/// Reads the input for `day` like [`read`], but downloads it into the cache
+/// first if there is no copy anywhere, or if `refresh` is set.
+///
+/// Without a session token this just returns `Ok(None)` for a missing input,
+/// so that it can be used wherever [`read`] is.
+pub fn fetch(day: u8, refresh: bool) -> Result<Option<String>, client::Error> {
+    if !refresh {
+        if let Some(input) = read(day) {
+            return Ok(Some(input));
+        }
+    }
+
+    let session = match session_token() {
+        Some(session) => session,
+        None if refresh => return Err(client::Error::NoSession),
+        None => return Ok(None),
+    };
+
+    let input = client::download_input(day, &session)?;
+
+    // every input ends with a newline, so anything else was cut short
+    if !input.ends_with('\n') {
+        return Err(client::Error::Request(
+            "the download was truncated".to_string(),
+        ));
+    }
+
+    if let Some(path) = cache_path(day) {
+        write_cached(&path, &input)?;
+    }
+
+    Ok(Some(input))
+}
+
+/// Returns the length and 64-bit FNV-1a hash of `input`, as stored next to a
+/// cached input.
+fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{} {hash:016x}", input.len())
+}
+
+/// Reads the cached input at `path`, if it exists and matches its checksum.
+fn read_cached(path: &Path) -> Option<String> {
+    let input = non_empty(path)?;
+    let expected = std::fs::read_to_string(path.with_extension("sum")).ok()?;
+
+    Some(input).filter(|input| checksum(input) == expected.trim())
+}
+
+/// Caches `input` at `path`. The checksum is written last, so an interrupted
+/// write leaves a mismatch behind rather than a copy that looks complete.
+fn write_cached(path: &Path, input: &str) -> std::io::Result<()> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, input)?;
+    std::fs::write(path.with_extension("sum"), checksum(input))
+}
+
+fn non_empty(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .filter(|input| !input.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aocd_cache_lookup() {
+        let dir = std::env::temp_dir().join(format!("aoc-2024-aocd-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        for user in ["github.alice.1", "github.bob.2"] {
+            std::fs::create_dir_all(dir.join(user)).unwrap();
+        }
+
+        std::fs::write(dir.join("github.alice.1/2024_03_input.txt"), "alice").unwrap();
+        std::fs::write(dir.join("github.bob.2/2024_03_input.txt"), "bob").unwrap();
+        std::fs::write(dir.join("github.bob.2/2024_04_input.txt"), "bob").unwrap();
+        std::fs::write(dir.join("token2id.json"), r#"{"abc": "github.bob.2"}"#).unwrap();
+
+        let user = |token, day| {
+            let path = aocd_path_in(&dir, token, 2024, day)?;
+            let parent = path.parent()?.file_name()?;
+            Some(parent.to_string_lossy().into_owned())
+        };
+
+        assert_eq!(user(None, 3).as_deref(), Some("github.alice.1"));
+        assert_eq!(user(None, 4).as_deref(), Some("github.bob.2"));
+        assert_eq!(user(Some("abc"), 3).as_deref(), Some("github.bob.2"));
+        assert_eq!(user(Some("unknown"), 3).as_deref(), Some("github.alice.1"));
+        assert_eq!(user(Some("abc"), 5), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cached_input_is_verified() {
+        let dir = std::env::temp_dir().join(format!("aoc-2024-cache-{}", std::process::id()));
+        let path = dir.join("2024").join("day01.txt");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        write_cached(&path, "3   4\n4   3\n").unwrap();
+        assert_eq!(read_cached(&path).as_deref(), Some("3   4\n4   3\n"));
+
+        // a truncated copy no longer matches its checksum
+        std::fs::write(&path, "3   4\n4").unwrap();
+        assert_eq!(read_cached(&path), None);
+
+        std::fs::remove_file(path.with_extension("sum")).unwrap();
+        assert_eq!(read_cached(&path), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
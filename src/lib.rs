@@ -1,7 +1,259 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
+// without std, only the days and the modules they share are compiled (see the
+// `std` feature)
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{any::Any, fmt::Display};
+
+use error::AocError;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "std")]
+pub mod answers;
+pub mod arith;
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod client;
+#[cfg(feature = "std")]
+pub mod config;
+pub mod cycle;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod generate;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+#[cfg(feature = "std")]
+pub mod input;
+pub mod memo;
+pub mod numthe;
+pub mod par;
+pub mod parse;
+pub mod progress;
+#[cfg(feature = "std")]
+pub mod puzzle;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod scramble;
+pub mod search;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod timings;
+
+// the `dayNN` modules and the `DAYS` table are generated by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// A single day's puzzle, which parses the raw input once and then solves
+/// each part from the parsed input.
+///
+/// The parsed input's type differs from day to day, so the registry erases
+/// it; passing one day's parsed input to another day's parts panics.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    pub parse: fn(&str) -> Result<Parsed, AocError>,
+    pub part_1: fn(&dyn Any) -> Answer,
+    pub part_2: Option<fn(&dyn Any) -> Answer>,
+    /// Describes the structure of a parsed input, for days that support it.
+    pub stats: Option<fn(&dyn Any) -> Stats>,
+    /// Returns the day's examples, for days declared with `aoc_day!`.
+    pub examples: Option<fn() -> Vec<Example>>,
+}
+
+impl Day {
+    /// Parses `input` and solves part 1.
+    pub fn solve_part_1(&self, input: &str) -> Result<Answer, AocError> {
+        Ok((self.part_1)(&*(self.parse)(input)?))
+    }
+
+    /// Parses `input` and solves part 2, if the day has one.
+    pub fn solve_part_2(&self, input: &str) -> Result<Option<Answer>, AocError> {
+        self.part_2
+            .map(|part_2| Ok(part_2(&*(self.parse)(input)?)))
+            .transpose()
+    }
+}
+
+/// Recovers the concrete type of an input parsed by `parse`, panicking if it
+/// was parsed by some other day.
+fn downcast<T: 'static>(parsed: &dyn Any, _parse: impl Fn(&str) -> Result<T, AocError>) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("parsed input belongs to another day")
+}
+
+/// A parsed input, as returned by [`Day::parse`].
+pub type Parsed = Box<dyn Any>;
+
+/// Named statistics about the structure of an input, like its dimensions.
+pub type Stats = Vec<(&'static str, String)>;
+
+/// The answer to a part of a puzzle, which is what every part returns.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// A number, which is what most puzzles ask for.
+    Int(u64),
+    /// Some text, like a list of names or coordinates.
+    Str(String),
+    /// A picture drawn in characters, with a line for each row, for puzzles
+    /// whose answer has to be read off of a grid.
+    Grid(String),
+}
+
+impl Answer {
+    /// Converts any integer to an answer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is negative or doesn't fit in a `u64`, which no
+    /// puzzle's answer does.
+    pub fn int<T: TryInto<u64>>(value: T) -> Self
+    where
+        T::Error: core::fmt::Debug,
+    {
+        Self::Int(value.try_into().expect("answer out of range"))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Str(text) | Self::Grid(text) => f.write_str(text),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(value as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+// answers are compared with plain literals in the tests
+
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        matches!(self, Self::Int(value) if value == other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Self::Str(text) | Self::Grid(text) if text == other)
+    }
+}
+
+/// A sample input from a puzzle's description, with the answer it documents
+/// for each part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    /// Records the documented answer to `part` for `input`, adding it to an
+    /// existing example with the same input if there is one.
+    fn record(examples: &mut Vec<Example>, input: &'static str, part: usize, answer: String) {
+        let index = match examples.iter().position(|example| example.input == input) {
+            Some(index) => index,
+            None => {
+                examples.push(Example {
+                    input,
+                    answers: [None, None],
+                });
+
+                examples.len() - 1
+            }
+        };
+
+        examples[index].answers[part - 1] = Some(answer);
+    }
+}
+
+/// A day's puzzle solution, normally implemented with the `aoc_day!` macro.
+pub trait Solution {
+    /// The year of the event that the puzzle is from.
+    const YEAR: u16 = 2024;
+
+    /// The day of the month on which the puzzle was released.
+    const DAY: u8;
+
+    /// The representation of the input that both parts work from.
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AocError>;
+    fn part_1(parsed: &Self::Parsed) -> Answer;
+    fn part_2(parsed: &Self::Parsed) -> Answer;
+}
+
+/// Returns the registered day with the given number in the configured year
+/// (see [`config`]), if it was compiled in.
+#[cfg(feature = "std")]
+pub fn day(number: u8) -> Option<&'static Day> {
+    find(config::get().year, number)
+}
+
+/// Returns the registered day of `year` with the given number, if it was
+/// compiled in.
+pub fn find(year: u16, number: u8) -> Option<&'static Day> {
+    DAYS.iter()
+        .find(|day| day.year == year && day.number == number)
+}
+
+/// Returns every registered day of `year`, in order.
+pub fn days(year: u16) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |day| day.year == year)
+}
+
+#[cfg(feature = "cargo-aoc")]
+pub mod cargo_aoc;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// this has to follow the `cargo_aoc` module, since it consumes the parts that
+// the `#[aoc]` attributes register while they are expanded
+#[cfg(feature = "cargo-aoc")]
+aoc_runner_derive::aoc_lib! { year = 2024 }
@@ -0,0 +1,119 @@
+/// Declares the [`Solution`](crate::Solution) for a day, along with its
+/// standard example and golden tests.
+///
+/// The day must define `parse`, which takes the raw input as a `&str` and
+/// returns the given `parsed` type (or an [`AocError`](crate::error::AocError)),
+/// and `part1` and `part2`, which take a reference to it and return an
+/// [`Answer`](crate::Answer). Examples and real answers are optional, and each
+/// one generates a test named after the part it checks; the golden tests read
+/// the real input with [`crate::input::read`], and so need the `std` feature.
+/// They pass without checking anything if the input can't be found, since
+/// inputs aren't redistributed with the code.
+/// The examples are also exposed to the registry through a generated
+/// `examples` function.
+///
+/// Days of years other than 2024 also give their `year` before their `day`.
+///
+/// ```ignore
+/// aoc_day! {
+///     day: 7,
+///     parsed: Vec<Equation>,
+///     example_part_1: EXAMPLE => 3749,
+///     example_part_2: EXAMPLE => 11387,
+///     answer_part_1: 538191549061,
+/// }
+/// ```
+macro_rules! aoc_day {
+    (
+        $(year: $year:literal,)?
+        day: $day:literal,
+        parsed: $parsed:ty,
+        $(example_part_1: $example_1:expr => $expected_1:expr,)?
+        $(example_part_2: $example_2:expr => $expected_2:expr,)?
+        $(answer_part_1: $answer_1:expr,)?
+        $(answer_part_2: $answer_2:expr,)?
+    ) => {
+        /// The registered solution for this day.
+        #[derive(Debug, Clone, Copy)]
+        pub struct Solver;
+
+        impl $crate::Solution for Solver {
+            $(const YEAR: u16 = $year;)?
+            const DAY: u8 = $day;
+
+            type Parsed = $parsed;
+
+            fn parse(input: &str) -> Result<Self::Parsed, $crate::error::AocError> {
+                parse(input)
+            }
+
+            fn part_1(parsed: &Self::Parsed) -> $crate::Answer {
+                part1(parsed)
+            }
+
+            fn part_2(parsed: &Self::Parsed) -> $crate::Answer {
+                part2(parsed)
+            }
+        }
+
+        /// The examples from the puzzle description, with the answers that it
+        /// gives for them.
+        #[allow(unused_mut)]
+        pub fn examples() -> ::alloc::vec::Vec<$crate::Example> {
+            let mut examples = ::alloc::vec::Vec::new();
+            $($crate::Example::record(&mut examples, $example_1, 1, ::alloc::string::ToString::to_string(&$expected_1));)?
+            $($crate::Example::record(&mut examples, $example_2, 2, ::alloc::string::ToString::to_string(&$expected_2));)?
+            examples
+        }
+
+        #[cfg(all(test, feature = "std"))]
+        mod solution_tests {
+            use super::*;
+
+            /// Parses the real input, or returns `None` (and says so) if it
+            /// can't be found, so that the golden tests pass without it.
+            #[allow(dead_code)]
+            fn input() -> Option<$parsed> {
+                let year = <Solver as $crate::Solution>::YEAR;
+                let Some(input) = $crate::input::read_year(year, $day) else {
+                    eprintln!("skipping: no input for {year} day {}", $day);
+                    return None;
+                };
+
+                Some(parse(&input).unwrap())
+            }
+
+            $(
+                #[test]
+                fn example_part_1() {
+                    assert_eq!(part1(&parse($example_1).unwrap()), $expected_1);
+                }
+            )?
+
+            $(
+                #[test]
+                fn part_1() {
+                    if let Some(input) = input() {
+                        assert_eq!(part1(&input), $answer_1);
+                    }
+                }
+            )?
+
+            $(
+                #[test]
+                fn example_part_2() {
+                    assert_eq!(part2(&parse($example_2).unwrap()), $expected_2);
+                }
+            )?
+
+            $(
+                #[test]
+                fn part_2() {
+                    if let Some(input) = input() {
+                        assert_eq!(part2(&input), $answer_2);
+                    }
+                }
+            )?
+        }
+    };
+}
@@ -0,0 +1,150 @@
+//! Caches for memoizing recursive functions.
+//!
+//! A recursive counter takes its cache as an argument, and wraps its body in
+//! [`Memo::get_or_insert_with`] (or the same method on [`DenseMemo`]), which
+//! only runs the body the first time a key is seen and hands the cache back
+//! to it for the recursive calls:
+//!
+//! ```
+//! use aoc_2024::memo::Memo;
+//!
+//! fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     match n {
+//!         0 | 1 => n,
+//!         n => memo.get_or_insert_with(n, |memo| {
+//!             fibonacci(memo, n - 1) + fibonacci(memo, n - 2)
+//!         }),
+//!     }
+//! }
+//!
+//! assert_eq!(fibonacci(&mut Memo::default(), 90), 2880067194370816120);
+//! ```
+//!
+//! [`Memo`] takes any hashable key, while [`DenseMemo`] is for keys that are
+//! already small indices (like a position in a string), and stores the values
+//! in a slice of a fixed length instead.
+
+use alloc::{boxed::Box, vec};
+use core::hash::Hash;
+
+use crate::hash::HashMap;
+
+/// A cache of the values of a function for any hashable keys.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    /// Returns the value cached for `key`, or computes it with `f` (which can
+    /// use the cache for other keys) and caches it if there isn't one.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(self);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the number of values cached.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// A cache of the values of a function for the keys `0..len`.
+#[derive(Debug, Clone)]
+pub struct DenseMemo<V> {
+    values: Box<[Option<V>]>,
+}
+
+impl<V: Clone> DenseMemo<V> {
+    /// Creates an empty cache for the keys `0..len`.
+    pub fn new(len: usize) -> Self {
+        Self {
+            values: vec![None; len].into_boxed_slice(),
+        }
+    }
+
+    /// Returns the value cached for `key`, or computes it with `f` (which can
+    /// use the cache for other keys) and caches it if there isn't one.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is out of the cache's range.
+    pub fn get_or_insert_with(&mut self, key: usize, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = &self.values[key] {
+            return value.clone();
+        }
+
+        let value = f(self);
+        self.values[key] = Some(value.clone());
+        value
+    }
+
+    /// Returns the number of keys the cache has room for.
+    pub fn capacity(&self) -> usize {
+        self.values.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the lattice paths from `(row, col)` to `(0, 0)`, without
+    /// caching, and with each kind of cache.
+    fn paths(row: usize, col: usize) -> u64 {
+        match (row, col) {
+            (0, _) | (_, 0) => 1,
+            _ => paths(row - 1, col) + paths(row, col - 1),
+        }
+    }
+
+    fn memo_paths(memo: &mut Memo<(usize, usize), u64>, row: usize, col: usize) -> u64 {
+        match (row, col) {
+            (0, _) | (_, 0) => 1,
+            _ => memo.get_or_insert_with((row, col), |memo| {
+                memo_paths(memo, row - 1, col) + memo_paths(memo, row, col - 1)
+            }),
+        }
+    }
+
+    fn dense_paths(memo: &mut DenseMemo<u64>, width: usize, row: usize, col: usize) -> u64 {
+        match (row, col) {
+            (0, _) | (_, 0) => 1,
+            _ => memo.get_or_insert_with(row * width + col, |memo| {
+                dense_paths(memo, width, row - 1, col) + dense_paths(memo, width, row, col - 1)
+            }),
+        }
+    }
+
+    #[test]
+    fn lattice_paths() {
+        let mut memo = Memo::default();
+        assert_eq!(memo_paths(&mut memo, 6, 7), paths(6, 7));
+        assert_eq!(memo.len(), 42);
+
+        // later calls reuse what earlier ones cached
+        assert_eq!(memo_paths(&mut memo, 5, 5), paths(5, 5));
+        assert_eq!(memo.len(), 42);
+
+        let mut dense = DenseMemo::new(7 * 8);
+        assert_eq!(dense_paths(&mut dense, 8, 6, 7), paths(6, 7));
+        assert_eq!(memo_paths(&mut memo, 30, 30), 118264581564861424);
+    }
+}
@@ -0,0 +1,184 @@
+//! Number theory on `i64`s: greatest common divisors, modular arithmetic, and
+//! the Chinese remainder theorem.
+//!
+//! Moduli must be positive, and residues are always returned in `0..m`.
+//! Products are taken in `i128`, so nothing here overflows for moduli that
+//! fit in an `i64` (although [`lcm`] and [`crt`] can produce one that doesn't,
+//! and panic if so).
+
+/// Returns the greatest common divisor of `a` and `b`, which is never
+/// negative. The gcd of `0` and `0` is `0`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    i64::try_from(a).expect("gcd overflowed")
+}
+
+/// Returns the least common multiple of `a` and `b`, which is never
+/// negative. The lcm of anything and `0` is `0`.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    match gcd(a, b) {
+        0 => 0,
+        g => (a / g).checked_mul(b).expect("lcm overflowed").abs(),
+    }
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the
+/// [`gcd`] of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// Divides `lhs` by `rhs`, rounding towards negative infinity.
+pub fn div_floor(lhs: i64, rhs: i64) -> i64 {
+    let (q, r) = (lhs / rhs, lhs % rhs);
+    if r != 0 && (r < 0) != (rhs < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Divides `lhs` by `rhs`, rounding towards positive infinity.
+pub fn div_ceil(lhs: i64, rhs: i64) -> i64 {
+    -div_floor(-lhs, rhs)
+}
+
+/// Returns `a * b` modulo `m`.
+pub fn mod_mul(a: i64, b: i64, m: i64) -> i64 {
+    (i128::from(a) * i128::from(b)).rem_euclid(i128::from(m)) as i64
+}
+
+/// Returns `base` to the power of `exp`, modulo `m`.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Returns the `x` in `0..m` with `a * x = 1 (mod m)`, if `a` and `m` are
+/// coprime (and otherwise there isn't one).
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    Some(x.rem_euclid(m)).filter(|_| g == 1)
+}
+
+/// Solves the system of congruences `x = a (mod m)` given as `(a, m)` pairs,
+/// returning the smallest non-negative solution `x` together with the
+/// [`lcm`] of the moduli, modulo which it's unique.
+///
+/// The moduli don't have to be coprime, but if they aren't then the
+/// congruences might conflict, in which case there's no solution. An empty
+/// system is solved by `(0, 1)`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, modulus), (a, m)| {
+            let (g, p, _) = extended_gcd(modulus, m);
+            let difference = a - x;
+
+            if difference % g != 0 {
+                return None;
+            }
+
+            // x + modulus * k satisfies the new congruence, where k solves
+            // (modulus / g) * k = difference / g (mod m / g)
+            let step = m / g;
+            let k = mod_mul(difference / g, p, step);
+            let combined = lcm(modulus, m);
+
+            let x = (i128::from(x) + i128::from(modulus) * i128::from(k))
+                .rem_euclid(i128::from(combined)) as i64;
+
+            Some((x, combined))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        for (a, b) in [(240, 46), (-240, 46), (46, -240), (7, 0), (0, -7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn rounding_division() {
+        assert_eq!((div_floor(7, 2), div_ceil(7, 2)), (3, 4));
+        assert_eq!((div_floor(-7, 2), div_ceil(-7, 2)), (-4, -3));
+        assert_eq!((div_floor(7, -2), div_ceil(7, -2)), (-4, -3));
+        assert_eq!((div_floor(-6, 2), div_ceil(-6, 2)), (-3, -3));
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(2, 64, i64::MAX), 2);
+        assert_eq!(mod_mul(i64::MAX - 1, i64::MAX - 1, i64::MAX), 1);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(3, 101), (5, 103)]), Some((10305, 10403)));
+        assert_eq!(
+            crt([(8085 % 101, 101), (8085 % 103, 103)]),
+            Some((8085, 10403))
+        );
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+
+        // moduli that share a factor must agree on it
+        assert_eq!(crt([(1, 4), (1, 6)]), Some((1, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
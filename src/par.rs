@@ -0,0 +1,143 @@
+//! Parallel iteration, which falls back to ordinary sequential iterators when
+//! the `rayon` feature is disabled.
+//!
+//! Days should glob-import this module in place of the `rayon` prelude, and
+//! take their sequential path instead when [`is_sequential`] is set, so that
+//! their results and timings can be reproduced on a single thread.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "rayon")]
+pub use rayon::{
+    iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator},
+    str::ParallelString,
+};
+
+#[cfg(not(feature = "rayon"))]
+pub use sequential::*;
+
+static SEQUENTIAL: AtomicBool = AtomicBool::new(false);
+
+/// Makes the days bypass their parallel code paths, and solve everything on
+/// the calling thread.
+pub fn set_sequential(sequential: bool) {
+    SEQUENTIAL.store(sequential, Ordering::Relaxed);
+}
+
+/// Returns whether the days should solve everything on the calling thread,
+/// which they always do without the `rayon` feature.
+pub fn is_sequential() -> bool {
+    cfg!(not(feature = "rayon")) || SEQUENTIAL.load(Ordering::Relaxed)
+}
+
+/// Sets the number of threads that parallel iterators use, returning `false`
+/// if they have already started (or if they run sequentially anyway).
+pub fn set_threads(threads: usize) -> bool {
+    #[cfg(feature = "rayon")]
+    return rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .is_ok();
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        let _ = threads;
+        false
+    }
+}
+
+/// Calls `f` on every item at once, returning the results in order, or on
+/// one item after another if [`is_sequential`] is set.
+///
+/// With the `rayon` feature the calls are jobs on rayon's thread pool, so any
+/// parallel iterators within them share the pool's threads rather than each
+/// starting as many again. Without it, each call gets a thread of its own.
+#[cfg(feature = "std")]
+pub fn map_concurrent<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> R + Send + Sync,
+) -> alloc::vec::Vec<R> {
+    if is_sequential() {
+        return items.iter().map(f).collect();
+    }
+
+    #[cfg(feature = "rayon")]
+    return items.par_iter().map(f).collect();
+
+    #[cfg(not(feature = "rayon"))]
+    std::thread::scope(|scope| {
+        let f = &f;
+        let handles = items
+            .iter()
+            .map(|item| scope.spawn(move || f(item)))
+            .collect::<alloc::vec::Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[cfg(not(feature = "rayon"))]
+mod sequential {
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, T: 'a + ?Sized> IntoParallelRefIterator<'a> for T
+    where
+        &'a T: IntoIterator,
+    {
+        type Iter = <&'a T as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    pub trait ParallelString {
+        fn par_split_terminator(&self, terminator: char) -> core::str::SplitTerminator<'_, char>;
+    }
+
+    impl ParallelString for str {
+        fn par_split_terminator(&self, terminator: char) -> core::str::SplitTerminator<'_, char> {
+            self.split_terminator(terminator)
+        }
+    }
+
+    /// Sequential versions of the `rayon` adaptors that have no direct
+    /// [`Iterator`] equivalent.
+    pub trait ParallelIterator: Iterator + Sized {
+        fn map_with<T, R>(
+            self,
+            mut init: T,
+            mut f: impl FnMut(&mut T, Self::Item) -> R,
+        ) -> impl Iterator<Item = R> {
+            self.map(move |item| f(&mut init, item))
+        }
+
+        fn reduce_with(
+            self,
+            op: impl FnMut(Self::Item, Self::Item) -> Self::Item,
+        ) -> Option<Self::Item> {
+            self.reduce(op)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+}
@@ -0,0 +1,184 @@
+//! Shared helpers for parsing puzzle inputs.
+
+use alloc::vec::Vec;
+use core::{fmt::Display, str::FromStr};
+
+use crate::error::AocError;
+
+/// A section of a puzzle input, delimited by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The position of this block in the input, starting from zero.
+    pub index: usize,
+    /// The text of the block, without any surrounding blank lines.
+    pub text: &'a str,
+}
+
+/// An iterator over the blank-line-separated blocks of an input, created by
+/// [`blocks`].
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    rest: &'a str,
+    index: usize,
+}
+
+/// Splits `s` at the end of its first line.
+fn split_line(s: &str) -> (&str, &str) {
+    match s.find('\n') {
+        Some(end) => (&s[..end], &s[end + 1..]),
+        None => (s, ""),
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip any blank lines before the block
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let (line, rest) = split_line(self.rest);
+            if !line.trim().is_empty() {
+                break;
+            }
+
+            self.rest = rest;
+        }
+
+        let start = self.rest;
+        let mut len = 0;
+
+        while !self.rest.is_empty() {
+            let (line, rest) = split_line(self.rest);
+            if line.trim().is_empty() {
+                break;
+            }
+
+            len += self.rest.len() - rest.len();
+            self.rest = rest;
+        }
+
+        let block = Block {
+            index: self.index,
+            text: start[..len].trim_end(),
+        };
+
+        self.index += 1;
+        Some(block)
+    }
+}
+
+/// Returns an iterator over the sections of `input` that are separated by one
+/// or more blank lines. Lines containing only whitespace count as blank, so
+/// inputs with `\r\n` line endings or trailing spaces are handled too.
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks {
+        rest: input,
+        index: 0,
+    }
+}
+
+/// Parses every non-blank line of `input` as a `T`, ignoring surrounding
+/// whitespace, and moving any error to where it is in `input`.
+pub fn parse_lines<T: FromStr<Err = AocError>>(input: &str) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse()
+                .map_err(|error: AocError| error.within(input, line))
+        })
+        .collect()
+}
+
+/// Parses a number, with an error pointing at `s` (a slice of `input`) if it
+/// isn't one.
+pub fn number<T: FromStr>(input: &str, s: &str) -> Result<T, AocError>
+where
+    T::Err: Display,
+{
+    s.parse()
+        .map_err(|error| AocError::at(input, s, format_args!("invalid number {s:?}: {error}")))
+}
+
+/// Parses the unsigned decimal integer at the start of `bytes`, returning it
+/// along with the bytes that follow it.
+///
+/// This skips the UTF-8 validation and sign handling of `str::parse`, which
+/// adds up when an input is mostly numbers. If `bytes` doesn't start with a
+/// digit then this returns zero and all of `bytes`. Like C's `atoi`, it
+/// doesn't check for overflow, and a value too large for a `u64` wraps; use
+/// [`parse_uint_checked`] where that matters.
+#[inline]
+pub fn parse_uint(bytes: &[u8]) -> (u64, &[u8]) {
+    let (digits, rest) = split_digits(bytes);
+    let value = digits.iter().fold(0u64, |value, &digit| {
+        value.wrapping_mul(10).wrapping_add((digit - b'0') as u64)
+    });
+
+    (value, rest)
+}
+
+/// Like [`parse_uint`], but the value is `None` if it's too large for a `u64`
+/// (although its digits are still skipped).
+#[inline]
+pub fn parse_uint_checked(bytes: &[u8]) -> (Option<u64>, &[u8]) {
+    let (digits, rest) = split_digits(bytes);
+    let value = digits.iter().try_fold(0u64, |value, &digit| {
+        value.checked_mul(10)?.checked_add((digit - b'0') as u64)
+    });
+
+    (value, rest)
+}
+
+/// Splits the ASCII digits at the start of `bytes` from the bytes after them.
+#[inline]
+fn split_digits(bytes: &[u8]) -> (&[u8], &[u8]) {
+    let len = bytes
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit())
+        .count();
+
+    bytes.split_at(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_blank_lines() {
+        let input = "\na\nb\n\n\nc\r\n \r\nd\n";
+        let texts = blocks(input).map(|block| block.text).collect::<Vec<_>>();
+
+        assert_eq!(texts, ["a\nb", "c", "d"]);
+        assert_eq!(blocks(input).nth(2).map(|block| block.index), Some(2));
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn parse_uint_stops_at_non_digits() {
+        assert_eq!(parse_uint(b"1234|56"), (1234, &b"|56"[..]));
+        assert_eq!(parse_uint(b"007"), (7, &b""[..]));
+        assert_eq!(parse_uint(b"-1"), (0, &b"-1"[..]));
+        assert_eq!(parse_uint(b"18446744073709551615"), (u64::MAX, &b""[..]));
+        assert_eq!(parse_uint(b"18446744073709551616:"), (0, &b":"[..]));
+    }
+
+    #[test]
+    fn parse_uint_checked_rejects_overflow() {
+        assert_eq!(parse_uint_checked(b"1234|56"), (Some(1234), &b"|56"[..]));
+        assert_eq!(
+            parse_uint_checked(b"18446744073709551615"),
+            (Some(u64::MAX), &b""[..])
+        );
+        assert_eq!(
+            parse_uint_checked(b"18446744073709551616:"),
+            (None, &b":"[..])
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Progress reporting for slow solvers.
+//!
+//! The parts are plain functions called through the [`DAYS`](crate::DAYS)
+//! registry, so rather than threading a callback through every one of them, a
+//! slow part reports into a process-wide [`Progress`] that the caller installs
+//! once with [`set_reporter`]. Without one, reporting does nothing, and so it
+//! always does without std.
+
+#[cfg(feature = "std")]
+use std::{boxed::Box, sync::OnceLock};
+
+/// Receives progress updates from slow solvers. Updates can arrive from
+/// several threads at once.
+pub trait Progress: Send + Sync {
+    /// Called when a solver starts on `total` units of work.
+    fn start(&self, total: usize);
+
+    /// Called as a solver completes `done` more units of work.
+    fn advance(&self, done: usize);
+
+    /// Called when a solver has finished.
+    fn finish(&self);
+}
+
+#[cfg(feature = "std")]
+static REPORTER: OnceLock<Box<dyn Progress>> = OnceLock::new();
+
+/// Installs `reporter` for the rest of the process, returning `false` (and
+/// leaving the existing one in place) if one was already installed.
+#[cfg(feature = "std")]
+pub fn set_reporter(reporter: impl Progress + 'static) -> bool {
+    REPORTER.set(Box::new(reporter)).is_ok()
+}
+
+/// Returns the installed reporter, if there is one.
+#[inline]
+fn reporter() -> Option<&'static dyn Progress> {
+    #[cfg(feature = "std")]
+    return REPORTER.get().map(|reporter| &**reporter);
+
+    #[cfg(not(feature = "std"))]
+    None
+}
+
+/// Reports that a solver is starting on `total` units of work.
+pub fn start(total: usize) {
+    if let Some(reporter) = reporter() {
+        reporter.start(total);
+    }
+}
+
+/// Reports that a solver has completed `done` more units of work.
+#[inline]
+pub fn advance(done: usize) {
+    if let Some(reporter) = reporter() {
+        reporter.advance(done);
+    }
+}
+
+/// Reports that a solver has finished.
+pub fn finish() {
+    if let Some(reporter) = reporter() {
+        reporter.finish();
+    }
+}
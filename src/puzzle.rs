@@ -0,0 +1,155 @@
+//! Puzzle descriptions, and the example inputs and answers in them.
+//!
+//! [`fetch`] downloads the page for a day into a cache next to the downloaded
+//! inputs, under `$XDG_CACHE_HOME/aoc-2024/puzzles/<year>` (or `~/.cache/...`).
+//! The description of part 2 only appears once part 1 is solved, so the page
+//! has to be downloaded again (with `refresh`) to see it.
+//!
+//! Everything here works with the configured year.
+
+use std::path::{Path, PathBuf};
+
+use crate::{client, config, input};
+
+/// The candidate examples in a puzzle's description.
+///
+/// These are found by where they appear in the page rather than by what they
+/// mean, so they need checking before being relied on: some days have several
+/// examples, or put other things in code blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The text of each `<pre><code>` block, which are usually example inputs.
+    pub examples: Vec<String>,
+    /// The last emphasized code in the description of each part, which is
+    /// usually the answer for the example.
+    pub answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Extracts the candidate examples from the HTML page of a puzzle.
+    pub fn parse(page: &str) -> Self {
+        let examples = between(page, "<pre><code>", "</code></pre>")
+            .map(|(_, block)| text(block))
+            .collect();
+
+        let articles = between(page, "<article class=\"day-desc\">", "</article>");
+        let mut answers = articles.map(|(_, article)| {
+            between(article, "<code><em>", "</em></code>")
+                .chain(between(article, "<em><code>", "</code></em>"))
+                .map(|(start, answer)| (start, text(answer)))
+                .max_by_key(|&(start, _)| start)
+                .map(|(_, answer)| answer)
+        });
+
+        Self {
+            examples,
+            answers: [answers.next().flatten(), answers.next().flatten()],
+        }
+    }
+}
+
+/// Returns each piece of `html` between `start` and `end`, along with the
+/// offset of the `start` before it.
+fn between<'a>(
+    html: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    html.match_indices(start).filter_map(move |(at, _)| {
+        let (inside, _) = html[at + start.len()..].split_once(end)?;
+        Some((at, inside))
+    })
+}
+
+/// Returns the text of `html`, without its tags and with its character
+/// references resolved.
+fn text(html: &str) -> String {
+    client::strip_tags(html)
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Returns the path that the page for `day` is cached at once downloaded,
+/// whether or not it exists.
+pub fn path(day: u8) -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&std::env::var_os("HOME")?).join(".cache"),
+    };
+
+    let dir = base
+        .join("aoc-2024")
+        .join("puzzles")
+        .join(config::get().year.to_string());
+
+    Some(dir.join(format!("day{day:02}.html")))
+}
+
+/// Reads the cached page for `day`, if it has been downloaded.
+pub fn read(day: u8) -> Option<String> {
+    std::fs::read_to_string(path(day)?).ok()
+}
+
+/// Reads the page for `day` like [`read`], but downloads it first if it isn't
+/// cached, or if `refresh` is set.
+///
+/// Without a session token this just returns `Ok(None)` for a missing page.
+pub fn fetch(day: u8, refresh: bool) -> Result<Option<String>, client::Error> {
+    if !refresh {
+        if let Some(page) = read(day) {
+            return Ok(Some(page));
+        }
+    }
+
+    let session = match input::session_token() {
+        Some(session) => session,
+        None if refresh => return Err(client::Error::NoSession),
+        None => return Ok(None),
+    };
+
+    let page = client::download_puzzle(day, &session)?;
+
+    if let Some(path) = path(day) {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, &page)?;
+    }
+
+    Ok(Some(page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>The total distance is <code>2</code> plus <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>2066446</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a &lt;<em>b</em>&gt; &amp; c
+</code></pre>
+<p>So, the similarity score is <em><code>31</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn puzzle_page() {
+        let puzzle = Puzzle::parse(PAGE);
+
+        assert_eq!(puzzle.examples, ["3   4\n4   3\n", "a <b> & c\n"]);
+        assert_eq!(
+            puzzle.answers,
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+
+        assert_eq!(Puzzle::parse("<main></main>"), Puzzle::default());
+    }
+}
@@ -0,0 +1,195 @@
+//! Drawing grids as images, to look at a day's state rather than its answer.
+//!
+//! A day's grid implements [`Renderable`] by picking a colour for each of its
+//! cells, and [`Renderable::render`] draws it as an [`Image`] with a square of
+//! pixels per cell. An image can be saved as a PNG, and a sequence of them
+//! (say, a frame per step of a simulation) as an animated GIF with a
+//! [`GifEncoder`].
+//!
+//! Both encoders are written out here rather than taken from a crate, since
+//! images of a few flat colours need very little of either format. The PNGs
+//! aren't compressed at all, so prefer a small scale for large grids.
+
+use alloc::{vec, vec::Vec};
+use core::time::Duration;
+
+mod gif;
+mod png;
+
+/// A colour, as its red, green and blue components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+}
+
+/// A rectangle of pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Creates an image filled with `background`.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the pixel at `(row, col)`, if it's in the image.
+    pub fn pixel(&self, (row, col): (usize, usize)) -> Option<Rgb> {
+        (row < self.height && col < self.width).then(|| self.pixels[row * self.width + col])
+    }
+
+    /// Colours the pixel at `(row, col)`, which must be in the image.
+    pub fn set_pixel(&mut self, (row, col): (usize, usize), color: Rgb) {
+        assert!(row < self.height && col < self.width, "pixel out of bounds");
+        self.pixels[row * self.width + col] = color;
+    }
+
+    /// Encodes the image as a PNG.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image is empty, which PNG can't represent.
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self)
+    }
+}
+
+/// A grid that can be drawn as an image.
+pub trait Renderable {
+    /// Returns the number of rows and columns of cells.
+    fn shape(&self) -> (usize, usize);
+
+    /// Returns the colour of the cell at `(row, col)`.
+    fn color(&self, position: (usize, usize)) -> Rgb;
+
+    /// Draws the grid with each cell as a `scale` by `scale` square.
+    fn render(&self, scale: usize) -> Image {
+        let (rows, cols) = self.shape();
+        let mut image = Image::new(cols * scale, rows * scale, Rgb::BLACK);
+
+        for row in 0..rows {
+            for col in 0..cols {
+                let color = self.color((row, col));
+
+                for y in row * scale..(row + 1) * scale {
+                    image.pixels[y * image.width..][col * scale..(col + 1) * scale].fill(color);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+/// Builds an animated GIF a frame at a time, so that long animations don't
+/// have to be held in memory as images.
+#[derive(Debug, Clone)]
+pub struct GifEncoder {
+    bytes: Vec<u8>,
+    width: u16,
+    height: u16,
+    /// The time each frame is shown for, in hundredths of a second.
+    delay: u16,
+}
+
+impl GifEncoder {
+    /// Starts an endlessly looping animation of `width` by `height` frames,
+    /// each shown for `delay` (rounded down to a hundredth of a second).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frames would be empty, or over 65535 pixels wide or tall.
+    pub fn new(width: usize, height: usize, delay: Duration) -> Self {
+        assert!(width > 0 && height > 0, "GIF frames can't be empty");
+        let width = u16::try_from(width).expect("the frames are too wide for a GIF");
+        let height = u16::try_from(height).expect("the frames are too tall for a GIF");
+        let delay = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+
+        Self {
+            bytes: gif::header(width, height),
+            width,
+            height,
+            delay,
+        }
+    }
+
+    /// Adds `frame` to the end of the animation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` isn't the size given to [`GifEncoder::new`], or has
+    /// more than 256 colours.
+    pub fn push(&mut self, frame: &Image) {
+        assert!(
+            (frame.width, frame.height) == (self.width.into(), self.height.into()),
+            "every frame of a GIF has to be the same size"
+        );
+
+        gif::write_frame(&mut self.bytes, frame, self.delay);
+    }
+
+    /// Finishes the animation, returning the GIF.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(gif::TRAILER);
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Checkerboard;
+
+    impl Renderable for Checkerboard {
+        fn shape(&self) -> (usize, usize) {
+            (2, 3)
+        }
+
+        fn color(&self, (row, col): (usize, usize)) -> Rgb {
+            match (row + col) % 2 {
+                0 => Rgb::WHITE,
+                _ => Rgb::BLACK,
+            }
+        }
+    }
+
+    #[test]
+    fn rendering() {
+        let image = Checkerboard.render(2);
+
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.pixel((1, 1)), Some(Rgb::WHITE));
+        assert_eq!(image.pixel((1, 2)), Some(Rgb::BLACK));
+        assert_eq!(image.pixel((3, 3)), Some(Rgb::WHITE));
+        assert_eq!(image.pixel((4, 0)), None);
+
+        assert!(image.to_png().starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut gif = GifEncoder::new(6, 4, Duration::from_millis(250));
+        gif.push(&image);
+        gif.push(&Image::new(6, 4, Rgb(1, 2, 3)));
+        let gif = gif.finish();
+
+        assert!(gif.starts_with(b"GIF89a\x06\x00\x04\x00"));
+        assert_eq!(gif.last(), Some(&gif::TRAILER));
+    }
+}
@@ -0,0 +1,241 @@
+//! A GIF encoder, which gives each frame its own palette and compresses it
+//! with LZW.
+
+use alloc::vec::Vec;
+
+use super::{Image, Rgb};
+use crate::hash::HashMap;
+
+pub(super) const TRAILER: u8 = 0x3b;
+
+/// The largest code LZW can use, since codes are at most 12 bits.
+const MAX_CODE: u16 = 4095;
+
+/// Returns the header of an endlessly looping animation.
+pub(super) fn header(width: u16, height: u16) -> Vec<u8> {
+    let mut bytes = b"GIF89a".to_vec();
+    bytes.extend(width.to_le_bytes());
+    bytes.extend(height.to_le_bytes());
+    // no global palette, 8 bits per colour, and the default background and
+    // aspect ratio
+    bytes.extend([0x70, 0, 0]);
+
+    // the NETSCAPE2.0 extension, which asks for the animation to loop forever
+    bytes.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0");
+    bytes
+}
+
+pub(super) fn write_frame(bytes: &mut Vec<u8>, frame: &Image, delay: u16) {
+    let mut palette = Vec::new();
+    let mut indices = HashMap::default();
+    let pixels = frame
+        .pixels
+        .iter()
+        .map(|&pixel| {
+            *indices.entry(pixel).or_insert_with(|| {
+                palette.push(pixel);
+                palette.len() - 1
+            }) as u8
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        palette.len() <= 256,
+        "GIF frames can have at most 256 colours"
+    );
+
+    // palettes have a power of two entries, and LZW needs at least 2 bits
+    let depth = palette.len().next_power_of_two().trailing_zeros().max(1) as u8;
+    palette.resize(1 << depth, Rgb::BLACK);
+
+    // the graphic control extension, which only sets the frame's delay
+    bytes.extend([0x21, 0xf9, 4, 0]);
+    bytes.extend(delay.to_le_bytes());
+    bytes.extend([0, 0]);
+
+    // the image descriptor, covering the whole screen and followed by the
+    // frame's own palette
+    bytes.push(0x2c);
+    bytes.extend([0; 4]);
+    bytes.extend((frame.width as u16).to_le_bytes());
+    bytes.extend((frame.height as u16).to_le_bytes());
+    bytes.push(0x80 | (depth - 1));
+    bytes.extend(palette.iter().flat_map(|color| [color.0, color.1, color.2]));
+
+    let min_code_size = depth.max(2);
+    bytes.push(min_code_size);
+
+    for block in compress(&pixels, min_code_size).chunks(255) {
+        bytes.push(block.len() as u8);
+        bytes.extend(block);
+    }
+
+    bytes.push(0);
+}
+
+/// Compresses palette indices with GIF's variant of LZW, in which codes
+/// grow a bit wider each time the table outgrows them, and the table is
+/// cleared once it fills up.
+fn compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter::default();
+    let mut table = HashMap::default();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+
+    writer.write(clear, width);
+
+    let mut indices = indices.iter().copied();
+    if let Some(first) = indices.next() {
+        let mut prefix = u16::from(first);
+
+        for index in indices {
+            if let Some(&code) = table.get(&(prefix, index)) {
+                prefix = code;
+                continue;
+            }
+
+            writer.write(prefix, width);
+            // widen the codes once the next one to be added no longer fits,
+            // which is when a decoder (a code behind) will expect it
+            if next == 1 << width {
+                width += 1;
+            }
+
+            if next == MAX_CODE {
+                writer.write(clear, width);
+                table.clear();
+                next = end + 1;
+                width = min_code_size + 1;
+            } else {
+                table.insert((prefix, index), next);
+                next += 1;
+            }
+
+            prefix = u16::from(index);
+        }
+
+        writer.write(prefix, width);
+        if next == 1 << width {
+            width += 1;
+        }
+    }
+
+    writer.write(end, width);
+    writer.finish()
+}
+
+/// Packs codes into bytes, starting from the least significant bit.
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.len;
+        self.len += width;
+
+        while self.len >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::*;
+    use crate::rng::Rng;
+
+    /// Decompresses codes as a GIF decoder would, to check that they survive
+    /// the round trip.
+    fn decompress(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let initial = (0..clear)
+            .map(|index| vec![index as u8])
+            .collect::<Vec<_>>();
+
+        let mut table = initial.clone();
+        let mut width = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+        let mut bit = 0;
+
+        loop {
+            let code = (0..width as usize).fold(0, |code, i| {
+                let at = bit + i;
+                code | usize::from(bytes[at / 8] >> (at % 8) & 1) << i
+            });
+            bit += width as usize;
+
+            if code == clear {
+                table.clone_from(&initial);
+                // the clear and end codes themselves
+                table.extend([vec![], vec![]]);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            } else if code == end {
+                return output;
+            }
+
+            let entry = match table.get(code) {
+                Some(entry) => entry.clone(),
+                None => {
+                    let mut entry = previous.clone().expect("a code ahead of the table");
+                    entry.push(entry[0]);
+                    entry
+                }
+            };
+
+            if let Some(mut previous) = previous {
+                previous.push(entry[0]);
+                table.push(previous);
+
+                if table.len() == 1 << width && width < 12 {
+                    width += 1;
+                }
+            }
+
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut rng = Rng::new(2024);
+
+        for (len, colors, min_code_size) in [(0, 4, 2), (1, 4, 2), (5000, 4, 2), (20000, 256, 8)] {
+            let indices = (0..len)
+                .map(|_| rng.below(colors) as u8)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                decompress(&compress(&indices, min_code_size), min_code_size),
+                indices
+            );
+        }
+
+        // long runs fill the table with ever longer strings
+        let runs = vec![3; 100_000];
+        assert_eq!(decompress(&compress(&runs, 2), 2), runs);
+    }
+}
@@ -0,0 +1,159 @@
+//! A PNG encoder for truecolour images, which stores the pixel data
+//! uncompressed.
+
+use alloc::vec::Vec;
+
+use super::Image;
+
+const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// The longest block of uncompressed data that deflate allows.
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+pub(super) fn encode(image: &Image) -> Vec<u8> {
+    assert!(image.width > 0 && image.height > 0, "PNGs can't be empty");
+    let width = u32::try_from(image.width).expect("the image is too wide for a PNG");
+    let height = u32::try_from(image.height).expect("the image is too tall for a PNG");
+
+    // each row starts with the filter it was encoded with, which is always none
+    let mut rows = Vec::with_capacity(image.height * (1 + 3 * image.width));
+    for row in image.pixels.chunks(image.width) {
+        rows.push(0);
+        rows.extend(row.iter().flat_map(|pixel| [pixel.0, pixel.1, pixel.2]));
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per sample of RGB, and the default compression, filtering and
+    // (lack of) interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&rows));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let len = u32::try_from(data.len()).expect("PNG chunks are at most 4GiB");
+    png.extend(len.to_be_bytes());
+
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED_BLOCK).max(1);
+    let mut stream = Vec::with_capacity(data.len() + 5 * blocks + 6);
+
+    // deflate with a 32KiB window, and a check value that makes the pair a
+    // multiple of 31
+    stream.extend([0x78, 0x01]);
+
+    let mut chunks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let is_final = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+
+        stream.push(u8::from(is_final));
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(chunk);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut byte = 0;
+
+    while byte < 256 {
+        let mut crc = byte as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[byte] = crc;
+        byte += 1;
+    }
+
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MODULUS;
+        (a, (b + a) % MODULUS)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::render::Rgb;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7; MAX_STORED_BLOCK + 10];
+        let stream = zlib_stored(&data);
+
+        // two blocks, only the second of which is final
+        assert_eq!(stream.len(), 2 + 2 * 5 + data.len() + 4);
+        assert_eq!(stream[2..7], [0, 0xff, 0xff, 0, 0]);
+        assert_eq!(stream[7 + MAX_STORED_BLOCK..][..5], [1, 10, 0, 0xf5, 0xff]);
+        assert_eq!(
+            zlib_stored(&[]),
+            [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn single_pixel() {
+        let png = encode(&Image::new(1, 1, Rgb(255, 0, 0)));
+
+        assert_eq!(png[..8], *SIGNATURE);
+        assert_eq!(
+            png[8..33],
+            *b"\0\0\0\x0dIHDR\0\0\0\x01\0\0\0\x01\x08\x02\0\0\0\x90\x77\x53\xde"
+        );
+        assert_eq!(png[png.len() - 12..], *b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+}
@@ -0,0 +1,379 @@
+//! Anonymizing puzzle inputs so that they can be shared.
+//!
+//! Each supported day applies transformations that keep the input valid and
+//! structurally similar (shuffling independent lines, renaming identifiers,
+//! reflecting grids), but the answers may still change, so callers should
+//! recompute them from the scrambled input.
+
+use crate::{parse, rng::Rng};
+
+/// The days that [`scramble`] knows how to transform.
+pub const SUPPORTED_DAYS: &[u8] = &[1, 2, 4, 5, 7, 11, 12, 13, 14, 18, 19, 20, 22, 23, 24];
+
+/// Scrambles the input for the given day, or returns `None` if the day is not
+/// supported (see [`SUPPORTED_DAYS`]).
+pub fn scramble(day: u8, input: &str, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let rng = &mut rng;
+
+    let output = match day {
+        1 => shuffle_columns(input, rng),
+        2 => shuffle_reports(input, rng),
+        4 | 20 => reflect_grid(input, rng),
+        5 => remap_pages(input, rng),
+        7 | 14 | 22 => shuffle_lines(input, rng),
+        11 => shuffle_words(input, rng),
+        12 => remap_plants(&reflect_grid(input, rng), rng),
+        13 => shuffle_blocks(input, rng),
+        18 => mirror_bytes(input),
+        19 => remap_colors(input, rng),
+        23 => remap_computers(input, rng),
+        24 => rename_wires(input, rng),
+        _ => return None,
+    };
+
+    Some(output)
+}
+
+fn lines(input: &str) -> Vec<&str> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn join_lines(lines: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    lines.into_iter().fold(String::new(), |mut output, line| {
+        output.push_str(line.as_ref());
+        output.push('\n');
+        output
+    })
+}
+
+fn shuffle_lines(input: &str, rng: &mut Rng) -> String {
+    let mut lines = lines(input);
+    rng.shuffle(&mut lines);
+    join_lines(lines)
+}
+
+fn shuffle_words(input: &str, rng: &mut Rng) -> String {
+    let mut words = input.split_whitespace().collect::<Vec<_>>();
+    rng.shuffle(&mut words);
+    words.join(" ") + "\n"
+}
+
+fn shuffle_blocks(input: &str, rng: &mut Rng) -> String {
+    let mut blocks = parse::blocks(input)
+        .map(|block| block.text)
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut blocks);
+    blocks.join("\n\n") + "\n"
+}
+
+/// Shuffles the two location lists independently, since neither part of day 1
+/// depends on how the lists are paired.
+fn shuffle_columns(input: &str, rng: &mut Rng) -> String {
+    let (mut left, mut right): (Vec<_>, Vec<_>) = lines(input)
+        .into_iter()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .map(|(left, right)| (left, right.trim()))
+        .unzip();
+
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+
+    join_lines(
+        left.into_iter()
+            .zip(right)
+            .map(|(left, right)| format!("{left}   {right}")),
+    )
+}
+
+/// Shuffles the reports and reverses some of them, which preserves whether
+/// each one is safe.
+fn shuffle_reports(input: &str, rng: &mut Rng) -> String {
+    let mut reports = lines(input)
+        .into_iter()
+        .map(|line| {
+            let mut levels = line.split_whitespace().collect::<Vec<_>>();
+            if rng.coin() {
+                levels.reverse();
+            }
+            levels.join(" ")
+        })
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut reports);
+    join_lines(reports)
+}
+
+/// Applies a random rotation or reflection to a rectangular character grid.
+fn reflect_grid(input: &str, rng: &mut Rng) -> String {
+    let mut grid = lines(input)
+        .into_iter()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let symmetry = rng.below(8);
+
+    if symmetry & 1 != 0 {
+        grid.reverse();
+    }
+
+    if symmetry & 2 != 0 {
+        grid.iter_mut().for_each(|row| row.reverse());
+    }
+
+    if symmetry & 4 != 0 {
+        let ncols = grid.first().map_or(0, Vec::len);
+        grid = (0..ncols)
+            .map(|col| grid.iter().map(|row| row[col]).collect())
+            .collect();
+    }
+
+    join_lines(grid.into_iter().map(String::from_iter))
+}
+
+/// Replaces each plant type with a different uppercase letter.
+fn remap_plants(input: &str, rng: &mut Rng) -> String {
+    let permutation = rng.permutation(26);
+
+    input
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' => (b'A' + permutation[(c as u8 - b'A') as usize] as u8) as char,
+            _ => c,
+        })
+        .collect()
+}
+
+/// Renames the pages with a random permutation of the two-digit numbers, then
+/// shuffles the rules and the updates (but not the pages within an update).
+fn remap_pages(input: &str, rng: &mut Rng) -> String {
+    let permutation = rng.permutation(90);
+    let remap = |page: &str| {
+        page.parse::<usize>()
+            .ok()
+            .filter(|page| (10..100).contains(page))
+            .map_or_else(
+                || page.to_string(),
+                |page| (permutation[page - 10] + 10).to_string(),
+            )
+    };
+
+    let mut blocks = parse::blocks(input);
+    let (Some(rules), Some(updates)) = (blocks.next(), blocks.next()) else {
+        return input.to_string();
+    };
+
+    let mut rules = lines(rules.text)
+        .into_iter()
+        .map(|rule| rule.split('|').map(remap).collect::<Vec<_>>().join("|"))
+        .collect::<Vec<_>>();
+
+    let mut updates = lines(updates.text)
+        .into_iter()
+        .map(|update| update.split(',').map(remap).collect::<Vec<_>>().join(","))
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut rules);
+    rng.shuffle(&mut updates);
+
+    format!("{}\n{}", join_lines(rules), join_lines(updates))
+}
+
+/// Reflects every falling byte through the centre of the memory space, which
+/// swaps the start and exit but keeps the length of every path.
+fn mirror_bytes(input: &str) -> String {
+    let bytes = lines(input)
+        .into_iter()
+        .filter_map(|line| line.split_once(','))
+        .filter_map(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+        .collect::<Vec<_>>();
+
+    let size = bytes.iter().map(|&(x, y)| x.max(y)).max().unwrap_or(0);
+
+    join_lines(
+        bytes
+            .into_iter()
+            .map(|(x, y)| format!("{},{}", size - x, size - y)),
+    )
+}
+
+/// Permutes the five stripe colors and shuffles the towels and designs.
+fn remap_colors(input: &str, rng: &mut Rng) -> String {
+    const COLORS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+    let permutation = rng.permutation(COLORS.len());
+    let remap = |s: &str| -> String {
+        s.chars()
+            .map(|c| match COLORS.iter().position(|&color| color == c) {
+                Some(index) => COLORS[permutation[index]],
+                None => c,
+            })
+            .collect()
+    };
+
+    let mut blocks = parse::blocks(input);
+    let (Some(towels), Some(designs)) = (blocks.next(), blocks.next()) else {
+        return input.to_string();
+    };
+
+    let mut towels = towels.text.split(", ").map(remap).collect::<Vec<_>>();
+    let mut designs = lines(designs.text)
+        .into_iter()
+        .map(remap)
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut towels);
+    rng.shuffle(&mut designs);
+
+    format!("{}\n\n{}", towels.join(", "), join_lines(designs))
+}
+
+/// Renames every computer with a random permutation of the two-letter names,
+/// then shuffles the connections and the order of each pair.
+fn remap_computers(input: &str, rng: &mut Rng) -> String {
+    let permutation = rng.permutation(26 * 26);
+    let remap = |name: &str| match name.as_bytes() {
+        &[a @ b'a'..=b'z', b @ b'a'..=b'z'] => {
+            let index = permutation[(a - b'a') as usize * 26 + (b - b'a') as usize];
+            let bytes = [b'a' + (index / 26) as u8, b'a' + (index % 26) as u8];
+            String::from_utf8(bytes.to_vec()).unwrap()
+        }
+        _ => name.to_string(),
+    };
+
+    let mut connections = lines(input)
+        .into_iter()
+        .filter_map(|line| line.split_once('-'))
+        .map(|(a, b)| match rng.coin() {
+            true => format!("{}-{}", remap(a), remap(b)),
+            false => format!("{}-{}", remap(b), remap(a)),
+        })
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut connections);
+    join_lines(connections)
+}
+
+/// Gives every internal wire (anything other than an `x`, `y`, or `z` wire) a
+/// fresh random name, then shuffles the gates and the order of their inputs.
+fn rename_wires(input: &str, rng: &mut Rng) -> String {
+    let mut blocks = parse::blocks(input);
+    let (Some(initial), Some(gates)) = (blocks.next(), blocks.next()) else {
+        return input.to_string();
+    };
+
+    let mut names = std::collections::HashMap::new();
+    let mut taken = std::collections::HashSet::new();
+    let mut rename = |wire: &str, rng: &mut Rng| -> String {
+        if wire.starts_with(['x', 'y', 'z']) {
+            return wire.to_string();
+        }
+
+        names
+            .entry(wire.to_string())
+            .or_insert_with(|| loop {
+                // avoid the reserved prefixes so renamed wires stay internal
+                let name = (0..3)
+                    .map(|_| (b'a' + rng.below(23) as u8) as char)
+                    .collect::<String>();
+
+                if taken.insert(name.clone()) {
+                    break name;
+                }
+            })
+            .clone()
+    };
+
+    let mut gates = lines(gates.text)
+        .into_iter()
+        .filter_map(|line| {
+            let (expr, output) = line.split_once(" -> ")?;
+            let mut parts = expr.split_whitespace();
+            let (a, op, b) = (parts.next()?, parts.next()?, parts.next()?);
+            let (a, b) = if rng.coin() { (a, b) } else { (b, a) };
+
+            Some(format!(
+                "{} {op} {} -> {}",
+                rename(a, rng),
+                rename(b, rng),
+                rename(output, rng)
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    rng.shuffle(&mut gates);
+    format!("{}\n\n{}", initial.text, join_lines(gates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that scrambling changes the input but not its answers.
+    fn assert_answers_preserved(day: u8, input: &str) {
+        let Some(solution) = crate::find(2024, day) else {
+            return;
+        };
+
+        for seed in 0..4 {
+            let scrambled = scramble(day, input, seed).unwrap();
+            assert_ne!(scrambled.trim(), input.trim());
+            assert_eq!(
+                solution.solve_part_1(&scrambled),
+                solution.solve_part_1(input)
+            );
+            assert_eq!(
+                solution.solve_part_2(&scrambled),
+                solution.solve_part_2(input)
+            );
+        }
+    }
+
+    #[test]
+    fn shuffling_preserves_answers() {
+        assert_answers_preserved(1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+        assert_answers_preserved(
+            2,
+            "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n",
+        );
+        assert_answers_preserved(11, "125 17 3 0 9");
+    }
+
+    #[test]
+    fn reflecting_preserves_answers() {
+        assert_answers_preserved(
+            12,
+            "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\n\
+             VVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE\n",
+        );
+    }
+
+    #[test]
+    fn renaming_keeps_structure() {
+        let input = "kh-tc\nqp-kh\nde-cg\nka-co\nta-co\nde-co\nka-de\nta-ka\nde-ta\n";
+        let scrambled = scramble(23, input, 7).unwrap();
+        assert_eq!(lines(&scrambled).len(), lines(input).len());
+
+        let Some(solution) = crate::find(2024, 23) else {
+            return;
+        };
+
+        // the password changes, but the size of the largest clique doesn't
+        let password = solution
+            .solve_part_2(&scrambled)
+            .unwrap()
+            .unwrap()
+            .to_string();
+        assert_eq!(password.split(',').count(), 4);
+    }
+
+    #[test]
+    fn unsupported_days() {
+        assert_eq!(scramble(6, "", 0), None);
+        assert_eq!(scramble(25, "", 0), None);
+    }
+}
@@ -0,0 +1,379 @@
+//! Shortest paths through graphs given by a successor function, with
+//! breadth-first search, Dijkstra's algorithm, and A*.
+//!
+//! A node can be anything hashable, like a position or a position and a
+//! direction, and a search only visits the nodes that its successor function
+//! leads to, so the graph never has to be built up front. [`bfs`] and
+//! [`dijkstra`] explore everything reachable from the start, and return the
+//! [`Reached`] nodes with their distances and every predecessor on a shortest
+//! path to each, from which the paths themselves can be recovered. [`astar`]
+//! stops at the first goal it reaches, and returns just the path to it.
+
+use alloc::{
+    collections::{BinaryHeap, VecDeque},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, hash::Hash, ops::Add};
+
+use hashbrown::hash_map::Entry;
+
+use crate::hash::{HashMap, HashSet};
+
+/// The cost of following an edge, and so the distance to a node. The default
+/// value is the distance from the start to itself.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// The nodes reached by a search, each with its distance from the start and
+/// its predecessors on the shortest paths to it.
+#[derive(Debug, Clone)]
+pub struct Reached<N, C> {
+    start: N,
+    nodes: HashMap<N, (C, Vec<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Reached<N, C> {
+    fn new(start: N) -> Self {
+        let mut nodes = HashMap::default();
+        nodes.insert(start.clone(), (C::default(), Vec::new()));
+
+        Self { start, nodes }
+    }
+
+    /// Records that `node` can be reached for `cost` from `predecessor`,
+    /// returning `true` if that's cheaper than any way found so far.
+    fn relax(&mut self, node: N, cost: C, predecessor: &N) -> bool {
+        match self.nodes.entry(node) {
+            Entry::Vacant(entry) => {
+                entry.insert((cost, vec![predecessor.clone()]));
+                true
+            }
+            Entry::Occupied(mut entry) => {
+                let (best, predecessors) = entry.get_mut();
+
+                match cost.cmp(best) {
+                    Ordering::Less => {
+                        *best = cost;
+                        predecessors.clear();
+                        predecessors.push(predecessor.clone());
+                        true
+                    }
+                    Ordering::Equal => {
+                        predecessors.push(predecessor.clone());
+                        false
+                    }
+                    Ordering::Greater => false,
+                }
+            }
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// Returns the number of nodes reached, including the start.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    /// Returns the distance from the start to `node`, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(distance, _)| distance)
+    }
+
+    /// Returns every node that comes just before `node` on a shortest path to
+    /// it, which is none for a node that wasn't reached. The start has none
+    /// unless an edge of zero cost leads back to it.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.nodes
+            .get(node)
+            .map_or(&[], |(_, predecessors)| predecessors)
+    }
+
+    /// Returns an iterator over the nodes reached and their distances, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.nodes
+            .iter()
+            .map(|(node, &(distance, _))| (node, distance))
+    }
+
+    /// Returns a shortest path from the start to `node`, including both, if
+    /// `node` was reached. Where there are several, this picks one.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut current = node;
+
+        // the start can have predecessors of its own, so the walk ends there
+        // rather than where the predecessors run out
+        while *current != self.start {
+            current = self.predecessors(current).first()?;
+            path.push(current.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns every node on at least one shortest path from the start to any
+    /// of `ends`.
+    pub fn on_shortest_paths(&self, ends: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut seen = HashSet::default();
+        let mut stack = ends
+            .into_iter()
+            .filter(|node| self.contains(node))
+            .collect::<Vec<_>>();
+
+        while let Some(node) = stack.pop() {
+            if !seen.contains(&node) {
+                stack.extend_from_slice(self.predecessors(&node));
+                seen.insert(node);
+            }
+        }
+
+        seen
+    }
+}
+
+/// A node waiting to be expanded, ordered so that the [`BinaryHeap`] pops the
+/// lowest priority first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // among equal priorities, the node furthest along is likely closest to
+        // a goal
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+/// Finds the fewest edges from `start` to every node reachable from it, where
+/// `successors` returns the nodes that each one has edges to.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if reached.relax(next.clone(), distance + 1, &node) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Finds the cheapest paths from `start` to every node reachable from it,
+/// where `successors` returns the nodes that each one has edges to with the
+/// cost of each edge.
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, _) = search(start, successors, |_| C::default(), |_| false);
+    reached
+}
+
+/// Finds a cheapest path from `start` to a node that `is_goal`, returning it
+/// (including both ends) with its cost, or `None` if no goal is reachable.
+///
+/// `heuristic` estimates the cost from a node to the nearest goal, and the path
+/// is only guaranteed to be the cheapest if it never overestimates, and never
+/// drops by more than an edge's cost across that edge.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, goal) = search(start, successors, heuristic, is_goal);
+    let goal = goal?;
+
+    Some((reached.path_to(&goal)?, reached.distance(&goal)?))
+}
+
+/// Expands nodes in order of their cost plus `heuristic` until the queue runs
+/// out or a node that `is_goal` is expanded, which is returned as well.
+fn search<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new(start.clone());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        // a node is queued again each time a cheaper way to it is found, so
+        // only its cheapest entry is expanded
+        if reached.distance(&node).is_some_and(|best| cost > best) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return (reached, Some(node));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+
+            if reached.relax(next.clone(), next_cost, &node) {
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    (reached, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The open cells next to `(row, col)` in `maze`, where walls are `#`.
+    fn open_neighbours(maze: &[&str], (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+        [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ]
+        .into_iter()
+        .filter(|&(row, col)| {
+            maze.get(row)
+                .and_then(|line| line.as_bytes().get(col))
+                .is_some_and(|&cell| cell != b'#')
+        })
+        .collect()
+    }
+
+    const MAZE: &[&str] = &["...#", ".#..", "...#", "#..."];
+
+    #[test]
+    fn breadth_first() {
+        let reached = bfs((0, 0), |&position| open_neighbours(MAZE, position));
+
+        assert_eq!(reached.len(), 12);
+        assert_eq!(reached.distance(&(3, 3)), Some(6));
+        assert_eq!(reached.distance(&(0, 3)), None);
+
+        // the two routes around the wall at (1, 1) meet at (2, 2)
+        assert_eq!(reached.predecessors(&(2, 2)).len(), 2);
+        assert_eq!(reached.on_shortest_paths([(2, 2)]).len(), 8);
+
+        let path = reached.path_to(&(3, 3)).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), ((0, 0), (3, 3)));
+        assert!(path
+            .windows(2)
+            .all(|w| open_neighbours(MAZE, w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn weighted() {
+        // going right costs 1 and going down costs 10, so the cheapest paths
+        // stay in the top row as long as they can
+        let costly_down = |&position: &(usize, usize)| {
+            open_neighbours(MAZE, position)
+                .into_iter()
+                .map(move |next| (next, if next.0 > position.0 { 10 } else { 1 }))
+        };
+
+        let reached = dijkstra((0, 0), costly_down);
+        assert_eq!(reached.distance(&(1, 2)), Some(12));
+        assert_eq!(
+            reached.path_to(&(1, 2)).unwrap()[..3],
+            [(0, 0), (0, 1), (0, 2)]
+        );
+        assert_eq!(reached.on_shortest_paths([(1, 2)]).len(), 4);
+
+        let manhattan = |&(row, col): &(usize, usize)| row.abs_diff(3) + col.abs_diff(3);
+        let (path, cost) = astar((0, 0), costly_down, manhattan, |&node| node == (3, 3)).unwrap();
+        assert_eq!(cost, reached.distance(&(3, 3)).unwrap());
+        assert_eq!(path.len(), 7);
+
+        assert_eq!(
+            astar((0, 0), costly_down, manhattan, |&node| node == (0, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn zero_cost_cycle() {
+        // 0 and 1 are joined both ways for free, so each is a predecessor of
+        // the other
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 5)],
+            _ => vec![],
+        };
+
+        let reached = dijkstra(0, edges);
+        assert_eq!(reached.predecessors(&0), [1]);
+        assert_eq!(reached.path_to(&0), Some(vec![0]));
+        assert_eq!(reached.path_to(&2), Some(vec![0, 1, 2]));
+        assert_eq!(reached.path_to(&3), None);
+
+        assert_eq!(
+            astar(0, edges, |_| 0, |&node| node == 2),
+            Some((vec![0, 1, 2], 5))
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Solving days from any [`BufRead`] rather than a `&str`, without holding the
+//! whole input in memory.
+//!
+//! Only some days can do this: those whose parts look at one line at a time
+//! (or, like day 1, only need a compact summary of the lines) have a
+//! `solve_reader` function alongside their usual `parse`, which reads the
+//! input with a [`LineReader`] and returns the answers to both parts.
+
+use std::io::{self, BufRead};
+
+use thiserror::Error;
+
+use crate::error::AocError;
+
+/// Why a streamed input couldn't be solved.
+#[derive(Debug, Error)]
+pub enum StreamError {
+    #[error("failed to read the input: {0}")]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Invalid(#[from] AocError),
+}
+
+/// Reads an input a line at a time, reusing a single buffer for its lines.
+#[derive(Debug)]
+pub struct LineReader<R> {
+    reader: R,
+    line: String,
+    /// The number of the line in `line`, counting from 1.
+    number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            number: 0,
+        }
+    }
+
+    /// Reads the next line, without its line ending, or returns `None` at the
+    /// end of the input.
+    pub fn next_line(&mut self) -> Result<Option<&str>, StreamError> {
+        self.line.clear();
+        if self.reader.read_line(&mut self.line)? == 0 {
+            return Ok(None);
+        }
+
+        self.number += 1;
+        let line = self.line.strip_suffix('\n').unwrap_or(&self.line);
+        Ok(Some(line.strip_suffix('\r').unwrap_or(line)))
+    }
+
+    /// Moves a parse error in the last line read, located relative to that
+    /// line, to where it is in the whole input.
+    pub fn locate(&self, mut error: AocError) -> AocError {
+        if let Some((line, _)) = error.location_mut() {
+            *line += self.number - 1;
+        }
+
+        error
+    }
+
+    /// Reads every remaining line, passing each one to `f` and locating any
+    /// error it returns.
+    pub fn for_each(
+        &mut self,
+        mut f: impl FnMut(&str) -> Result<(), AocError>,
+    ) -> Result<(), StreamError> {
+        while let Some(line) = self.next_line()? {
+            if let Err(error) = f(line) {
+                return Err(self.locate(error).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_numbers() {
+        let mut reader = LineReader::new("first\r\nsecond\n\nfourth".as_bytes());
+        let mut lines = Vec::new();
+        let error = reader.for_each(|line| {
+            lines.push(line.to_string());
+            match line {
+                "fourth" => Err(AocError::at(line, &line[2..], "no")),
+                _ => Ok(()),
+            }
+        });
+
+        assert_eq!(lines, ["first", "second", "", "fourth"]);
+        assert_eq!(error.unwrap_err().to_string(), "line 4, column 3: no");
+        assert!(reader.next_line().unwrap().is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Summaries of repeated timings, and a CSV log of them for charting
+//! performance over time in other tools.
+//!
+//! Both the `aoc bench` command and the `days` benchmark append to the log,
+//! with a line for each part they measured:
+//!
+//! ```csv
+//! timestamp,year,day,part,runs,mean_ns,p95_ns,allocations
+//! 1733011200,2024,1,1,10,52134,58210,3
+//! ```
+//!
+//! The timestamp is in seconds since the Unix epoch, and the allocations are
+//! left empty where they weren't counted.
+
+use std::{
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The header of a timings CSV file.
+pub const CSV_HEADER: &str = "timestamp,year,day,part,runs,mean_ns,p95_ns,allocations";
+
+/// A summary of the times taken to solve one part of a day several times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub runs: usize,
+    pub mean: Duration,
+    /// The 95th percentile, by the nearest-rank method.
+    pub p95: Duration,
+    /// The number of heap allocations made by each run, if they were counted.
+    pub allocations: Option<usize>,
+}
+
+impl Timing {
+    /// Summarizes the times of several runs of a part, returning `None` if
+    /// there weren't any.
+    pub fn from_samples(
+        year: u16,
+        day: u8,
+        part: u8,
+        samples: &[Duration],
+        allocations: Option<usize>,
+    ) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let rank = (sorted.len() * 95).div_ceil(100);
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+
+        Some(Self {
+            year,
+            day,
+            part,
+            runs: samples.len(),
+            mean,
+            p95: sorted[rank - 1],
+            allocations,
+        })
+    }
+
+    /// Formats this timing as a line of CSV, recorded at `timestamp`.
+    fn to_csv(&self, timestamp: u64) -> String {
+        let allocations = self.allocations.map_or(String::new(), |n| n.to_string());
+
+        format!(
+            "{timestamp},{},{},{},{},{},{},{allocations}",
+            self.year,
+            self.day,
+            self.part,
+            self.runs,
+            self.mean.as_nanos(),
+            self.p95.as_nanos()
+        )
+    }
+}
+
+/// Appends `timings` to the CSV file at `path`, creating it (with a header)
+/// if it doesn't exist yet.
+pub fn append_csv(path: &Path, timings: &[Timing]) -> std::io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    let mut lines = String::new();
+    if !path.exists() {
+        lines.push_str(CSV_HEADER);
+        lines.push('\n');
+    }
+
+    for timing in timings {
+        lines.push_str(&timing.to_csv(timestamp));
+        lines.push('\n');
+    }
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(lines.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timing_summary() {
+        let samples = (1..=20)
+            .rev()
+            .map(Duration::from_micros)
+            .collect::<Vec<_>>();
+        let timing = Timing::from_samples(2024, 1, 2, &samples, Some(3)).unwrap();
+
+        assert_eq!(timing.runs, 20);
+        assert_eq!(timing.mean, Duration::from_nanos(10_500));
+        assert_eq!(timing.p95, Duration::from_micros(19));
+        assert_eq!(
+            timing.to_csv(1733011200),
+            "1733011200,2024,1,2,20,10500,19000,3"
+        );
+
+        let timing = Timing::from_samples(2024, 1, 1, &samples[..1], None).unwrap();
+        assert_eq!(timing.p95, Duration::from_micros(20));
+        assert!(timing.to_csv(0).ends_with(",20000,20000,"));
+
+        assert_eq!(Timing::from_samples(2024, 1, 1, &[], None), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! Bindings that expose the 2024 days to JavaScript, for the page in `web/`.
+//!
+//! Browsers can't spawn threads for rayon, so build without it (and without
+//! the CLI), then generate the JavaScript glue next to the page:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --crate-type cdylib \
+//!     --no-default-features --features wasm,all-days,fast-hash,logos,nalgebra
+//! wasm-bindgen --target web --out-dir web/pkg \
+//!     target/wasm32-unknown-unknown/release/aoc_2024.wasm
+//! ```
+//!
+//! A page that only needs a few days can replace `all-days` with their `dayNN`
+//! features, and drop `logos` and `nalgebra` for their hand-rolled fallbacks,
+//! to leave those dependencies out of the build entirely.
+
+use wasm_bindgen::prelude::*;
+
+/// Returns the numbers of the days that were compiled in.
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    crate::days(2024).map(|day| day.number).collect()
+}
+
+/// Solves `part` of `day` for `input`, throwing an error if the day or part
+/// doesn't exist or the input can't be parsed.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    answer(day, part, input).map_err(|message| JsError::new(&message))
+}
+
+/// Solves `part` of `day` for `input`, describing anything that goes wrong.
+fn answer(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solution = crate::find(2024, day).ok_or_else(|| format!("day {day} is not compiled in"))?;
+
+    let answer = match part {
+        1 => solution.solve_part_1(input).map(Some),
+        2 => solution.solve_part_2(input),
+        _ => return Err(format!("there is no part {part}")),
+    };
+
+    match answer.map_err(|err| err.to_string())? {
+        Some(answer) => Ok(answer.to_string()),
+        None => Err(format!("day {day} has no part {part}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_and_errors() {
+        if crate::find(2024, 1).is_some() {
+            assert_eq!(answer(1, 1, "1 3\n4 2\n").as_deref(), Ok("2"));
+            assert_eq!(answer(1, 3, ""), Err("there is no part 3".to_string()));
+        }
+
+        assert_eq!(
+            answer(26, 1, ""),
+            Err("day 26 is not compiled in".to_string())
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Runs the `aoc` binary itself, for behaviour that only shows in its output.
+
+use std::{path::PathBuf, process::Command};
+
+use serde_json::Value;
+
+/// Writes `contents` to a file of its own in the temporary directory.
+fn write_input(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-cli-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Runs `aoc` with `args`, returning each line it printed to stdout as JSON
+/// and everything it printed to stderr.
+fn run_json(args: &[&str]) -> (Vec<Value>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("--format")
+        .arg("json")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    let lines = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|_| panic!("not JSON: {line}")))
+        .collect();
+
+    (lines, String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn json_parse_errors() {
+    let path = write_input("day01.txt", "3   4\n4 three\n");
+    let path = path.to_str().unwrap();
+
+    for args in [
+        &["run", "--day", "1", "--input", path][..],
+        &["batch", "--day", "1", path][..],
+    ] {
+        let (lines, stderr) = run_json(args);
+
+        assert!(!stderr.contains("error"), "{stderr}");
+        assert!(lines
+            .iter()
+            .any(|line| line["day"] == 1 && line["error"].as_str().unwrap().contains("line 2")));
+    }
+}